@@ -0,0 +1,133 @@
+use crate::board::Board;
+use crate::evaluate::simple_evaluator::SimpleEvaluator;
+use crate::search::logger::NullLogger;
+use crate::search::Search;
+use std::fmt::Write;
+use std::time::Instant;
+
+/// The depth every position of the suite is searched to
+pub const BENCH_DEPTH: usize = 5;
+
+/// A fixed mix of openings, middlegames, and endgames whose node total fingerprints the search
+const BENCH_POSITIONS: [&str; 8] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "2r3k1/pp3ppp/2n5/3p4/3P4/2N5/PP3PPP/2R3K1 w - - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "8/8/4k3/3p4/3P4/4K3/8/8 w - - 0 1",
+    "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1",
+];
+
+/// Searches every position of the fixed suite and totals the nodes visited
+///
+/// Each position gets a fresh search with its own transposition table and no time limit, so the
+/// total only changes when the search itself does.
+///
+/// # Arguments
+///
+/// * `depth` - The depth to search each position to
+///
+/// # Returns
+///
+/// * `String` - One line per position with its node count and best move, then the total nodes
+///   and the nodes searched per second, ending with `Bench: <total nodes>`
+///
+/// # Example
+/// ```
+/// println!("{}", bench(BENCH_DEPTH));
+/// ```
+pub fn bench(depth: usize) -> String {
+    let start = Instant::now();
+    let (mut report, total_nodes) = run_suite(&BENCH_POSITIONS, depth);
+    let elapsed_ms = start.elapsed().as_millis().max(1);
+    let nps = u128::from(total_nodes) * 1000 / elapsed_ms;
+
+    writeln!(report, "{total_nodes} nodes {nps} nps").unwrap();
+    write!(report, "Bench: {total_nodes}").unwrap();
+    report
+}
+
+/// Searches each of `positions` to `depth` and reports the nodes each search visited
+///
+/// # Arguments
+///
+/// * `positions` - The FEN strings of the positions to search
+/// * `depth` - The depth to search each position to
+///
+/// # Returns
+///
+/// * `(String, u64)` - One line per position with its node count and best move, and the total
+///   number of nodes visited
+fn run_suite(positions: &[&str], depth: usize) -> (String, u64) {
+    let evaluator = SimpleEvaluator::new();
+    let mut report = String::new();
+    let mut total_nodes = 0;
+
+    for (idx, fen) in positions.iter().enumerate() {
+        let board = Board::from_fen(fen);
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(NullLogger));
+        let best_move = search
+            .search(Some(depth))
+            .expect("Every bench position has a legal move");
+
+        total_nodes += search.get_nodes();
+        writeln!(
+            report,
+            "Position {}/{}: {} nodes, bestmove {best_move}",
+            idx + 1,
+            positions.len(),
+            search.get_nodes()
+        )
+        .unwrap();
+    }
+
+    (report, total_nodes)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_bench_ends_with_deterministic_total() {
+        let report = bench(3);
+        let total: u64 = report
+            .lines()
+            .filter(|line| line.starts_with("Position "))
+            .filter_map(|line| line.split_whitespace().nth(2)?.parse::<u64>().ok())
+            .sum();
+
+        assert_eq!(
+            report.lines().last(),
+            Some(format!("Bench: {total}").as_str())
+        );
+        assert_eq!(report.lines().last(), bench(3).lines().last());
+    }
+
+    #[test]
+    fn test_bench_reports_nps_before_total() {
+        let report = bench(3);
+        let lines: Vec<&str> = report.lines().collect();
+        let total = lines[lines.len() - 1].trim_start_matches("Bench: ");
+
+        assert_eq!(lines.len(), BENCH_POSITIONS.len() + 2);
+        assert!(lines[lines.len() - 2].starts_with(&format!("{total} nodes ")));
+        assert!(lines[lines.len() - 2].ends_with(" nps"));
+    }
+
+    #[test]
+    fn test_single_position_suite_is_deterministic() {
+        let suite = [BENCH_POSITIONS[1]];
+        let (report, nodes) = run_suite(&suite, 4);
+
+        assert!(nodes > 0);
+        assert!(report.starts_with(&format!("Position 1/1: {nodes} nodes, bestmove ")));
+        assert_eq!(run_suite(&suite, 4), (report, nodes));
+    }
+}
@@ -1,20 +1,65 @@
 use std::fmt;
 pub mod bitboard;
 pub mod boardbuilder;
+pub mod move_error;
+pub mod perft;
 pub mod piece;
 mod piece_bitboards;
 pub mod ply;
+pub mod san;
+pub mod see;
 pub mod serialize;
 pub mod square;
+pub mod zobrist;
 
 use bitboard::Bitboard;
 #[allow(clippy::module_name_repetitions)]
 pub use boardbuilder::BoardBuilder;
-use piece::{Color, Kind};
+use move_error::MoveError;
+use piece::{Color, Kind, PIECE_VALUES};
 use piece_bitboards::PieceBitboards;
-use ply::castling::{CastlingKind, CastlingStatus};
-pub use ply::Ply;
-use square::Square;
+use ply::castling::{CastlingKind, CastlingRooks, CastlingStatus};
+pub use ply::{MoveList, Ply};
+use square::rays::{Rays, RAYS};
+use square::{Direction, Square};
+use zobrist::Zobrist;
+
+use crate::evaluate::params::EvalParams;
+use crate::evaluate::piece_square_tables::piece_square_value;
+
+/// The material phase of a board with all of its starting pieces
+pub const MAX_PHASE: u32 = 24;
+
+/// Returns the piece-square value of a piece from white's point of view, with the default tables
+/// the board keeps its running totals of
+const fn signed_piece_square_value(piece: Kind, square: Square) -> (i64, i64) {
+    let (middlegame, endgame) = piece_square_value(piece, square, &EvalParams::DEFAULT);
+    match piece.get_color() {
+        Color::White => (middlegame, endgame),
+        Color::Black => (-middlegame, -endgame),
+    }
+}
+
+/// Returns the squares of `rank` from one file to another, both included, in either order
+const fn rank_span(rank: u8, from: u8, to: u8) -> Bitboard {
+    let (low, high) = if from < to { (from, to) } else { (to, from) };
+    let files = (1u64 << (high + 1)) - (1u64 << low);
+    Bitboard::new(files << (rank * 8))
+}
+
+/// Every dark square of the board, starting with a1
+const DARK_SQUARES: Bitboard = Bitboard::new(0xAA55_AA55_AA55_AA55);
+
+/// Which pawns stand on a file, as seen by one side
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    /// Neither side has a pawn on the file
+    Open,
+    /// Only the opponent has pawns on the file
+    HalfOpen,
+    /// The side has at least one of its own pawns on the file
+    Closed,
+}
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub enum GameState {
@@ -30,7 +75,7 @@ pub enum GameState {
 
 /// A board object, representing all of the state of the game
 /// Starts at bottom left corner of a chess board (a1), wrapping left to right on each row
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 pub struct Board {
     pub current_turn: Color,
     pub fullmove_counter: u16,
@@ -41,8 +86,36 @@ pub struct Board {
     pub bitboards: PieceBitboards,
 
     history: Vec<Ply>,
+
+    zobrist_key: u64,
+    key_history: Vec<u64>,
+
+    in_check: bool,
+    check_history: Vec<bool>,
+
+    mg_score: i64,
+    eg_score: i64,
+
+    castling_rooks: CastlingRooks,
+    chess960: bool,
+}
+
+impl PartialEq for Board {
+    /// Compares the state of the game. The Zobrist keys and check flags are derived from that
+    /// state, so they are left out of the comparison.
+    fn eq(&self, other: &Self) -> bool {
+        self.current_turn == other.current_turn
+            && self.fullmove_counter == other.fullmove_counter
+            && self.game_state == other.game_state
+            && self.en_passant_file == other.en_passant_file
+            && self.bitboards == other.bitboards
+            && self.history == other.history
+            && self.castling_rooks == other.castling_rooks
+    }
 }
 
+impl Eq for Board {}
+
 impl Default for Board {
     /// Creates a new board object that represents the starting board state in a normal game
     ///
@@ -51,7 +124,7 @@ impl Default for Board {
     /// let board = Board::default();
     /// ```
     fn default() -> Self {
-        Self {
+        let mut board = Self {
             current_turn: Color::White,
             fullmove_counter: 1,
             game_state: GameState::InProgress,
@@ -61,7 +134,24 @@ impl Default for Board {
             en_passant_file: None,
 
             history: vec![Ply::default()],
-        }
+
+            zobrist_key: 0,
+            key_history: Vec::new(),
+
+            in_check: false,
+            check_history: Vec::new(),
+
+            mg_score: 0,
+            eg_score: 0,
+
+            castling_rooks: CastlingRooks::new(),
+            chess960: false,
+        };
+        board.zobrist_key = board.compute_zobrist_key();
+        board.in_check = board.is_in_check(board.current_turn);
+        (board.mg_score, board.eg_score) = board.compute_piece_square_score();
+
+        board
     }
 }
 
@@ -106,7 +196,8 @@ impl Board {
     /// leave the king in check, or moves that illegally capture the player's
     /// own pieces, move through their own pieces, etc. This function is not
     /// usually called on its own. It is normally paired with `filter_moves()`
-    /// to create a legal moveset of the board.
+    /// to create a legal moveset of the board. In double check, only the king's
+    /// moves are generated, and a pinned piece only moves along its pin.
     ///
     /// # Examples
     /// ```
@@ -114,37 +205,193 @@ impl Board {
     /// let movelist = board.get_all_moves(Square::new("a2"));
     /// ```
     fn get_all_moves(&self) -> Vec<Ply> {
+        let mut own_pieces = match self.current_turn {
+            Color::White => self.bitboards.white_pieces,
+            Color::Black => self.bitboards.black_pieces,
+        };
+        // Only the king can get out of a double check
+        if self.checker_count(self.current_turn) > 1 {
+            own_pieces &= self.bitboards.get_bitboard(Kind::King(self.current_turn));
+        }
+
+        let pins: Vec<(Square, Bitboard)> = self.pins(self.current_turn).collect();
+
         let mut all_moves = Vec::new();
+        for square in own_pieces {
+            let piece = self
+                .get_piece(square)
+                .expect("No piece found where the bitboard claimed a piece was!");
+            let allowed = Self::pin_mask(&pins, square);
+
+            all_moves.extend(
+                piece
+                    .get_moveset(square, self)
+                    .into_iter()
+                    .filter(|mv| !(allowed & Bitboard::from(mv.dest)).is_empty())
+                    .map(|mv| self.with_captured_piece(mv)),
+            );
+        }
 
-        for square_idx in 0..64u8 {
-            let square = Square::from(square_idx);
-            if let Some(piece) = self.get_piece(square) {
-                if self.current_turn != piece.get_color() {
-                    continue;
+        all_moves
+    }
+
+    /// Returns the squares the piece on `square` may move to without leaving the line to its king,
+    /// given the pins of its side, which is every square for a piece that is not pinned
+    fn pin_mask(pins: &[(Square, Bitboard)], square: Square) -> Bitboard {
+        pins.iter()
+            .find_map(|&(pinned, ray)| (pinned == square).then_some(ray))
+            .unwrap_or(Bitboard::new(u64::MAX))
+    }
+
+    /// Returns every potential capture for the current side, including en passant and promotions
+    /// that capture
+    ///
+    /// Only the squares each piece attacks that hold an enemy piece are visited, so this is much
+    /// cheaper than filtering `get_all_moves`. Like `get_all_moves`, pinned pieces only capture
+    /// along their pin, but the captures may still leave the king in check.
+    ///
+    /// # Returns
+    ///
+    /// * `MoveList` - The pseudo-legal captures, with their captured piece filled in
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+    /// let captures = board.get_capture_moves();
+    /// assert_eq!(captures.len(), 1);
+    /// assert_eq!(captures[0].to_string(), "e4d5");
+    /// ```
+    pub fn get_capture_moves(&self) -> MoveList {
+        const PROMOTIONS: [fn(Color) -> Kind; 4] =
+            [Kind::Queen, Kind::Rook, Kind::Knight, Kind::Bishop];
+
+        let color = self.current_turn;
+        let (mut own_pieces, enemy_pieces, mut own_pawns, promotion_rank) = match color {
+            Color::White => (
+                self.bitboards.white_pieces,
+                self.bitboards.black_pieces,
+                self.bitboards.white_pawns,
+                6,
+            ),
+            Color::Black => (
+                self.bitboards.black_pieces,
+                self.bitboards.white_pieces,
+                self.bitboards.black_pawns,
+                1,
+            ),
+        };
+
+        if self.checker_count(color) > 1 {
+            own_pieces &= self.bitboards.get_bitboard(Kind::King(color));
+            own_pawns = Bitboard::new(0);
+        }
+
+        let pins: Vec<(Square, Bitboard)> = self.pins(color).collect();
+
+        let mut captures = MoveList::new();
+        for square in own_pieces {
+            let piece = self
+                .get_piece(square)
+                .expect("No piece found where the bitboard claimed a piece was!");
+            let promotes = matches!(piece, Kind::Pawn(_)) && square.rank == promotion_rank;
+            let allowed = Self::pin_mask(&pins, square);
+
+            for dest in piece.get_attacks(square, self) & enemy_pieces & allowed {
+                let victim = self
+                    .get_piece(dest)
+                    .expect("No piece found where the bitboard claimed a piece was!");
+                if promotes {
+                    captures.extend(PROMOTIONS.map(|promotion| {
+                        Ply::builder(square, dest)
+                            .captured(victim)
+                            .promoted_to(promotion(color))
+                            .build()
+                    }));
+                } else {
+                    captures.push(Ply::builder(square, dest).captured(victim).build());
                 }
+            }
+        }
 
-                all_moves.append(
-                    &mut piece
-                        .get_moveset(square, self)
-                        .into_iter()
-                        .map(|mut mv| {
-                            if mv.en_passant {
-                                mv.captured_piece = self.get_piece(Square {
-                                    rank: mv.start.rank,
-                                    file: mv.dest.file,
-                                });
-                            } else {
-                                mv.captured_piece = self.get_piece(mv.dest);
-                            }
-
-                            mv
-                        })
-                        .collect::<Vec<Ply>>(),
+        if let Some(file) = self.en_passant_file {
+            let target = Square {
+                rank: match color {
+                    Color::White => 5,
+                    Color::Black => 2,
+                },
+                file,
+            };
+            // The pawns that could take on the target are those an enemy pawn there would attack
+            let attackers = Kind::Pawn(color.opposite()).get_attacks(target, self) & own_pawns;
+            for square in attackers.into_iter().filter(|&square| {
+                !(Self::pin_mask(&pins, square) & Bitboard::from(target)).is_empty()
+            }) {
+                captures.push(
+                    Ply::builder(square, target)
+                        .en_passant(true)
+                        .captured(Kind::Pawn(color.opposite()))
+                        .build(),
                 );
             }
         }
 
-        all_moves
+        captures
+    }
+
+    /// Fills in the piece `mv` captures, including a pawn taken en passant
+    ///
+    /// Castling never captures, even when Chess960 writes it as the king taking its own rook.
+    fn with_captured_piece(&self, mut mv: Ply) -> Ply {
+        mv.captured_piece = if mv.is_castles {
+            None
+        } else if mv.en_passant {
+            self.get_piece(Square {
+                rank: mv.start.rank,
+                file: mv.dest.file,
+            })
+        } else {
+            self.get_piece(mv.dest)
+        };
+
+        mv
+    }
+
+    #[allow(dead_code)]
+    /// Returns how many pseudo-legal moves each kind of piece of the current side generates
+    ///
+    /// This is a debugging aid for move generation: a generator producing too many or too few
+    /// moves stands out in the per-piece breakdown.
+    ///
+    /// # Returns
+    ///
+    /// * `[(Kind, usize); 6]` - The number of moves for pawns, knights, bishops, rooks, queens,
+    ///   and the king, in that order
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let counts = board.get_move_counts_by_piece();
+    /// assert_eq!((Kind::Pawn(Color::White), 16), counts[0]);
+    /// ```
+    pub fn get_move_counts_by_piece(&self) -> [(Kind, usize); 6] {
+        let color = self.current_turn;
+        let mut counts = [
+            (Kind::Pawn(color), 0),
+            (Kind::Knight(color), 0),
+            (Kind::Bishop(color), 0),
+            (Kind::Rook(color), 0),
+            (Kind::Queen(color), 0),
+            (Kind::King(color), 0),
+        ];
+
+        for mv in self.get_all_moves() {
+            let piece = self.get_piece(mv.start);
+            if let Some((_, count)) = counts.iter_mut().find(|(kind, _)| Some(*kind) == piece) {
+                *count += 1;
+            }
+        }
+
+        counts
     }
 
     /// Returns a list of all legal moves for the current side
@@ -155,199 +402,1185 @@ impl Board {
     /// let movelist = board.get_all_moves(Square::new("a2"));
     /// ```
     pub fn get_legal_moves(&mut self) -> Vec<Ply> {
+        let pinned = self.pinned_pieces(self.current_turn);
+        let evasions = self.check_evasion_squares(self.current_turn);
+
         self.get_all_moves()
             .into_iter()
-            .filter(|mv| self.is_legal_move(*mv).is_ok())
+            .filter(|mv| self.is_legal_with(*mv, pinned, evasions))
             .collect()
     }
 
-    /// Returns a boolean representing whether or not a given move is legal
+    /// Returns the potential moves for the current side that satisfy `keep`
     ///
-    /// The move is only considered legal if it does not leave the king in check
+    /// Like `get_all_moves`, the moves are not checked for leaving the king in check, so a caller
+    /// that only needs some of the moves, such as the captures, can test the legality of just the
+    /// ones it plays with `is_legal`.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Whether a move, with its captured piece filled in, belongs in the list
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Ply>` - The pseudo-legal moves that satisfy `keep`
     ///
     /// # Examples
     /// ```
-    /// let ply = Ply(Square::new("e2"), Square::new("e9"));
-    /// assert!(!board.is_legal_move(ply));
+    /// let board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+    /// let captures = board.get_filtered_moves(|ply| ply.captured_piece.is_some());
+    /// assert_eq!(captures.len(), 1);
     /// ```
-    fn is_legal_move(&mut self, ply: Ply) -> Result<Ply, &'static str> {
-        self.make_move(ply);
-        if self.is_in_check(self.current_turn.opposite()) {
-            self.unmake_move();
-            return Err("Move is not valid. The move would leave the king in check.");
+    pub fn get_filtered_moves(&self, keep: impl Fn(&Ply) -> bool) -> Vec<Ply> {
+        let mut all_moves = self.get_all_moves();
+        all_moves.retain(keep);
+
+        all_moves
+    }
+
+    /// Returns whether a pseudo-legal move leaves the king of the side to move out of check
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - A move generated for the current position, or completed with `complete_ply`
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the move is legal
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1");
+    /// let pinned = board.complete_ply(Ply::new(Square::from("e2"), Square::from("c3"))).unwrap();
+    /// assert!(!board.is_legal(pinned));
+    /// ```
+    pub fn is_legal(&mut self, ply: Ply) -> bool {
+        let pinned = self.pinned_pieces(self.current_turn);
+        let evasions = self.check_evasion_squares(self.current_turn);
+
+        self.is_legal_with(ply, pinned, evasions)
+    }
+
+    /// Returns whether a pseudo-legal move is legal, given the pinned pieces of the side to move
+    /// and the squares that answer a check against it
+    ///
+    /// Only en passant and castling are made on the board to find out. Any other move is legal
+    /// unless it takes a pinned piece off the line to its king or leaves a check unanswered.
+    fn is_legal_with(&mut self, ply: Ply, pinned: Bitboard, evasions: Option<Bitboard>) -> bool {
+        if self.is_king_step(ply) {
+            return self.is_legal_king_step(ply);
+        }
+        // En passant removes a second piece from the board and castling moves two, so both are
+        // always made
+        if ply.en_passant || ply.is_castles {
+            return self.is_legal_move(ply).is_ok();
+        }
+        if !(pinned & Bitboard::from(ply.start)).is_empty() {
+            // A pinned piece never leaves its line, so it can neither block nor capture a checker
+            return evasions.is_none()
+                && self
+                    .pin_ray(ply.start)
+                    .is_some_and(|ray| !(ray & Bitboard::from(ply.dest)).is_empty());
         }
-        self.unmake_move();
 
-        Ok(ply)
+        evasions.is_none_or(|evasions| !(evasions & Bitboard::from(ply.dest)).is_empty())
     }
 
-    /// Switches the current turn to the other player
+    /// Returns the squares a piece other than the king can move to in order to answer a check
+    /// against the king of `color`, which are the checker's square and the squares between it
+    /// and the king
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Bitboard>` - The squares, which are empty in double check, or `None` if the king
+    ///   is not in check
+    fn check_evasion_squares(&self, color: Color) -> Option<Bitboard> {
+        let checkers = self.checkers(color);
+        if checkers.is_empty() {
+            return None;
+        }
+        if checkers.count_ones() > 1 {
+            return Some(Bitboard::new(0));
+        }
+
+        let king = self.bitboards.get_bitboard(Kind::King(color));
+        let rays = RAYS.get_or_init(Rays::new).rays;
+        // A slider checks along the one ray from the king that reaches it, while any other
+        // checker is adjacent or a knight's jump away and can only be captured
+        let evasions = rays[king.bitscan_forward() as usize]
+            .iter()
+            .zip(rays[checkers.bitscan_forward() as usize].iter())
+            .find(|(from_king, _)| !(**from_king & checkers).is_empty())
+            .map_or(checkers, |(from_king, from_checker)| {
+                *from_king & !*from_checker
+            });
+
+        Some(evasions)
+    }
+
+    #[allow(dead_code)]
+    /// Returns whether the piece on `square` is pinned against its own king
+    ///
+    /// A pinned piece shields its king from an enemy slider, so it may only move along the line
+    /// between the two.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square of the piece to check
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the piece is pinned, which is false for an empty square or a king
     ///
     /// # Examples
     /// ```
-    /// let board = BoardBuilder::construct_starting_board().build();
-    /// board.switch_turn();
-    /// assert_eq!(Color::Black, board.current_turn);
-    /// board.switch_turn();
-    /// assert_eq!(Color::White, board.current_turn);
+    /// let board = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1");
+    /// assert!(board.is_pinned(Square::from("e2")));
     /// ```
-    pub fn switch_turn(&mut self) {
-        self.current_turn = self.current_turn.opposite();
+    pub fn is_pinned(&self, square: Square) -> bool {
+        self.pin_ray(square).is_some()
     }
 
-    /// Returns a `CastlingStatus` representing whether or not the current `kind` of castling is availiable
+    /// Returns the pieces of `color` that are pinned against their own king
+    ///
+    /// A piece is pinned when it is the only piece between its king and an enemy bishop, rook, or
+    /// queen moving along that line. The lines are walked out from the king once, so this is
+    /// cheaper than asking `is_pinned` about every piece.
     ///
     /// # Arguments
     ///
-    /// * `kind` - The kind of castling to check for
+    /// * `color` - The side whose pinned pieces are wanted
+    ///
+    /// # Returns
+    ///
+    /// * `Bitboard` - The squares of the pinned pieces
     ///
     /// # Examples
     /// ```
-    /// let board = BoardBuilder::construct_starting_board().build();
-    /// assert_eq!(CastlingStatus::Availiable, board.castling_ability(CastlingKind::WhiteKingside));
-    /// assert_eq!(CastlingStatus::Availiable, board.castling_ability(CastlingKind::BlackQueenside));
+    /// let board = Board::from_fen("4k3/4r3/8/8/1b6/8/3PN3/4K3 w - - 0 1");
+    /// let pinned = Bitboard::from(Square::from("d2")) | Bitboard::from(Square::from("e2"));
+    /// assert_eq!(board.pinned_pieces(Color::White), pinned);
     /// ```
-    pub fn castling_ability(&self, kind: CastlingKind) -> Result<CastlingStatus, &'static str> {
-        match (kind, self.current_turn) {
-            (CastlingKind::WhiteKingside | CastlingKind::WhiteQueenside, Color::Black)
-            | (CastlingKind::BlackKingside | CastlingKind::BlackQueenside, Color::White) => {
-                return Err("Cannot castle when it is not your turn.");
-            }
-            _ => (),
-        }
+    pub fn pinned_pieces(&self, color: Color) -> Bitboard {
+        self.pins(color)
+            .fold(Bitboard::new(0), |pinned, (square, _)| {
+                pinned | Bitboard::from(square)
+            })
+    }
 
-        if self.castle_status(kind) == CastlingStatus::Availiable
-            && self
-                .no_pieces_between_castling(kind)
-                .and(self.no_checks_castling(kind))
-                .is_ok()
-        {
-            Ok(CastlingStatus::Availiable)
-        } else {
-            Ok(CastlingStatus::Unavailiable)
-        }
+    /// Returns the squares a pinned piece may move to without exposing its king, which are the
+    /// squares between the king and the pinner along with the pinner itself
+    fn pin_ray(&self, square: Square) -> Option<Bitboard> {
+        let color = self.get_piece(square)?.get_color();
+
+        self.pins(color)
+            .find_map(|(pinned, ray)| (pinned == square).then_some(ray))
     }
 
-    /// Returns a Result representing whether or not there are no squares with pieces on the castling path
+    /// Returns each piece of `color` pinned against its king along with the squares it may still
+    /// move to, as described by `pin_ray`
+    #[allow(clippy::cast_possible_truncation)]
+    fn pins(&self, color: Color) -> impl Iterator<Item = (Square, Bitboard)> + '_ {
+        const LINES: [(Direction, bool, bool); 8] = [
+            (Direction::North, true, false),
+            (Direction::East, true, false),
+            (Direction::South, false, false),
+            (Direction::West, false, false),
+            (Direction::NorthEast, true, true),
+            (Direction::NorthWest, true, true),
+            (Direction::SouthEast, false, true),
+            (Direction::SouthWest, false, true),
+        ];
+
+        let king = match color {
+            Color::White => self.bitboards.white_king,
+            Color::Black => self.bitboards.black_king,
+        };
+        let king_square =
+            (!king.is_empty()).then(|| Square::from_index(king.bitscan_forward() as u8));
+        let rays = RAYS.get_or_init(Rays::new).rays;
+        let nearest = move |ray: Bitboard, ascending: bool| {
+            let blockers = ray & self.bitboards.all_pieces;
+            (!blockers.is_empty()).then(|| {
+                let idx = if ascending {
+                    blockers.bitscan_forward()
+                } else {
+                    blockers.bitscan_reverse()
+                };
+                Square::from_index(idx as u8)
+            })
+        };
+
+        king_square.into_iter().flat_map(move |king_square| {
+            LINES
+                .into_iter()
+                .filter_map(move |(direction, ascending, diagonal)| {
+                    let ray = rays[usize::from(king_square.index())][direction as usize];
+                    let square = nearest(ray, ascending)?;
+                    if self.get_piece(square)?.get_color() != color {
+                        return None;
+                    }
+                    let pinner = nearest(
+                        rays[usize::from(square.index())][direction as usize],
+                        ascending,
+                    )?;
+                    let pins = match self.get_piece(pinner)? {
+                        piece if piece.get_color() == color => false,
+                        Kind::Queen(_) => true,
+                        Kind::Bishop(_) => diagonal,
+                        Kind::Rook(_) => !diagonal,
+                        _ => false,
+                    };
+
+                    pins.then(|| {
+                        (
+                            square,
+                            ray & !rays[usize::from(pinner.index())][direction as usize],
+                        )
+                    })
+                })
+        })
+    }
+
+    /// Returns whether a move is a king move other than castling
+    fn is_king_step(&self, ply: Ply) -> bool {
+        !ply.is_castles && matches!(self.get_piece(ply.start), Some(Kind::King(_)))
+    }
+
+    /// Returns whether a non-castling king move leaves the king out of check
+    ///
+    /// Instead of making the move, the destination is tested against the enemy's attacks with the
+    /// king lifted off its starting square, so a slider checking along the king's path still
+    /// covers the squares behind it.
     ///
     /// # Arguments
     ///
-    /// * `kind` - The kind of castling to check for
+    /// * `ply` - The king move to check
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the destination is safe for the king
+    fn is_legal_king_step(&self, ply: Ply) -> bool {
+        let enemies = match self.current_turn {
+            Color::White => self.bitboards.black_pieces,
+            Color::Black => self.bitboards.white_pieces,
+        };
+        let occupancy = self.bitboards.all_pieces & !Bitboard::from(ply.start);
+
+        (self.attackers_to_with_blockers(ply.dest, occupancy) & enemies & !Bitboard::from(ply.dest))
+            .is_empty()
+    }
+
+    /// Returns the number of legal moves for the current side
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.legal_move_count(), 20);
+    /// ```
+    pub fn legal_move_count(&mut self) -> usize {
+        self.get_legal_moves().len()
+    }
+
+    /// Returns whether `ply` could be played by the side to move if its king's safety is ignored
+    ///
+    /// Moves remembered from another position, such as killer moves, can be checked with this
+    /// before they are trusted. Only the origin, destination, and promotion of `ply` are compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The move to check
     ///
     /// # Examples
     /// ```
     /// let board = BoardBuilder::construct_starting_board().build();
-    /// assert!(board.no_pieces_between_castling(CastlingKind::WhiteKingside).is_err());
-    /// assert!(board.no_pieces_between_castling(CastlingKind::BlackQueenside).is_err());
+    /// assert!(board.is_pseudo_legal(Ply::new(Square::from("g1"), Square::from("f3"))));
+    /// assert!(!board.is_pseudo_legal(Ply::new(Square::from("g8"), Square::from("f6"))));
     /// ```
-    fn no_pieces_between_castling(&self, kind: CastlingKind) -> Result<(), &'static str> {
-        let pieces_blocking = match kind {
-            CastlingKind::WhiteKingside => self.bitboards.all_pieces & 0x60,
-            CastlingKind::WhiteQueenside => self.bitboards.all_pieces & 0xE,
-            CastlingKind::BlackKingside => self.bitboards.all_pieces & 0x_60000000_00000000,
-            CastlingKind::BlackQueenside => self.bitboards.all_pieces & 0x_0E00_0000_0000_0000,
-        };
+    pub fn is_pseudo_legal(&self, ply: Ply) -> bool {
+        self.complete_ply(ply).is_ok()
+    }
 
-        if pieces_blocking.is_empty() {
-            Ok(())
-        } else {
-            Err("There are pieces between the start and destination squares.")
+    /// Returns the move the side to move would make by playing `partial`, with its capture and
+    /// special move flags filled in from the board
+    ///
+    /// Moves built outside of move generation, such as from UCI notation, only need a correct
+    /// origin, destination, and promotion. The result can be passed to `make_move`. Like
+    /// `is_pseudo_legal`, this does not check whether the move leaves the king in check.
+    ///
+    /// # Arguments
+    ///
+    /// * `partial` - The move to complete
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Ply, &'static str>` - The completed move, or an error if the side to move has no
+    ///   such move
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+    /// let ply = board.complete_ply(Ply::new(Square::from("e1"), Square::from("g1"))).unwrap();
+    /// assert!(ply.is_castles);
+    /// ```
+    pub fn complete_ply(&self, partial: Ply) -> Result<Ply, &'static str> {
+        let piece = self
+            .get_piece(partial.start)
+            .filter(|piece| piece.get_color() == self.current_turn)
+            .ok_or("There is no piece of the side to move on the starting square.")?;
+
+        piece
+            .get_moveset(partial.start, self)
+            .into_iter()
+            .find(|mv| mv.dest == partial.dest && mv.promoted_to == partial.promoted_to)
+            .map(|mv| self.with_captured_piece(mv))
+            .ok_or("The piece on the starting square cannot make this move.")
+    }
+
+    /// Returns a boolean representing whether or not a given move is legal
+    ///
+    /// The move is only considered legal if it does not leave the king in check
+    ///
+    /// # Examples
+    /// ```
+    /// let ply = Ply(Square::new("e2"), Square::new("e9"));
+    /// assert!(!board.is_legal_move(ply));
+    /// ```
+    fn is_legal_move(&mut self, ply: Ply) -> Result<Ply, &'static str> {
+        self.make_move(ply);
+        if self.is_in_check(self.current_turn.opposite()) {
+            self.unmake_move();
+            return Err("Move is not valid. The move would leave the king in check.");
         }
+        self.unmake_move();
+
+        Ok(ply)
     }
 
-    /// Returns a Result representing whether or not there are no squares in check between two squares
+    #[allow(dead_code)]
+    /// Returns a legal move alongside the draw that could be claimed once it is played, if any
     ///
-    /// This method is mostly used for calculating castling rights
+    /// A move is reported as drawing if it repeats a position for the third time or brings the
+    /// halfmove clock to 100 without delivering checkmate. This lets a UI label drawing moves
+    /// before they are played.
     ///
     /// # Arguments
     ///
-    /// * `start` - The starting square
-    /// * `dest` - The destination square
+    /// * `ply` - The move to check
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Ply, Option<GameState>), &str>` - The move and either
+    ///   `GameState::ThreefoldRepetition` or `GameState::FiftyMoveRule` if it allows a draw claim,
+    ///   or an error if the move is not legal
     ///
     /// # Examples
     /// ```
-    /// let board = BoardBuilder::construct_starting_board().build();
-    /// assert!(board.no_checks_between(Square::new("a1"), Square::new("h1")).is_ok());
-    /// assert!(board.no_checks_between(Square::new("a8"), Square::new("h8")).is_err());
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// let ply = board.find_move("g1f3").unwrap();
+    /// assert_eq!(board.is_legal_move_with_draw(ply), Ok((ply, None)));
     /// ```
-    fn no_checks_castling(&self, kind: CastlingKind) -> Result<(), &'static str> {
-        let attacks = self.get_attacked_squares(self.current_turn);
-        if match kind {
-            CastlingKind::WhiteKingside => (attacks & 0x70).is_empty(),
-            CastlingKind::WhiteQueenside => (attacks & 0x1C).is_empty(),
-            CastlingKind::BlackKingside => (attacks & 0x_7000_0000_0000_0000).is_empty(),
-            CastlingKind::BlackQueenside => (attacks & 0x1C00_0000_0000_0000).is_empty(),
-        } {
-            Ok(())
-        } else {
-            Err("There are checks between the start and destination squares.")
+    pub fn is_legal_move_with_draw(
+        &mut self,
+        ply: Ply,
+    ) -> Result<(Ply, Option<GameState>), &'static str> {
+        self.make_move(ply);
+        if self.is_in_check(self.current_turn.opposite()) {
+            self.unmake_move();
+            return Err("Move is not valid. The move would leave the king in check.");
+        }
+
+        let draw = if self.is_draw_by_repetition() {
+            Some(GameState::ThreefoldRepetition)
+        } else if self.is_draw_by_fifty_moves() {
+            Some(GameState::FiftyMoveRule)
+        } else {
+            None
+        };
+        self.unmake_move();
+
+        Ok((ply, draw))
+    }
+
+    #[allow(dead_code)]
+    /// Returns all legal moves for the current side, each paired with the draw it would allow
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// let drawing_moves = board
+    ///     .get_legal_moves_with_draws()
+    ///     .into_iter()
+    ///     .filter(|(_, draw)| draw.is_some());
+    /// ```
+    pub fn get_legal_moves_with_draws(&mut self) -> Vec<(Ply, Option<GameState>)> {
+        self.get_all_moves()
+            .into_iter()
+            .filter_map(|mv| self.is_legal_move_with_draw(mv).ok())
+            .collect()
+    }
+
+    /// Returns the Zobrist hash of the current position
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let key = board.zobrist_key();
+    /// ```
+    pub const fn zobrist_key(&self) -> u64 {
+        self.zobrist_key
+    }
+
+    /// Returns the file of the pawn that just moved two squares, if the last move was a double
+    /// pawn push
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1");
+    /// assert_eq!(board.en_passant_file(), Some(4));
+    /// ```
+    pub const fn en_passant_file(&self) -> Option<u8> {
+        self.en_passant_file
+    }
+
+    /// Returns how many times the current position has occurred before with the same side to move
+    ///
+    /// Only positions since the last capture or pawn move are considered, as no earlier position
+    /// can be repeated.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.repetition_count(), 0);
+    /// ```
+    pub fn repetition_count(&self) -> u8 {
+        let count = self
+            .key_history
+            .iter()
+            .rev()
+            .take(self.get_halfmove_clock() as usize)
+            .skip(1)
+            .step_by(2)
+            .filter(|&&key| key == self.zobrist_key)
+            .count();
+
+        u8::try_from(count).unwrap_or(u8::MAX)
+    }
+
+    /// Returns a boolean representing whether or not the current position has occurred at least
+    /// twice before with the same side to move
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert!(!board.is_threefold_repetition());
+    /// ```
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetition_count() >= 2
+    }
+
+    /// Returns whether the side to move can claim a draw because the current position has
+    /// occurred three times
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert!(!board.is_draw_by_repetition());
+    /// ```
+    pub fn is_draw_by_repetition(&self) -> bool {
+        self.is_threefold_repetition()
+    }
+
+    /// Returns whether the game is drawn by the fifty move rule
+    ///
+    /// A checkmate delivered on the hundredth halfmove still wins, so it takes precedence.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 100 80");
+    /// assert!(board.is_draw_by_fifty_moves());
+    /// ```
+    pub fn is_draw_by_fifty_moves(&self) -> bool {
+        self.get_halfmove_clock() >= 100
+            && !(self.in_check() && self.clone().get_legal_moves().is_empty())
+    }
+
+    #[allow(dead_code)]
+    /// Returns whether the incrementally updated Zobrist key matches the key computed from scratch
+    ///
+    /// This is a debugging aid for the incremental updates in `make_move` and `unmake_move`, which
+    /// would otherwise drift silently and corrupt the transposition table and repetition checks.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// board.make_move(board.find_move("e2e4").unwrap());
+    /// assert!(board.zobrist_key_is_consistent());
+    /// ```
+    pub fn zobrist_key_is_consistent(&self) -> bool {
+        self.zobrist_key == self.compute_zobrist_key()
+    }
+
+    /// Computes the Zobrist key of the current position from scratch
+    ///
+    /// The key is otherwise kept up to date incrementally as pieces are added and removed and as
+    /// moves are made.
+    fn compute_zobrist_key(&self) -> u64 {
+        let keys = Zobrist::get();
+        let mut key = self.state_key();
+
+        for square_idx in 0..64u8 {
+            let square = Square::from_index(square_idx);
+            if let Some(piece) = self.get_piece(square) {
+                key ^= keys.piece(piece, square);
+            }
+        }
+
+        key
+    }
+
+    /// Computes the middlegame and endgame piece-square scores of the position from scratch
+    ///
+    /// The scores are otherwise kept up to date incrementally as pieces are added and removed.
+    fn compute_piece_square_score(&self) -> (i64, i64) {
+        self.bitboards
+            .all_pieces
+            .into_iter()
+            .filter_map(|square| Some(signed_piece_square_value(self.get_piece(square)?, square)))
+            .fold((0, 0), |(mg_score, eg_score), (middlegame, endgame)| {
+                (mg_score + middlegame, eg_score + endgame)
+            })
+    }
+
+    /// Returns the middlegame piece-square score of white's pieces minus black's
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.mg_score(), 0);
+    /// ```
+    pub const fn mg_score(&self) -> i64 {
+        self.mg_score
+    }
+
+    /// Returns the endgame piece-square score of white's pieces minus black's
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.eg_score(), 0);
+    /// ```
+    pub const fn eg_score(&self) -> i64 {
+        self.eg_score
+    }
+
+    /// Returns the part of the Zobrist key that does not depend on piece placement: the side to
+    /// move, the castling rights, and the en passant file
+    fn state_key(&self) -> u64 {
+        let keys = Zobrist::get();
+        let mut key = 0;
+
+        if self.current_turn == Color::Black {
+            key ^= keys.black_to_move();
+        }
+        for kind in [
+            CastlingKind::WhiteKingside,
+            CastlingKind::WhiteQueenside,
+            CastlingKind::BlackKingside,
+            CastlingKind::BlackQueenside,
+        ] {
+            if self.castle_status(kind) == CastlingStatus::Availiable {
+                key ^= keys.castling(kind);
+            }
+        }
+        if let Some(file) = self.en_passant_file {
+            key ^= keys.en_passant(file);
+        }
+
+        key
+    }
+
+    /// Switches the current turn to the other player
+    ///
+    /// Whether the new side to move is in check is worked out here, see `Board::in_check`.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// board.switch_turn();
+    /// assert_eq!(Color::Black, board.current_turn);
+    /// board.switch_turn();
+    /// assert_eq!(Color::White, board.current_turn);
+    /// ```
+    pub fn switch_turn(&mut self) {
+        self.current_turn = self.current_turn.opposite();
+        self.in_check = self.is_in_check(self.current_turn);
+    }
+
+    /// Returns a `CastlingStatus` representing whether or not the current `kind` of castling is availiable
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of castling to check for
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(CastlingStatus::Availiable, board.castling_ability(CastlingKind::WhiteKingside));
+    /// assert_eq!(CastlingStatus::Availiable, board.castling_ability(CastlingKind::BlackQueenside));
+    /// ```
+    pub fn castling_ability(&self, kind: CastlingKind) -> Result<CastlingStatus, &'static str> {
+        match (kind, self.current_turn) {
+            (CastlingKind::WhiteKingside | CastlingKind::WhiteQueenside, Color::Black)
+            | (CastlingKind::BlackKingside | CastlingKind::BlackQueenside, Color::White) => {
+                return Err("Cannot castle when it is not your turn.");
+            }
+            _ => (),
+        }
+
+        if self.castle_status(kind) == CastlingStatus::Availiable
+            && (!self.chess960
+                || self.get_piece(self.castling_rook(kind)) == Some(Kind::Rook(kind.color())))
+            && self
+                .no_pieces_between_castling(kind)
+                .and(self.no_checks_castling(kind))
+                .is_ok()
+        {
+            Ok(CastlingStatus::Availiable)
+        } else {
+            Ok(CastlingStatus::Unavailiable)
+        }
+    }
+
+    /// Returns a Result representing whether or not there are no squares with pieces on the castling path
+    ///
+    /// Every square the king or rook passes over or lands on must be empty, apart from the squares
+    /// the two of them start on.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of castling to check for
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert!(board.no_pieces_between_castling(CastlingKind::WhiteKingside).is_err());
+    /// assert!(board.no_pieces_between_castling(CastlingKind::BlackQueenside).is_err());
+    /// ```
+    fn no_pieces_between_castling(&self, kind: CastlingKind) -> Result<(), &'static str> {
+        let king_square = self
+            .castling_king(kind)
+            .ok_or("The king_square is not on its castling square.")?;
+        let rook = self.castling_rook(kind);
+        let path = rank_span(kind.rank(), king_square.file, kind.king_dest_file())
+            | rank_span(kind.rank(), rook.file, kind.rook_dest_file());
+        let pieces_blocking = self.bitboards.all_pieces
+            & path
+            & !(Bitboard::from(king_square) | Bitboard::from(rook));
+
+        if pieces_blocking.is_empty() {
+            Ok(())
+        } else {
+            Err("There are pieces between the start and destination squares.")
+        }
+    }
+
+    /// Returns a Result representing whether or not there are no squares in check between two squares
+    ///
+    /// This method is mostly used for calculating castling rights
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of castling to check for
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert!(board.no_checks_castling(CastlingKind::WhiteKingside).is_ok());
+    /// ```
+    fn no_checks_castling(&self, kind: CastlingKind) -> Result<(), &'static str> {
+        let king_square = self
+            .castling_king(kind)
+            .ok_or("The king_square is not on its castling square.")?;
+        let enemy = self.current_turn.opposite();
+        let path = rank_span(kind.rank(), king_square.file, kind.king_dest_file());
+        if path
+            .into_iter()
+            .all(|square| self.attackers_to(square, enemy).is_empty())
+        {
+            Ok(())
+        } else {
+            Err("There are checks between the start and destination squares.")
+        }
+    }
+
+    /// Returns the square the king castles from
+    ///
+    /// In standard chess the king always castles from the e-file, while in Chess960 it castles
+    /// from wherever it started on its back rank.
+    fn castling_king(&self, kind: CastlingKind) -> Option<Square> {
+        if !self.chess960 {
+            return Some(Square {
+                rank: kind.rank(),
+                file: 4,
+            });
+        }
+
+        self.bitboards
+            .get_bitboard(Kind::King(kind.color()))
+            .into_iter()
+            .next()
+            .filter(|king| king.rank == kind.rank())
+    }
+
+    /// Returns whether a king on `square` stands where it castles from
+    pub fn is_castling_square(&self, square: Square, kind: CastlingKind) -> bool {
+        self.castling_king(kind) == Some(square)
+    }
+
+    /// Returns the square the rook used for the `kind` of castling started the game on
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.castling_rook(CastlingKind::BlackQueenside), Square::from("a8"));
+    /// ```
+    pub const fn castling_rook(&self, kind: CastlingKind) -> Square {
+        Square {
+            rank: kind.rank(),
+            file: self.castling_rooks.file(kind),
         }
     }
 
-    /// Returns a bitboard representing all squares that are attacked from `color`'s perspective
+    /// Returns the destination of the castling move for the `kind` of castling
+    ///
+    /// Standard chess writes castling as the king moving two squares, while Chess960 writes it as
+    /// the king capturing its own rook, since the king may move any distance or not at all.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.castling_dest(CastlingKind::WhiteKingside), Square::from("g1"));
+    /// ```
+    pub const fn castling_dest(&self, kind: CastlingKind) -> Square {
+        if self.chess960 {
+            self.castling_rook(kind)
+        } else {
+            Square {
+                rank: kind.rank(),
+                file: kind.king_dest_file(),
+            }
+        }
+    }
+
+    /// Returns the kind of castling a castling move makes
+    ///
+    /// The kingside rook is always on the h-file side of the king, so the direction of the move
+    /// tells the two apart in both standard chess and Chess960.
+    const fn castling_kind(ply: Ply, color: Color) -> CastlingKind {
+        let kingside = ply.dest.file > ply.start.file;
+        match (color, kingside) {
+            (Color::White, true) => CastlingKind::WhiteKingside,
+            (Color::White, false) => CastlingKind::WhiteQueenside,
+            (Color::Black, true) => CastlingKind::BlackKingside,
+            (Color::Black, false) => CastlingKind::BlackQueenside,
+        }
+    }
+
+    /// Returns whether the board plays Chess960
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert!(!board.is_chess960());
+    /// ```
+    pub const fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    /// Sets whether the board plays Chess960, which changes how castling moves are written
+    ///
+    /// # Arguments
+    ///
+    /// * `chess960` - Whether the board plays Chess960
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// board.set_chess960(true);
+    /// assert!(board.find_move("e1h1").is_err());
+    /// ```
+    pub const fn set_chess960(&mut self, chess960: bool) {
+        self.chess960 = chess960;
+    }
+
+    #[allow(dead_code)]
+    /// Returns a bitboard representing all squares that are attacked from `color`'s perspective
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the player to calculate the attacked squares for
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    ///
+    /// let attacked_squares = board.get_attacked_squares(Color::White);
+    /// ```
+    fn get_attacked_squares(&self, color: Color) -> Bitboard {
+        let attacking_pieces = match color {
+            Color::White => self.bitboards.black_pieces,
+            Color::Black => self.bitboards.white_pieces,
+        };
+
+        let mut attacks = Bitboard::new(0);
+        for square in attacking_pieces {
+            let piece = self
+                .get_piece(square)
+                .expect("No piece found at {square} where bitboard claimed piece was!");
+
+            attacks |= piece.get_attacks(square, self);
+        }
+
+        attacks
+    }
+
+    /// Returns the pieces of `color` that attack `square`
+    ///
+    /// Sliding pieces are blocked by every piece on the board, and the square itself may be empty
+    /// or hold a piece of either color.
+    ///
+    /// # Arguments
+    ///
+    /// * `square` - The square to find the attackers of
+    /// * `color` - The side whose attackers are wanted
+    ///
+    /// # Returns
+    ///
+    /// * `Bitboard` - The squares of the attacking pieces
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1");
+    /// assert_eq!(board.attackers_to(Square::from("e1"), Color::Black), Bitboard::from(Square::from("d2")));
+    /// ```
+    pub fn attackers_to(&self, square: Square, color: Color) -> Bitboard {
+        let pieces = match color {
+            Color::White => self.bitboards.white_pieces,
+            Color::Black => self.bitboards.black_pieces,
+        };
+
+        self.attackers_to_with_blockers(square, self.bitboards.all_pieces) & pieces
+    }
+
+    /// Returns the halfmove clock of the current board state
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(0, board.get_halfmove_clock());
+    /// ```
+    pub fn get_halfmove_clock(&self) -> u16 {
+        self.history
+            .last()
+            .expect("Board should always have one history")
+            .halfmove_clock
+    }
+
+    /// Returns the move that led to the current board state, if any has been made
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(None, board.last_move());
+    /// let ply = board.find_move("e2e4").unwrap();
+    /// board.make_move(ply);
+    /// assert_eq!(Some(ply.dest), board.last_move().map(|mv| mv.dest));
+    /// ```
+    pub fn last_move(&self) -> Option<Ply> {
+        // The first entry only records the starting state of the board
+        self.history.get(1..).and_then(<[Ply]>::last).copied()
+    }
+
+    /// Returns the number of pieces of the specified kind on the board
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of piece to count
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(8, board.get_piece_count(Kind::Pawn(Color::White)));
+    /// ```
+    pub const fn get_piece_count(&self, kind: Kind) -> u32 {
+        self.bitboards.get_bitboard(kind).count_ones()
+    }
+
+    /// Returns the number of knights and bishops `color` has on the board
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(4, board.minor_count(Color::White));
+    /// ```
+    pub const fn minor_count(&self, color: Color) -> u32 {
+        self.get_piece_count(Kind::Knight(color)) + self.get_piece_count(Kind::Bishop(color))
+    }
+
+    /// Returns the number of rooks and queens `color` has on the board
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(3, board.major_count(Color::White));
+    /// ```
+    pub const fn major_count(&self, color: Color) -> u32 {
+        self.get_piece_count(Kind::Rook(color)) + self.get_piece_count(Kind::Queen(color))
+    }
+
+    #[allow(dead_code)]
+    /// Returns white's material advantage over black in centipawns, ignoring the kings
+    ///
+    /// The pieces are valued by `PIECE_VALUES`, the same values the evaluator and the static
+    /// exchange evaluation use, and nothing else about the position is taken into account.
+    ///
+    /// # Returns
+    ///
+    /// * `i64` - The material difference, positive when white is ahead
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+    /// assert_eq!(board.material_balance(), 500);
+    /// ```
+    pub fn material_balance(&self) -> i64 {
+        [
+            Kind::Pawn,
+            Kind::Knight,
+            Kind::Bishop,
+            Kind::Rook,
+            Kind::Queen,
+        ]
+        .into_iter()
+        .zip(PIECE_VALUES)
+        .map(|(kind, value)| {
+            value
+                * (i64::from(self.get_piece_count(kind(Color::White)))
+                    - i64::from(self.get_piece_count(kind(Color::Black))))
+        })
+        .sum()
+    }
+
+    /// Returns how much non-pawn material is left on the board, from `MAX_PHASE` in the opening
+    /// down to 0 once only kings and pawns remain
+    ///
+    /// Minor pieces count for 1, rooks for 2, and queens for 4. Promotions cannot push the phase
+    /// above `MAX_PHASE`.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(MAX_PHASE, board.total_material_phase());
+    /// ```
+    pub fn total_material_phase(&self) -> u32 {
+        let phase: u32 = [Color::White, Color::Black]
+            .into_iter()
+            .map(|color| {
+                self.minor_count(color)
+                    + 2 * self.get_piece_count(Kind::Rook(color))
+                    + 4 * self.get_piece_count(Kind::Queen(color))
+            })
+            .sum();
+
+        phase.min(MAX_PHASE)
+    }
+
+    /// Returns whether `file` is open, half-open, or closed for `color`
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to inspect, where 0 is the a-file
+    /// * `color` - The side the file is seen from
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/3p4/8/8/8/8/8/4K3 w - - 0 1");
+    /// assert_eq!(board.file_status(3, Color::White), FileStatus::HalfOpen);
+    /// assert_eq!(board.file_status(3, Color::Black), FileStatus::Closed);
+    /// ```
+    pub fn file_status(&self, file: u8, color: Color) -> FileStatus {
+        let file_mask = Bitboard::new(Square { rank: 0, file }.get_file_mask());
+        let own_pawns = self.bitboards.get_bitboard(Kind::Pawn(color)) & file_mask;
+        let enemy_pawns = self.bitboards.get_bitboard(Kind::Pawn(color.opposite())) & file_mask;
+
+        if !own_pawns.is_empty() {
+            FileStatus::Closed
+        } else if enemy_pawns.is_empty() {
+            FileStatus::Open
+        } else {
+            FileStatus::HalfOpen
+        }
+    }
+
+    /// Returns whether neither side has enough material left to ever deliver checkmate
+    ///
+    /// This covers a lone king against a lone king, a single minor piece against a lone king, and
+    /// any number of bishops that all stand on the same color of square.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+    /// assert!(board.is_insufficient_material());
+    /// ```
+    pub fn is_insufficient_material(&self) -> bool {
+        let has_mating_material = [Color::White, Color::Black].into_iter().any(|color| {
+            self.get_piece_count(Kind::Pawn(color)) > 0 || self.major_count(color) > 0
+        });
+        if has_mating_material {
+            return false;
+        }
+
+        let knights = self.get_piece_count(Kind::Knight(Color::White))
+            + self.get_piece_count(Kind::Knight(Color::Black));
+        let bishops = self.bitboards.white_bishops | self.bitboards.black_bishops;
+        if knights + bishops.count_ones() <= 1 {
+            return true;
+        }
+
+        knights == 0
+            && ((bishops & DARK_SQUARES).is_empty() || (bishops & !DARK_SQUARES).is_empty())
+    }
+
+    /// Returns a boolean representing whether or not the current side is in check
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert!(!board.is_in_check());
+    /// ```
+    pub fn is_in_check(&self, color: Color) -> bool {
+        !self.checkers(color).is_empty()
+    }
+
+    /// Returns whether the side to move is in check
+    ///
+    /// The answer is worked out once when a move is made and restored when it is unmade, so
+    /// asking repeatedly about the same position costs nothing. Pieces added or removed by hand
+    /// are not accounted for until the turn changes.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+    /// board.make_move(board.find_move("a1a8").unwrap());
+    /// assert!(board.in_check());
+    /// ```
+    pub const fn in_check(&self) -> bool {
+        self.in_check
+    }
+
+    /// Returns the enemy pieces giving check to the king of `color`
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The side whose king is checked
+    ///
+    /// # Returns
+    ///
+    /// * `Bitboard` - The squares of the checking pieces, which is empty if `color` has no king
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1");
+    /// assert_eq!(board.checkers(Color::White), Bitboard::from(Square::from("d2")));
+    /// ```
+    pub fn checkers(&self, color: Color) -> Bitboard {
+        let king = match color {
+            Color::White => self.bitboards.white_king,
+            Color::Black => self.bitboards.black_king,
+        };
+
+        king.into_iter().fold(Bitboard::new(0), |checkers, square| {
+            checkers | self.attackers_to(square, color.opposite())
+        })
+    }
+
+    /// Returns the number of enemy pieces giving check to the king of `color`
+    ///
+    /// In double check only a king move can answer both checks.
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1");
+    /// assert_eq!(board.checker_count(Color::White), 1);
+    /// ```
+    pub fn checker_count(&self, color: Color) -> u32 {
+        self.checkers(color).count_ones()
+    }
+
+    /// Returns whether the side to move has been checkmated
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1");
+    /// assert!(board.is_checkmate());
+    /// ```
+    pub fn is_checkmate(&mut self) -> bool {
+        self.get_legal_moves().is_empty() && self.in_check()
+    }
+
+    #[allow(dead_code)]
+    /// Returns whether the side to move has no legal moves but is not in check
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+    /// assert!(board.is_stalemate());
+    /// ```
+    pub fn is_stalemate(&mut self) -> bool {
+        self.get_legal_moves().is_empty() && !self.in_check()
+    }
+
+    #[allow(dead_code)]
+    /// Returns whether playing `ply` puts the opponent in check
     ///
     /// # Arguments
     ///
-    /// * `color` - The color of the player to calculate the attacked squares for
+    /// * `ply` - A legal move for the side to move
     ///
     /// # Examples
     /// ```
-    /// let board = BoardBuilder::construct_starting_board().build();
-    ///
-    /// let attacked_squares = board.get_attacked_squares(Color::White);
+    /// let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+    /// let ply = board.find_move("a1a8").unwrap();
+    /// assert!(board.is_check_after(ply));
     /// ```
-    fn get_attacked_squares(&self, color: Color) -> Bitboard {
-        let attacking_pieces = match color {
-            Color::White => self.bitboards.black_pieces,
-            Color::Black => self.bitboards.white_pieces,
-        };
-
-        let mut attacks = Bitboard::new(0);
-        for square in 0..64u8 {
-            if attacking_pieces & (1 << square) == Bitboard::new(0) {
-                continue;
-            }
-
-            let piece = self
-                .get_piece(Square::from(square))
-                .expect("No piece found at {square} where bitboard claimed piece was!");
-
-            attacks |= piece.get_attacks(Square::from(square), self);
-        }
+    pub fn is_check_after(&mut self, ply: Ply) -> bool {
+        self.make_move(ply);
+        let check = self.in_check();
+        self.unmake_move();
 
-        attacks
+        check
     }
 
-    /// Returns the halfmove clock of the current board state
+    #[allow(dead_code)]
+    /// Returns whether playing `ply` checkmates the opponent
     ///
-    /// # Examples
-    /// ```
-    /// let board = BoardBuilder::construct_starting_board().build();
-    /// assert_eq!(0, board.get_halfmove_clock());
-    /// ```
-    pub fn get_halfmove_clock(&self) -> u16 {
-        self.history
-            .last()
-            .expect("Board should always have one history")
-            .halfmove_clock
-    }
-
-    /// Returns a boolean representing whether or not the current side is in check
+    /// # Arguments
+    ///
+    /// * `ply` - A legal move for the side to move
     ///
     /// # Examples
     /// ```
-    /// let board = BoardBuilder::construct_starting_board().build();
-    /// assert!(!board.is_in_check());
+    /// let mut board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    /// let ply = board.find_move("a1a8").unwrap();
+    /// assert!(board.is_mate_after(ply));
     /// ```
-    pub fn is_in_check(&self, color: Color) -> bool {
-        let attacks = self.get_attacked_squares(color);
-
-        let king_pos = match color {
-            Color::White => self.bitboards.white_king,
-            Color::Black => self.bitboards.black_king,
-        };
+    pub fn is_mate_after(&mut self, ply: Ply) -> bool {
+        self.make_move(ply);
+        let mate = self.is_checkmate();
+        self.unmake_move();
 
-        !(king_pos & attacks).is_empty()
+        mate
     }
 
     #[allow(dead_code)]
@@ -370,10 +1603,9 @@ impl Board {
             return;
         }
 
-        let is_in_check = self.is_in_check(self.current_turn);
+        let is_in_check = self.in_check();
         let legal_moves_empty = self.get_legal_moves().is_empty();
-        //let threefold_repetition = self.is_threefold_repetition();
-        let threefold_repetition = false;
+        let threefold_repetition = self.is_draw_by_repetition();
 
         match (
             is_in_check,
@@ -426,6 +1658,44 @@ impl Board {
         self.bitboards.get_piece_kind(square)
     }
 
+    #[allow(dead_code)]
+    /// Returns every piece on the board along with the square it stands on
+    ///
+    /// The pieces are read straight from their bitboards, so this is much cheaper than calling
+    /// `get_piece` on every square. They are grouped by kind, white before black, and each kind
+    /// is in square order.
+    ///
+    /// # Returns
+    ///
+    /// * `impl Iterator<Item = (Square, Kind)>` - Each occupied square and the piece on it
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(board.pieces().count(), 32);
+    /// ```
+    pub fn pieces(&self) -> impl Iterator<Item = (Square, Kind)> + '_ {
+        [Color::White, Color::Black]
+            .into_iter()
+            .flat_map(|color| {
+                [
+                    Kind::Pawn,
+                    Kind::Knight,
+                    Kind::Bishop,
+                    Kind::Rook,
+                    Kind::Queen,
+                    Kind::King,
+                ]
+                .map(|kind| kind(color))
+            })
+            .flat_map(|kind| {
+                self.bitboards
+                    .get_bitboard(kind)
+                    .into_iter()
+                    .map(move |square| (square, kind))
+            })
+    }
+
     /// Adds a new piece of the specified kind to a square on the board
     ///
     /// # Arguments
@@ -441,6 +1711,11 @@ impl Board {
     /// ```
     pub fn add_piece(&mut self, square: Square, piece: Kind) {
         self.bitboards.add_piece(square, piece);
+        self.zobrist_key ^= Zobrist::get().piece(piece, square);
+
+        let (middlegame, endgame) = signed_piece_square_value(piece, square);
+        self.mg_score += middlegame;
+        self.eg_score += endgame;
     }
 
     /// Remove a specific kind of piece from the board at the specified square
@@ -462,6 +1737,11 @@ impl Board {
     /// ```
     pub fn remove_piece(&mut self, square: Square, piece: Kind) {
         self.bitboards.remove_piece(square, piece);
+        self.zobrist_key ^= Zobrist::get().piece(piece, square);
+
+        let (middlegame, endgame) = signed_piece_square_value(piece, square);
+        self.mg_score -= middlegame;
+        self.eg_score -= endgame;
     }
 
     /// Replaces the piece at the dest square with the piece at the destination square
@@ -502,6 +1782,51 @@ impl Board {
             .ok_or("Move not found")
     }
 
+    /// Makes a half-move on this board if it is legal, instead of panicking on a malformed one
+    ///
+    /// The move only needs its start, destination, and promotion filled in. The matching legal
+    /// move, with its capture and special move flags, is the one that gets played.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The move to play
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), MoveError>` - `Ok` if the move was played, `MoveError::MissingPromotion` if
+    ///   a pawn reaches the last rank without a promotion piece, or `MoveError::Illegal` if it is
+    ///   not legal in this position, in which case the board is unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// assert!(board.try_make_move(Ply::new(Square::from("e2"), Square::from("e4"))).is_ok());
+    /// assert!(board.try_make_move(Ply::new(Square::from("e4"), Square::from("e6"))).is_err());
+    /// ```
+    pub fn try_make_move(&mut self, ply: Ply) -> Result<(), MoveError> {
+        let candidates: Vec<Ply> = self
+            .get_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.start == ply.start && mv.dest == ply.dest)
+            .collect();
+        // Promotions are parsed as white pieces, so they are compared by their notation
+        let legal_move = candidates
+            .iter()
+            .find(|mv| mv.to_notation() == ply.to_notation())
+            .copied()
+            .ok_or_else(|| {
+                if ply.promoted_to.is_none() && candidates.iter().any(|mv| mv.promoted_to.is_some())
+                {
+                    MoveError::MissingPromotion(ply)
+                } else {
+                    MoveError::Illegal(ply)
+                }
+            })?;
+        self.make_move(legal_move);
+
+        Ok(())
+    }
+
     /// Makes a half-move on this board
     ///
     /// # Arguments
@@ -517,16 +1842,30 @@ impl Board {
     #[allow(clippy::too_many_lines)]
     pub fn make_move(&mut self, mut new_move: Ply) {
         let previous_move: Ply = self.history.last().copied().unwrap_or_default();
-        new_move.halfmove_clock = previous_move.halfmove_clock + 1;
+        let is_pawn_move = matches!(self.get_piece(new_move.start), Some(Kind::Pawn(_)));
+        new_move.halfmove_clock = if is_pawn_move || new_move.captured_piece.is_some() {
+            0
+        } else {
+            previous_move.halfmove_clock + 1
+        };
         new_move.castling_rights = previous_move.castling_rights;
 
-        self.make_move_en_passant_checks(&new_move);
+        self.key_history.push(self.zobrist_key);
+        self.check_history.push(self.in_check);
+        let previous_state_key = self.state_key();
 
-        if let (Some(promoted_to), Some(Kind::Pawn(c))) =
-            (new_move.promoted_to, self.get_piece(new_move.dest))
-        {
-            self.remove_piece(new_move.dest, Kind::Pawn(c));
-            self.add_piece(new_move.dest, promoted_to);
+        if new_move.is_castles {
+            self.en_passant_file = None;
+            self.castle(new_move, false);
+        } else {
+            self.make_move_en_passant_checks(&new_move);
+
+            if let (Some(promoted_to), Some(Kind::Pawn(c))) =
+                (new_move.promoted_to, self.get_piece(new_move.dest))
+            {
+                self.remove_piece(new_move.dest, Kind::Pawn(c));
+                self.add_piece(new_move.dest, promoted_to);
+            }
         }
 
         self.make_move_castling_checks(&mut new_move);
@@ -537,6 +1876,7 @@ impl Board {
             self.fullmove_counter += 1;
         }
         self.history.push(new_move);
+        self.zobrist_key ^= previous_state_key ^ self.state_key();
     }
 
     /// Handles En Passant related logic for making moves
@@ -561,81 +1901,62 @@ impl Board {
         }
     }
 
-    /// Handles Castling related logic for making moves
-    fn make_move_castling_checks(&mut self, new_move: &mut Ply) {
-        if new_move.is_castles {
-            let (rook_start, rook_dest) = match new_move.dest {
-                Square { rank: 0, file: 6 } => (Square::from("h1"), Square::from("f1")),
-                Square { rank: 0, file: 2 } => (Square::from("a1"), Square::from("d1")),
-                Square { rank: 7, file: 6 } => (Square::from("h8"), Square::from("f8")),
-                Square { rank: 7, file: 2 } => (Square::from("a8"), Square::from("d8")),
-                _ => panic!("Invalid castling king destination {}", new_move.dest),
-            };
+    /// Moves the king and rook of a castling move, or puts them back when `undo` is set
+    ///
+    /// Both pieces are lifted before either is placed, since in Chess960 the king may land where
+    /// the rook started or the other way around.
+    fn castle(&mut self, ply: Ply, undo: bool) {
+        let color = if undo {
+            self.current_turn.opposite()
+        } else {
+            self.current_turn
+        };
+        let kind = Self::castling_kind(ply, color);
+        let rank = kind.rank();
+        let mut king_squares = (
+            ply.start,
+            Square {
+                rank,
+                file: kind.king_dest_file(),
+            },
+        );
+        let mut rook_squares = (
+            self.castling_rook(kind),
+            Square {
+                rank,
+                file: kind.rook_dest_file(),
+            },
+        );
+        if undo {
+            king_squares = (king_squares.1, king_squares.0);
+            rook_squares = (rook_squares.1, rook_squares.0);
+        }
 
-            self.replace_square(rook_start, rook_dest);
+        self.remove_piece(king_squares.0, Kind::King(color));
+        self.remove_piece(rook_squares.0, Kind::Rook(color));
+        self.add_piece(king_squares.1, Kind::King(color));
+        self.add_piece(rook_squares.1, Kind::Rook(color));
+    }
 
-            match new_move.dest {
-                Square {
-                    rank: 0,
-                    file: 6 | 2,
-                } => {
-                    new_move.castling_rights.white_kingside = CastlingStatus::Unavailiable;
-                    new_move.castling_rights.white_queenside = CastlingStatus::Unavailiable;
-                }
-                Square {
-                    rank: 7,
-                    file: 6 | 2,
-                } => {
-                    new_move.castling_rights.black_kingside = CastlingStatus::Unavailiable;
-                    new_move.castling_rights.black_queenside = CastlingStatus::Unavailiable;
-                }
-                _ => panic!("Invalid castling king destination {}", new_move.dest),
-            };
-        } else if matches!(self.get_piece(new_move.dest), Some(Kind::King(_))) {
-            match self.current_turn {
-                Color::White => {
-                    new_move.castling_rights.white_kingside = CastlingStatus::Unavailiable;
-                    new_move.castling_rights.white_queenside = CastlingStatus::Unavailiable;
-                }
-                Color::Black => {
-                    new_move.castling_rights.black_kingside = CastlingStatus::Unavailiable;
-                    new_move.castling_rights.black_queenside = CastlingStatus::Unavailiable;
-                }
-            }
-        } else if matches!(self.get_piece(new_move.dest), Some(Kind::Rook(_))) {
-            match (self.current_turn, new_move.start) {
-                (Color::White, Square { rank: 0, file: 0 }) => {
-                    new_move.castling_rights.white_queenside = CastlingStatus::Unavailiable;
-                }
-                (Color::White, Square { rank: 0, file: 7 }) => {
-                    new_move.castling_rights.white_kingside = CastlingStatus::Unavailiable;
-                }
-                (Color::Black, Square { rank: 7, file: 0 }) => {
-                    new_move.castling_rights.black_queenside = CastlingStatus::Unavailiable;
-                }
-                (Color::Black, Square { rank: 7, file: 7 }) => {
-                    new_move.castling_rights.black_kingside = CastlingStatus::Unavailiable;
-                }
-                _ => (),
+    /// Handles Castling related logic for making moves
+    fn make_move_castling_checks(&self, new_move: &mut Ply) {
+        let moved_piece = self.get_piece(new_move.dest);
+        for kind in CastlingKind::for_color(self.current_turn) {
+            let moved_rook = matches!(moved_piece, Some(Kind::Rook(_)))
+                && self.castling_rook(kind) == new_move.start;
+            if new_move.is_castles || matches!(moved_piece, Some(Kind::King(_))) || moved_rook {
+                new_move
+                    .castling_rights
+                    .set(kind, CastlingStatus::Unavailiable);
             }
         }
 
-        if let Some(piece) = new_move.captured_piece {
-            if matches!(piece, Kind::Rook(_)) {
-                match (self.current_turn, new_move.dest) {
-                    (Color::White, Square { rank: 7, file: 0 }) => {
-                        new_move.castling_rights.black_queenside = CastlingStatus::Unavailiable;
-                    }
-                    (Color::White, Square { rank: 7, file: 7 }) => {
-                        new_move.castling_rights.black_kingside = CastlingStatus::Unavailiable;
-                    }
-                    (Color::Black, Square { rank: 0, file: 0 }) => {
-                        new_move.castling_rights.white_queenside = CastlingStatus::Unavailiable;
-                    }
-                    (Color::Black, Square { rank: 0, file: 7 }) => {
-                        new_move.castling_rights.white_kingside = CastlingStatus::Unavailiable;
-                    }
-                    _ => (),
+        if let Some(Kind::Rook(_)) = new_move.captured_piece {
+            for kind in CastlingKind::for_color(self.current_turn.opposite()) {
+                if self.castling_rook(kind) == new_move.dest {
+                    new_move
+                        .castling_rights
+                        .set(kind, CastlingStatus::Unavailiable);
                 }
             }
         }
@@ -660,37 +1981,29 @@ impl Board {
             .pop()
             .expect("No previous move in the board history!");
 
-        self.replace_square(old_move.dest, old_move.start);
-
-        if let Some(promoted_piece) = old_move.promoted_to {
-            self.remove_piece(old_move.start, promoted_piece);
-            self.add_piece(old_move.start, Kind::Pawn(self.current_turn.opposite()));
-        }
+        if old_move.is_castles {
+            self.castle(old_move, true);
+        } else {
+            self.replace_square(old_move.dest, old_move.start);
 
-        if let Some(captured_piece) = old_move.captured_piece {
-            if old_move.en_passant {
-                self.add_piece(
-                    Square {
-                        file: old_move.dest.file,
-                        rank: old_move.start.rank,
-                    },
-                    captured_piece,
-                );
-            } else {
-                self.add_piece(old_move.dest, captured_piece);
+            if let Some(promoted_piece) = old_move.promoted_to {
+                self.remove_piece(old_move.start, promoted_piece);
+                self.add_piece(old_move.start, Kind::Pawn(self.current_turn.opposite()));
             }
-        }
-
-        if old_move.is_castles {
-            let (rook_start, rook_dest) = match old_move.dest {
-                Square { rank: 0, file: 6 } => (Square::from("h1"), Square::from("f1")),
-                Square { rank: 0, file: 2 } => (Square::from("a1"), Square::from("d1")),
-                Square { rank: 7, file: 6 } => (Square::from("h8"), Square::from("f8")),
-                Square { rank: 7, file: 2 } => (Square::from("a8"), Square::from("d8")),
-                _ => panic!("Invalid castling king destination {}", old_move.dest),
-            };
 
-            self.replace_square(rook_dest, rook_start);
+            if let Some(captured_piece) = old_move.captured_piece {
+                if old_move.en_passant {
+                    self.add_piece(
+                        Square {
+                            file: old_move.dest.file,
+                            rank: old_move.start.rank,
+                        },
+                        captured_piece,
+                    );
+                } else {
+                    self.add_piece(old_move.dest, captured_piece);
+                }
+            }
         }
 
         if self.history.last().is_some_and(|f| f.is_double_pawn_push) {
@@ -706,7 +2019,15 @@ impl Board {
         // Cannot make a move if game is over, so all previous moves are in progress
         self.game_state = GameState::InProgress;
 
-        self.switch_turn();
+        self.current_turn = self.current_turn.opposite();
+        self.in_check = self
+            .check_history
+            .pop()
+            .expect("No previous check flag in the board history!");
+        self.zobrist_key = self
+            .key_history
+            .pop()
+            .expect("No previous key in the board history!");
     }
 }
 
@@ -731,11 +2052,14 @@ impl fmt::Display for Board {
 
 #[cfg(test)]
 mod tests {
+    extern crate test;
+
     use crate::utils::tests::check_unique_equality;
     use boardbuilder::BoardBuilder;
 
     use super::*;
     use pretty_assertions::assert_eq;
+    use test::Bencher;
 
     #[test]
     fn test_default_board() {
@@ -1068,6 +2392,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_attackers_to_square_attacked_by_several_kinds() {
+        let board = Board::from_fen("4r3/7b/5n2/3p4/q3N1Pr/3k4/8/7K w - - 0 1");
+        let square = Square::from("e4");
+
+        // The rook on h4 is blocked by the pawn on g4
+        let expected = ["d5", "f6", "h7", "e8", "a4", "d3"]
+            .into_iter()
+            .fold(Bitboard::new(0), |attackers, attacker| {
+                attackers | Bitboard::from(Square::from(attacker))
+            });
+        assert_eq!(board.attackers_to(square, Color::Black), expected);
+        assert!(board.attackers_to(square, Color::White).is_empty());
+    }
+
+    #[test]
+    fn test_attackers_to_unattacked_square() {
+        let board = BoardBuilder::construct_starting_board().build();
+
+        assert!(board
+            .attackers_to(Square::from("e4"), Color::White)
+            .is_empty());
+        assert!(board
+            .attackers_to(Square::from("e4"), Color::Black)
+            .is_empty());
+    }
+
     #[test]
     fn test_get_attacked_squares_position_1() {
         let board = Board::from_fen("r3kb1r/p2bqpp1/5n2/4Q1p1/3P4/8/PPP2PPP/RNB1K2R b KQkq - 0 13");
@@ -1209,6 +2560,33 @@ mod tests {
         assert!(!all_moves.is_empty());
     }
 
+    #[test]
+    fn test_get_move_counts_by_piece() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let counts = board.get_move_counts_by_piece();
+
+        assert_eq!(
+            counts,
+            [
+                (Kind::Pawn(Color::White), 16),
+                (Kind::Knight(Color::White), 4),
+                (Kind::Bishop(Color::White), 0),
+                (Kind::Rook(Color::White), 0),
+                (Kind::Queen(Color::White), 0),
+                (Kind::King(Color::White), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_move_counts_by_piece_black() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2r b - - 0 1");
+        let counts = board.get_move_counts_by_piece();
+
+        assert_eq!(counts[3], (Kind::Rook(Color::Black), 10));
+        assert_eq!(counts[0].1 + counts[1].1 + counts[2].1 + counts[4].1, 0);
+    }
+
     #[test]
     fn test_add_piece() {
         let mut board = BoardBuilder::construct_starting_board().build();
@@ -1837,28 +3215,254 @@ mod tests {
         assert!(board.get_piece(start).is_none());
         assert_eq!(board.current_turn, Color::Black);
 
-        board.unmake_move();
-        assert_eq!(board.get_piece(start), Some(Kind::Pawn(Color::White)));
-        assert_eq!(board.get_piece(dest), Some(Kind::Knight(Color::Black)));
-        assert_eq!(board.current_turn, Color::White);
+        board.unmake_move();
+        assert_eq!(board.get_piece(start), Some(Kind::Pawn(Color::White)));
+        assert_eq!(board.get_piece(dest), Some(Kind::Knight(Color::Black)));
+        assert_eq!(board.current_turn, Color::White);
+    }
+
+    #[test]
+    fn test_piece_counts_starting_position() {
+        let board = BoardBuilder::construct_starting_board().build();
+
+        for color in [Color::White, Color::Black] {
+            assert_eq!(board.get_piece_count(Kind::Pawn(color)), 8);
+            assert_eq!(board.get_piece_count(Kind::King(color)), 1);
+            assert_eq!(board.minor_count(color), 4);
+            assert_eq!(board.major_count(color), 3);
+        }
+        assert_eq!(board.total_material_phase(), MAX_PHASE);
+    }
+
+    #[test]
+    fn test_piece_counts_queenless_middlegame() {
+        let board = Board::from_fen("r1b2rk1/pp2bppp/2n1pn2/8/3P4/2N1BN2/PP2BPPP/R4RK1 w - - 0 12");
+
+        assert_eq!(board.minor_count(Color::White), 4);
+        assert_eq!(board.minor_count(Color::Black), 4);
+        assert_eq!(board.major_count(Color::White), 2);
+        assert_eq!(board.major_count(Color::Black), 2);
+        assert_eq!(board.get_piece_count(Kind::Pawn(Color::White)), 6);
+        assert_eq!(board.get_piece_count(Kind::Pawn(Color::Black)), 6);
+        assert_eq!(board.total_material_phase(), 16);
+    }
+
+    #[test]
+    fn test_file_status() {
+        let board = Board::from_fen("4k3/p2p4/8/8/8/8/P1P5/4K3 w - - 0 1");
+
+        assert_eq!(board.file_status(0, Color::White), FileStatus::Closed);
+        assert_eq!(board.file_status(0, Color::Black), FileStatus::Closed);
+        assert_eq!(board.file_status(2, Color::White), FileStatus::Closed);
+        assert_eq!(board.file_status(2, Color::Black), FileStatus::HalfOpen);
+        assert_eq!(board.file_status(3, Color::White), FileStatus::HalfOpen);
+        assert_eq!(board.file_status(3, Color::Black), FileStatus::Closed);
+        assert_eq!(board.file_status(7, Color::White), FileStatus::Open);
+        assert_eq!(board.file_status(7, Color::Black), FileStatus::Open);
+    }
+
+    #[test]
+    fn test_insufficient_material_lone_kings() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+        assert!(board.is_insufficient_material());
+    }
+
+    #[test]
+    fn test_insufficient_material_single_minor() {
+        let bishop = Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert!(bishop.is_insufficient_material());
+
+        let knight = Board::from_fen("4k3/8/8/2n5/8/8/8/4K3 w - - 0 1");
+        assert!(knight.is_insufficient_material());
+    }
+
+    #[test]
+    fn test_insufficient_material_same_colored_bishops() {
+        // c1 and f8 are both dark squares
+        let board = Board::from_fen("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert!(board.is_insufficient_material());
+    }
+
+    #[test]
+    fn test_sufficient_material_opposite_colored_bishops() {
+        // c1 is a dark square and c8 is a light one
+        let board = Board::from_fen("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        assert!(!board.is_insufficient_material());
+    }
+
+    #[test]
+    fn test_sufficient_material_pawn() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        assert!(!board.is_insufficient_material());
+    }
+
+    #[test]
+    fn test_sufficient_material_two_knights_and_rook() {
+        let knights = Board::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1");
+        assert!(!knights.is_insufficient_material());
+
+        let rook = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+        assert!(!rook.is_insufficient_material());
+    }
+
+    #[test]
+    fn test_pieces_starting_position() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let pieces: Vec<(Square, Kind)> = board.pieces().collect();
+        assert_eq!(pieces.len(), 32);
+
+        let back_rank = [
+            Kind::Rook,
+            Kind::Knight,
+            Kind::Bishop,
+            Kind::Queen,
+            Kind::King,
+            Kind::Bishop,
+            Kind::Knight,
+            Kind::Rook,
+        ];
+        for (file, kind) in (0..8).zip(back_rank) {
+            assert!(pieces.contains(&(Square { rank: 0, file }, kind(Color::White))));
+            assert!(pieces.contains(&(Square { rank: 7, file }, kind(Color::Black))));
+        }
+        for (square, kind) in pieces {
+            assert_eq!(board.get_piece(square), Some(kind));
+        }
+    }
+
+    #[test]
+    fn test_pieces_empty_squares_are_skipped() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+        let pieces: Vec<(Square, Kind)> = board.pieces().collect();
+
+        assert_eq!(
+            pieces,
+            vec![
+                (Square::from("a1"), Kind::Rook(Color::White)),
+                (Square::from("e1"), Kind::King(Color::White)),
+                (Square::from("e8"), Kind::King(Color::Black)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_material_balance_starting_position() {
+        let board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(board.material_balance(), 0);
+    }
+
+    #[test]
+    fn test_material_balance_imbalances() {
+        // White is up the exchange
+        let exchange = Board::from_fen("4k3/pppp4/2n5/8/8/8/PPPP4/R3K3 w - - 0 1");
+        // Black has a queen for two rooks and a pawn
+        let queen = Board::from_fen("3qk3/ppp5/8/8/8/8/PPPP4/R3K2R b - - 0 1");
+
+        assert_eq!(exchange.material_balance(), 200);
+        assert_eq!(queen.material_balance(), 200);
+    }
+
+    #[test]
+    fn test_material_balance_matches_see_values() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1");
+        assert_eq!(
+            board.material_balance(),
+            see::see_value(Kind::Knight(Color::White))
+        );
+    }
+
+    #[test]
+    fn test_is_not_in_check() {
+        let board = BoardBuilder::construct_starting_board().build();
+        assert!(!board.is_in_check(Color::White));
+    }
+
+    #[test]
+    fn test_is_in_check_white_by_queen() {
+        let board = Board::from_fen("8/1k6/2q5/8/8/2K3Q1/8/8 w - - 0 1");
+        assert!(board.is_in_check(Color::White));
+    }
+
+    #[test]
+    fn test_is_in_check_black_by_queen() {
+        let board = Board::from_fen("8/1K6/2Q5/8/8/2k3q1/8/8 b - - 0 1");
+        assert!(board.is_in_check(Color::Black));
+    }
+
+    #[test]
+    fn test_in_check_matches_is_in_check_across_moves() {
+        let mut board = Board::from_fen("8/1k6/2q5/8/8/2K3Q1/8/8 w - - 0 1");
+        assert!(board.in_check());
+
+        // The king steps out of check, the queen blocks the next check and is taken with check
+        let moves = ["c3d2", "c6d6", "g3d3", "d6d3"];
+        let mut flags = vec![board.in_check()];
+        for notation in moves {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+            assert_eq!(board.in_check(), board.is_in_check(board.current_turn));
+            flags.push(board.in_check());
+        }
+        assert_eq!(flags, vec![true, false, true, false, true]);
+
+        for expected in flags.into_iter().rev().skip(1) {
+            board.unmake_move();
+            assert_eq!(board.in_check(), board.is_in_check(board.current_turn));
+            assert_eq!(board.in_check(), expected);
+        }
+    }
+
+    #[test]
+    fn test_is_check_after_checking_move() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+        let ply = board.find_move("a1a8").unwrap();
+
+        assert!(board.is_check_after(ply));
+        assert!(!board.is_mate_after(ply));
+        assert!(!board.is_in_check(Color::Black));
+        assert_eq!(board.current_turn, Color::White);
+    }
+
+    #[test]
+    fn test_is_mate_after_mating_move() {
+        let mut board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        let ply = board.find_move("a1a8").unwrap();
+
+        assert!(board.is_check_after(ply));
+        assert!(board.is_mate_after(ply));
+    }
+
+    #[test]
+    fn test_is_check_after_quiet_move() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let ply = board.find_move("e2e4").unwrap();
+
+        assert!(!board.is_check_after(ply));
+        assert!(!board.is_mate_after(ply));
     }
 
     #[test]
-    fn test_is_not_in_check() {
-        let board = BoardBuilder::construct_starting_board().build();
-        assert!(!board.is_in_check(Color::White));
+    fn test_is_checkmate_back_rank() {
+        let mut board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1");
+
+        assert!(board.is_checkmate());
+        assert!(!board.is_stalemate());
     }
 
     #[test]
-    fn test_is_in_check_white_by_queen() {
-        let board = Board::from_fen("8/1k6/2q5/8/8/2K3Q1/8/8 w - - 0 1");
-        assert!(board.is_in_check(Color::White));
+    fn test_is_stalemate() {
+        let mut board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+
+        assert!(board.is_stalemate());
+        assert!(!board.is_checkmate());
     }
 
     #[test]
-    fn test_is_in_check_black_by_queen() {
-        let board = Board::from_fen("8/1K6/2Q5/8/8/2k3q1/8/8 b - - 0 1");
-        assert!(board.is_in_check(Color::Black));
+    fn test_is_checkmate_and_stalemate_in_progress() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+
+        assert!(!board.is_checkmate());
+        assert!(!board.is_stalemate());
     }
 
     #[test]
@@ -1914,6 +3518,122 @@ mod tests {
         assert!(board.find_move(notation_made_up).is_err());
     }
 
+    #[test]
+    fn test_chess960_castling_with_king_on_b_file() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+        assert!(board.is_chess960());
+        assert_eq!(
+            board.castling_rook(CastlingKind::WhiteKingside),
+            Square::from("h1")
+        );
+
+        let original = board.clone();
+        let queenside = board.find_move("b1a1").unwrap();
+        assert!(queenside.is_castles);
+        board.make_move(queenside);
+        assert_eq!(
+            board.get_piece(Square::from("c1")),
+            Some(Kind::King(Color::White))
+        );
+        assert_eq!(
+            board.get_piece(Square::from("d1")),
+            Some(Kind::Rook(Color::White))
+        );
+        assert_eq!(board.get_piece(Square::from("a1")), None);
+        assert_eq!(board.get_piece(Square::from("b1")), None);
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/2KR3R b - - 1 1");
+        assert_eq!(board.zobrist_key(), board.compute_zobrist_key());
+
+        board.unmake_move();
+        assert_eq!(board.to_fen(), original.to_fen());
+        assert_eq!(board.zobrist_key(), original.zobrist_key());
+
+        board.make_move(board.clone().find_move("b1h1").unwrap());
+        assert_eq!(
+            board.get_piece(Square::from("g1")),
+            Some(Kind::King(Color::White))
+        );
+        assert_eq!(
+            board.get_piece(Square::from("f1")),
+            Some(Kind::Rook(Color::White))
+        );
+        assert_eq!(board.get_piece(Square::from("h1")), None);
+    }
+
+    #[test]
+    fn test_chess960_castling_swaps_king_and_rook() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4RKR1 w GE - 0 1");
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4RKR1 w GE - 0 1");
+
+        let original = board.clone();
+        let kingside = board.find_move("f1g1").unwrap();
+        assert!(kingside.is_castles);
+        board.make_move(kingside);
+        assert_eq!(
+            board.get_piece(Square::from("g1")),
+            Some(Kind::King(Color::White))
+        );
+        assert_eq!(
+            board.get_piece(Square::from("f1")),
+            Some(Kind::Rook(Color::White))
+        );
+        assert_eq!(
+            board.castle_status(CastlingKind::WhiteQueenside),
+            CastlingStatus::Unavailiable
+        );
+
+        board.unmake_move();
+        assert_eq!(board.to_fen(), original.to_fen());
+        assert_eq!(board.zobrist_key(), original.zobrist_key());
+
+        // The king passes over the castling rook on its way to c1
+        board.make_move(board.clone().find_move("f1e1").unwrap());
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/2KR2R1 b - - 1 1");
+    }
+
+    #[test]
+    fn test_chess960_castling_through_check_is_illegal() {
+        let mut board = Board::from_fen("2r1k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+        assert!(board.find_move("b1a1").is_err());
+        assert!(board.find_move("b1h1").is_err());
+    }
+
+    #[test]
+    fn test_standard_castling_written_as_king_takes_rook_in_chess960() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        assert!(!board.is_chess960());
+        assert!(board.find_move("e1g1").is_ok());
+
+        board.set_chess960(true);
+        assert!(board.find_move("e1g1").is_err());
+        assert!(board.find_move("e1h1").unwrap().is_castles);
+        assert_eq!(board.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1");
+    }
+
+    #[test]
+    fn test_try_make_move() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+
+        assert_eq!(
+            board.try_make_move(Ply::new(Square::from("e2"), Square::from("e4"))),
+            Ok(())
+        );
+        assert_eq!(
+            board.get_piece(Square::from("e4")),
+            Some(Kind::Pawn(Color::White))
+        );
+        assert!(board.last_move().unwrap().is_double_pawn_push);
+
+        let nonsense = Ply::new(Square::from("a1"), Square::from("h8"));
+        let key = board.zobrist_key();
+        assert_eq!(
+            board.try_make_move(nonsense),
+            Err(MoveError::Illegal(nonsense))
+        );
+        assert_eq!(board.zobrist_key(), key);
+        assert_eq!(board.current_turn, Color::Black);
+    }
+
     #[test]
     fn test_is_game_over() {
         let mut board = BoardBuilder::construct_starting_board().build();
@@ -1934,6 +3654,270 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zobrist_key_stays_consistent_through_a_game() {
+        // En passant on move 3, a promotion that captures on move 5, and castling on both sides
+        let game = [
+            "e2e4", "d7d5", "e4e5", "f7f5", "e5f6", "e7e6", "f6g7", "b8c6", "g7h8q", "c8d7",
+            "g1f3", "d8e7", "f1e2", "e8c8", "e1g1", "d5d4", "c2c4", "d4c3", "b2c3", "e7c5", "h8g8",
+            "c6e5", "f3e5", "c5f2", "f1f2",
+        ];
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let mut keys = vec![board.zobrist_key()];
+
+        for notation in game {
+            board
+                .try_make_move(Ply::parse(notation).unwrap())
+                .unwrap_or_else(|e| panic!("{notation}: {e}"));
+            assert!(board.zobrist_key_is_consistent(), "{notation}");
+            keys.push(board.zobrist_key());
+        }
+
+        keys.pop();
+        while let Some(key) = keys.pop() {
+            board.unmake_move();
+            assert_eq!(board.zobrist_key(), key);
+            assert!(board.zobrist_key_is_consistent());
+        }
+    }
+
+    #[test]
+    fn test_zobrist_key_make_unmake() {
+        let mut board =
+            Board::from_fen("r3k2r/pbppqNb1/1n2pnp1/3P4/1p2P3/2N2Q1p/PPPBBPPP/1R2K2R b Kkq - 2 2");
+        let original_key = board.zobrist_key;
+        assert_eq!(board.zobrist_key, board.compute_zobrist_key());
+
+        for mv in board.get_legal_moves() {
+            board.make_move(mv);
+            assert_eq!(board.zobrist_key, board.compute_zobrist_key(), "{mv:?}");
+            for reply in board.get_legal_moves() {
+                board.make_move(reply);
+                assert_eq!(board.zobrist_key, board.compute_zobrist_key(), "{reply:?}");
+                board.unmake_move();
+            }
+            board.unmake_move();
+            assert_eq!(board.zobrist_key, original_key);
+        }
+    }
+
+    #[test]
+    fn test_halfmove_clock_resets() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        for (notation, halfmove_clock) in [("g1f3", 1), ("e7e5", 0), ("f3e5", 0), ("b8c6", 1)] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+            assert_eq!(board.get_halfmove_clock(), halfmove_clock);
+        }
+
+        board.unmake_move();
+        assert_eq!(board.get_halfmove_clock(), 0);
+        board.unmake_move();
+        board.unmake_move();
+        assert_eq!(board.get_halfmove_clock(), 1);
+    }
+
+    #[test]
+    fn test_is_threefold_repetition() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        for notation in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+            assert!(!board.is_threefold_repetition());
+        }
+
+        let mv = board.find_move("f6g8").unwrap();
+        board.make_move(mv);
+        assert!(board.is_threefold_repetition());
+        assert!(board.is_game_over());
+        assert_eq!(board.game_state, GameState::ThreefoldRepetition);
+    }
+
+    #[test]
+    fn test_is_draw_by_repetition() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        for notation in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+            assert!(!board.is_draw_by_repetition());
+        }
+
+        let mv = board.find_move("f6g8").unwrap();
+        board.make_move(mv);
+        assert!(board.is_draw_by_repetition());
+        assert!(!board.is_draw_by_fifty_moves());
+    }
+
+    #[test]
+    fn test_is_draw_by_fifty_moves() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+        let shuffle = ["a1a2", "e8d8", "a2a1", "d8e8"];
+        for notation in shuffle.iter().cycle().take(99) {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+            assert!(!board.is_draw_by_fifty_moves());
+        }
+
+        let mv = board.find_move("d8e8").unwrap();
+        board.make_move(mv);
+        assert_eq!(board.get_halfmove_clock(), 100);
+        assert!(board.is_draw_by_fifty_moves());
+        board.unmake_move();
+        assert!(!board.is_draw_by_fifty_moves());
+    }
+
+    #[test]
+    fn test_is_draw_by_fifty_moves_checkmate_takes_precedence() {
+        let board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80");
+        assert!(!board.is_draw_by_fifty_moves());
+
+        let board = Board::from_fen("R6k/8/8/8/8/8/8/7K b - - 100 80");
+        assert!(board.is_in_check(Color::Black));
+        assert!(board.is_draw_by_fifty_moves());
+    }
+
+    #[test]
+    fn test_complete_ply_castles() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let partial = Ply::new(Square::from("e1"), Square::from("c1"));
+        let ply = board.complete_ply(partial).unwrap();
+
+        assert!(ply.is_castles);
+        assert_eq!(ply.captured_piece, None);
+    }
+
+    #[test]
+    fn test_complete_ply_en_passant() {
+        let mut board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+        let partial = Ply::new(Square::from("e5"), Square::from("d6"));
+        let ply = board.complete_ply(partial).unwrap();
+
+        assert!(ply.en_passant);
+        assert_eq!(ply.captured_piece, Some(Kind::Pawn(Color::Black)));
+
+        board.make_move(ply);
+        assert_eq!(board.get_piece(Square::from("d5")), None);
+        assert_eq!(board.to_fen(), "4k3/8/3P4/8/8/8/8/4K3 b - - 0 1");
+    }
+
+    #[test]
+    fn test_complete_ply_capture_and_double_push() {
+        let board = Board::from_fen("4k3/8/8/8/8/2n5/1P6/4K3 w - - 0 1");
+
+        let capture = board
+            .complete_ply(Ply::new(Square::from("b2"), Square::from("c3")))
+            .unwrap();
+        assert_eq!(capture.captured_piece, Some(Kind::Knight(Color::Black)));
+
+        let push = board
+            .complete_ply(Ply::new(Square::from("b2"), Square::from("b4")))
+            .unwrap();
+        assert!(push.is_double_pawn_push);
+    }
+
+    #[test]
+    fn test_complete_ply_invalid() {
+        let board = BoardBuilder::construct_starting_board().build();
+
+        assert!(board
+            .complete_ply(Ply::new(Square::from("e7"), Square::from("e5")))
+            .is_err());
+        assert!(board
+            .complete_ply(Ply::new(Square::from("e2"), Square::from("e5")))
+            .is_err());
+    }
+
+    #[test]
+    fn test_is_pseudo_legal() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4r3/R3K3 w Q - 0 1");
+
+        assert!(board.is_pseudo_legal(Ply::new(Square::from("a1"), Square::from("a8"))));
+        // Leaves the king in check, but is still pseudo-legal
+        assert!(board.is_pseudo_legal(Ply::new(Square::from("a1"), Square::from("b1"))));
+        assert!(!board.is_pseudo_legal(Ply::new(Square::from("a1"), Square::from("b2"))));
+        assert!(!board.is_pseudo_legal(Ply::new(Square::from("e2"), Square::from("e1"))));
+        assert!(!board.is_pseudo_legal(Ply::new(Square::from("c3"), Square::from("c4"))));
+    }
+
+    #[test]
+    fn test_repetition_count_shuffle() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+        let mut counts = vec![];
+        for notation in shuffle.iter().cycle().take(12) {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+            counts.push(board.repetition_count());
+        }
+
+        assert_eq!(counts, [0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3]);
+
+        for expected in [2, 2, 2, 2, 1, 1, 1, 1, 0, 0, 0, 0] {
+            board.unmake_move();
+            assert_eq!(board.repetition_count(), expected);
+        }
+    }
+
+    #[test]
+    fn test_repetition_count_resets_after_pawn_move() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        for notation in ["g1f3", "g8f6", "f3g1", "f6g8", "e2e3", "e7e6"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+        }
+        for notation in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+        }
+
+        assert_eq!(board.repetition_count(), 1);
+    }
+
+    #[test]
+    fn test_is_legal_move_with_draw_repetition() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        for notation in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+        }
+
+        let moves = board.get_legal_moves_with_draws();
+        assert_eq!(moves.len(), board.get_legal_moves().len());
+        for (mv, draw) in moves {
+            if mv.to_notation() == "f6g8" {
+                assert_eq!(draw, Some(GameState::ThreefoldRepetition));
+            } else {
+                assert_eq!(draw, None, "{mv:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_legal_move_with_draw_fifty_move_rule() {
+        let mut board = Board::from_fen("7k/8/6K1/8/8/8/P7/1R6 w - - 99 80");
+
+        let quiet_move = board.find_move("b1b2").unwrap();
+        assert_eq!(
+            board.is_legal_move_with_draw(quiet_move),
+            Ok((quiet_move, Some(GameState::FiftyMoveRule)))
+        );
+
+        let pawn_move = board.find_move("a2a3").unwrap();
+        assert_eq!(
+            board.is_legal_move_with_draw(pawn_move),
+            Ok((pawn_move, None))
+        );
+
+        let mating_move = board.find_move("b1b8").unwrap();
+        assert_eq!(
+            board.is_legal_move_with_draw(mating_move),
+            Ok((mating_move, None))
+        );
+
+        let illegal_move = Ply::new(Square::from("g6"), Square::from("g7"));
+        assert!(board.is_legal_move_with_draw(illegal_move).is_err());
+    }
+
     #[test]
     fn test_get_legal_moves_count_start() {
         let mut board = BoardBuilder::construct_starting_board().build();
@@ -1943,6 +3927,51 @@ mod tests {
         assert_eq!(result, correct);
     }
 
+    #[test]
+    fn test_double_check_allows_only_king_moves() {
+        // The knight on f3 and the rook on e8 both check the king, so neither the bishop nor the
+        // rook can help by capturing one of them
+        let mut board = Board::from_fen("4r1k1/8/8/8/8/5n2/3B4/4K2R w K - 0 1");
+        assert_eq!(board.checker_count(Color::White), 2);
+        assert_eq!(
+            board.checkers(Color::White),
+            Bitboard::from(Square::from("e8")) | Bitboard::from(Square::from("f3"))
+        );
+
+        let moves = board.get_legal_moves();
+        assert!(!moves.is_empty());
+        assert!(moves
+            .iter()
+            .all(|mv| mv.start == Square::from("e1") && !mv.is_castles));
+        assert!(board
+            .get_capture_moves()
+            .iter()
+            .all(|mv| mv.start == Square::from("e1")));
+    }
+
+    #[test]
+    fn test_single_check_is_counted_once() {
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/3B4/4K2R w K - 0 1");
+        assert_eq!(board.checker_count(Color::White), 1);
+        assert_eq!(board.checker_count(Color::Black), 0);
+    }
+
+    #[test]
+    fn test_single_check_is_answered_by_capturing_or_blocking() {
+        // The rook on e8 checks along the e-file, so the knight can only block on e5 and the
+        // bishop can either block on e2 or take the rook
+        let mut board = Board::from_fen("4r1k1/8/8/1B6/8/5N2/8/4K3 w - - 0 1");
+        let mut non_king_moves: Vec<String> = board
+            .get_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.start != Square::from("e1"))
+            .map(|mv| mv.to_string())
+            .collect();
+        non_king_moves.sort();
+
+        assert_eq!(non_king_moves, ["b5e2", "b5e8", "f3e5"]);
+    }
+
     #[test]
     fn test_get_legal_moves_count_from_position_1() {
         let mut board = Board::from_fen("2k1b3/8/8/8/2K5/5Q2/5PPP/5RN1 w - - 0 1");
@@ -2138,4 +4167,219 @@ mod tests {
 
         assert_eq!(result, correct);
     }
+
+    #[test]
+    fn test_king_steps_match_make_unmake_near_sliders() {
+        let fens = [
+            "4k3/8/8/8/8/8/8/r3K3 w - - 0 1",
+            "4k3/8/8/8/7b/8/8/4K2r w - - 0 1",
+            "8/8/8/3k4/8/3Q4/8/3RK3 b - - 0 1",
+            "4k3/4r3/8/8/8/8/3P4/2BKQ3 w - - 0 1",
+            "8/8/2b5/8/4K3/8/6q1/k7 w - - 0 1",
+            "r3k2r/8/8/8/3q4/8/3p4/R3K2R w KQkq - 0 1",
+        ];
+
+        for fen in fens {
+            let mut board = Board::from_fen(fen);
+            let fast: Vec<Ply> = board
+                .get_legal_moves()
+                .into_iter()
+                .filter(|mv| board.is_king_step(*mv))
+                .collect();
+            let mut slow: Vec<Ply> = board
+                .get_all_moves()
+                .into_iter()
+                .filter(|mv| board.is_king_step(*mv))
+                .collect();
+            slow.retain(|mv| board.is_legal_move(*mv).is_ok());
+
+            assert_eq!(fast, slow, "{fen}");
+        }
+    }
+
+    #[test]
+    fn test_pinned_knight_cannot_move() {
+        let mut board = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1");
+
+        assert!(board.is_pinned(Square::from("e2")));
+        assert!(board
+            .get_legal_moves()
+            .iter()
+            .all(|mv| mv.start != Square::from("e2")));
+    }
+
+    #[test]
+    fn test_pinned_rook_slides_along_pin() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/r2RK3 w - - 0 1");
+        let mut rook_moves: Vec<String> = board
+            .get_legal_moves()
+            .into_iter()
+            .filter(|mv| mv.start == Square::from("d1"))
+            .map(|mv| mv.to_string())
+            .collect();
+        rook_moves.sort();
+
+        assert!(board.is_pinned(Square::from("d1")));
+        assert_eq!(rook_moves, vec!["d1a1", "d1b1", "d1c1"]);
+    }
+
+    #[test]
+    fn test_pinned_piece_cannot_answer_check() {
+        // Rd2 would block the bishop, but the rook is pinned to the king along the first rank
+        let mut board = Board::from_fen("4k3/8/8/8/1b6/8/8/r2RK3 w - - 0 1");
+
+        assert!(board.is_pinned(Square::from("d1")));
+        assert!(board
+            .get_legal_moves()
+            .iter()
+            .all(|mv| mv.start != Square::from("d1")));
+    }
+
+    #[test]
+    fn test_pinned_pieces() {
+        // The pawn, knight, and queen are each pinned by a different slider, while nothing lies
+        // beyond the bishop on c1
+        let board = Board::from_fen("4k3/4r3/8/8/1b6/8/3PN3/2B1KQ1r w - - 0 1");
+        let pinned = Bitboard::from(Square::from("d2"))
+            | Bitboard::from(Square::from("e2"))
+            | Bitboard::from(Square::from("f1"));
+        assert_eq!(board.pinned_pieces(Color::White), pinned);
+        assert!(board.pinned_pieces(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_is_pinned_ignores_friendly_and_absent_pieces() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/3B4/2Q1K3 w - - 0 1");
+
+        assert!(!board.is_pinned(Square::from("d2")));
+        assert!(!board.is_pinned(Square::from("e1")));
+        assert!(!board.is_pinned(Square::from("a8")));
+    }
+
+    #[test]
+    fn test_capture_moves_match_captures_among_all_moves() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "rnbqkbnr/pppp1ppp/8/8/3Pp3/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 2",
+            "n1r1k3/1P6/8/8/8/8/6p1/4K2R w - - 0 1",
+            "n1r1k3/1P6/8/8/8/8/6p1/4K2R b - - 0 1",
+            "4k3/8/2q1r3/3P4/8/8/8/K7 w - - 0 1",
+            "4k3/4r3/8/8/3p4/8/4N3/4K3 w - - 0 1",
+            "4k3/8/8/1b6/2Pp4/8/8/6K1 w - d3 0 1",
+        ];
+        for fen in fens {
+            let board = Board::from_fen(fen);
+            let mut captures = board.get_capture_moves();
+            let mut expected = board.get_filtered_moves(|ply| ply.captured_piece.is_some());
+            captures.sort_by_key(ToString::to_string);
+            expected.sort_by_key(ToString::to_string);
+
+            assert_eq!(captures, expected, "{fen}");
+        }
+    }
+
+    #[test]
+    fn test_pinned_pieces_only_generate_moves_along_their_pin() {
+        // Taking the pawn would unpin the knight from the rook on e7, and a knight can never stay
+        // on the line to its king
+        let mut knight = Board::from_fen("4k3/4r3/8/8/3p4/8/4N3/4K3 w - - 0 1");
+        assert!(knight
+            .get_filtered_moves(|ply| ply.start == Square::from("e2"))
+            .is_empty());
+        assert!(knight.get_capture_moves().is_empty());
+        assert!(knight
+            .get_legal_moves()
+            .iter()
+            .all(|ply| ply.captured_piece.is_none()));
+
+        // A pinned rook can still slide along the file, up to and including the pinner
+        let rook = Board::from_fen("4k3/4r3/8/8/3p4/8/4R3/4K3 w - - 0 1");
+        let mut moves: Vec<String> = rook
+            .get_filtered_moves(|ply| ply.start == Square::from("e2"))
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        moves.sort();
+        assert_eq!(moves, vec!["e2e3", "e2e4", "e2e5", "e2e6", "e2e7"]);
+    }
+
+    #[bench]
+    fn bench_scan_every_square_for_pieces(bencher: &mut Bencher) {
+        let board = Board::from_fen("8/5k2/8/3p4/3P4/8/2K5/8 w - - 0 1");
+        bencher.iter(|| {
+            (0..64u8)
+                .filter_map(|idx| board.get_piece(Square::from_index(idx)))
+                .filter(|piece| piece.get_color() == board.current_turn)
+                .count()
+        });
+    }
+
+    #[bench]
+    fn bench_iterate_own_pieces(bencher: &mut Bencher) {
+        let board = Board::from_fen("8/5k2/8/3p4/3P4/8/2K5/8 w - - 0 1");
+        bencher.iter(|| {
+            board
+                .bitboards
+                .white_pieces
+                .into_iter()
+                .filter_map(|square| board.get_piece(square))
+                .count()
+        });
+    }
+
+    #[test]
+    fn test_piece_square_score_make_unmake() {
+        let fens = [
+            "r3k2r/pbppqNb1/1n2pnp1/3P4/1p2P3/2N2Q1p/PPPBBPPP/1R2K2R b Kkq - 2 2",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "8/1P4k1/8/8/8/8/6p1/4K3 w - - 0 1",
+        ];
+
+        for fen in fens {
+            let mut board = Board::from_fen(fen);
+            let original = (board.mg_score(), board.eg_score());
+            assert_eq!(original, board.compute_piece_square_score());
+
+            for mv in board.get_legal_moves() {
+                board.make_move(mv);
+                assert_eq!(
+                    (board.mg_score(), board.eg_score()),
+                    board.compute_piece_square_score(),
+                    "{mv:?}"
+                );
+                for reply in board.get_legal_moves() {
+                    board.make_move(reply);
+                    assert_eq!(
+                        (board.mg_score(), board.eg_score()),
+                        board.compute_piece_square_score(),
+                        "{reply:?}"
+                    );
+                    board.unmake_move();
+                }
+                board.unmake_move();
+            }
+
+            assert_eq!((board.mg_score(), board.eg_score()), original);
+        }
+    }
+
+    #[bench]
+    fn bench_get_legal_moves_start(bencher: &mut Bencher) {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        bencher.iter(|| board.get_legal_moves());
+    }
+
+    #[bench]
+    fn bench_get_legal_moves_by_making_each_move_start(bencher: &mut Bencher) {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        bencher.iter(|| {
+            board
+                .get_all_moves()
+                .into_iter()
+                .filter(|mv| board.is_legal_move(*mv).is_ok())
+                .collect::<Vec<Ply>>()
+        });
+    }
 }
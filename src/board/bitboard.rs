@@ -194,7 +194,7 @@ impl fmt::Debug for Bitboard {
 
 impl From<Square> for Bitboard {
     fn from(square: Square) -> Self {
-        Self(1 << u8::from(square))
+        Self(1 << square.index())
     }
 }
 
@@ -223,19 +223,43 @@ impl From<Bitboard> for u64 {
     }
 }
 
-#[allow(clippy::cast_possible_truncation)]
 impl From<Bitboard> for Vec<Square> {
     fn from(bitboard: Bitboard) -> Self {
-        let mut squares = vec![];
+        bitboard.into_iter().collect()
+    }
+}
+
+/// Iterates over the set squares of a bitboard from a1 to h8 by popping the least significant bit
+pub struct Squares(u64);
 
-        let mut mask = bitboard.0;
-        while mask != 0 {
-            let idx = mask.trailing_zeros() as u8;
-            squares.push(Square::from(idx));
-            mask &= mask - 1;
+impl Iterator for Squares {
+    type Item = Square;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn next(&mut self) -> Option<Square> {
+        if self.0 == 0 {
+            return None;
         }
 
-        squares
+        let idx = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(Square::from_index(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for Squares {}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = Squares;
+
+    fn into_iter(self) -> Squares {
+        Squares(self.0)
     }
 }
 
@@ -328,3 +352,26 @@ impl Bitboard {
         63 - self.0.leading_zeros()
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_into_iter_yields_set_squares_in_order() {
+        let bitboard = Bitboard::from(Square::from("h8"))
+            | Bitboard::from(Square::from("a1"))
+            | Bitboard::from(Square::from("e4"));
+        let squares: Vec<Square> = bitboard.into_iter().collect();
+
+        assert_eq!(bitboard.into_iter().len(), 3);
+        assert_eq!(
+            squares,
+            vec![Square::from("a1"), Square::from("e4"), Square::from("h8")]
+        );
+        assert_eq!(Bitboard::new(0).into_iter().next(), None);
+    }
+}
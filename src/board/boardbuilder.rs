@@ -1,6 +1,6 @@
 use super::piece::Color;
 use super::piece::Kind as PieceKind;
-use super::ply::castling::CastlingKind;
+use super::ply::castling::{CastlingKind, CastlingRooks};
 use super::ply::Ply;
 use super::Board;
 use super::CastlingStatus;
@@ -22,6 +22,9 @@ pub struct BoardBuilder {
     pub bitboards: PieceBitboardsBuilder,
 
     pub history: Vec<Ply>,
+
+    pub castling_rooks: CastlingRooks,
+    pub chess960: bool,
 }
 
 impl BoardBuilder {
@@ -59,6 +62,9 @@ impl BoardBuilder {
             bitboards: PieceBitboardsBuilder::default(),
 
             history: vec![Ply::default()],
+
+            castling_rooks: CastlingRooks::new(),
+            chess960: false,
         }
     }
 
@@ -75,6 +81,9 @@ impl BoardBuilder {
             bitboards: PieceBitboardsBuilder::new(),
 
             history: vec![Ply::default()],
+
+            castling_rooks: CastlingRooks::new(),
+            chess960: false,
         }
     }
 
@@ -159,6 +168,49 @@ impl BoardBuilder {
         self
     }
 
+    /// Set the file the rook used for the specified `CastlingKind` starts on
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of castling the rook is used for
+    ///
+    /// * `file` - The file of the rook, from 0 for the a-file to 7 for the h-file
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The current builder
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let builder = BoardBuilder::default().castling_rook(CastlingKind::WhiteKingside, 6);
+    /// ```
+    pub const fn castling_rook(mut self, kind: CastlingKind, file: u8) -> Self {
+        self.castling_rooks.set_file(kind, file);
+        self
+    }
+
+    /// Set whether the board plays Chess960, where castling moves are written as the king
+    /// capturing its own rook
+    ///
+    /// # Arguments
+    ///
+    /// * `chess960` - Whether the board plays Chess960
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The current builder
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let builder = BoardBuilder::default().chess960(true);
+    /// ```
+    pub const fn chess960(mut self, chess960: bool) -> Self {
+        self.chess960 = chess960;
+        self
+    }
+
     /// Adds a piece on the specified square
     ///
     /// # Arguments
@@ -440,7 +492,7 @@ impl BoardBuilder {
         );
 
         self.history[0].halfmove_clock = self.halfmove_clock;
-        Board {
+        let mut board = Board {
             current_turn: self.current_turn,
             fullmove_counter: self.fullmove_counter,
             game_state: self.game_state,
@@ -449,7 +501,24 @@ impl BoardBuilder {
 
             history: self.history.clone(),
             bitboards: self.bitboards.build(),
-        }
+
+            zobrist_key: 0,
+            key_history: Vec::new(),
+
+            in_check: false,
+            check_history: Vec::new(),
+
+            mg_score: 0,
+            eg_score: 0,
+
+            castling_rooks: self.castling_rooks,
+            chess960: self.chess960,
+        };
+        board.zobrist_key = board.compute_zobrist_key();
+        board.in_check = board.is_in_check(board.current_turn);
+        (board.mg_score, board.eg_score) = board.compute_piece_square_score();
+
+        board
     }
 }
 
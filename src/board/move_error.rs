@@ -0,0 +1,52 @@
+use std::fmt;
+
+use super::Ply;
+
+/// Why a move could not be played on a board
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// The text is not a move in long algebraic notation
+    Malformed(String),
+    /// The move is well formed but not legal in the position
+    Illegal(Ply),
+    /// The move promotes a pawn but does not say which piece to promote it to
+    MissingPromotion(Ply),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Malformed(notation) => write!(f, "Malformed move: {notation}"),
+            Self::Illegal(ply) => write!(f, "Illegal move: {ply}"),
+            Self::MissingPromotion(ply) => write!(f, "Move is missing a promotion piece: {ply}"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::square::Square;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            MoveError::Malformed("e2".to_string()).to_string(),
+            "Malformed move: e2"
+        );
+        assert_eq!(
+            MoveError::Illegal(Ply::new(Square::from("e2"), Square::from("e5"))).to_string(),
+            "Illegal move: e2e5"
+        );
+        assert_eq!(
+            MoveError::MissingPromotion(Ply::new(Square::from("e7"), Square::from("e8")))
+                .to_string(),
+            "Move is missing a promotion piece: e7e8"
+        );
+    }
+}
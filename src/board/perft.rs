@@ -0,0 +1,97 @@
+use super::Board;
+
+/// Which moves perft counts at its last ply
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveLegality {
+    /// Only moves that do not leave the king in check
+    Legal,
+    #[allow(dead_code)]
+    /// Every generated move, including the ones that leave the king in check
+    PseudoLegal,
+}
+
+impl Board {
+    /// Returns the number of positions reachable in exactly `depth` plies
+    ///
+    /// Legal moves are always followed to reach the last ply, so that the search stays on real
+    /// positions. At the last ply the moves are counted according to `legality`, which lets the
+    /// legal and pseudo-legal counts be compared to tell whether a bug lies in move generation or
+    /// in filtering out moves that leave the king in check.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The number of plies to search
+    /// * `legality` - Which moves to count at the last ply
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The number of leaf nodes
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(400, board.perft(2, MoveLegality::Legal));
+    /// ```
+    pub fn perft(&mut self, depth: u8, legality: MoveLegality) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        if depth == 1 {
+            let moves = match legality {
+                MoveLegality::Legal => self.get_legal_moves(),
+                MoveLegality::PseudoLegal => self.get_all_moves(),
+            };
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in self.get_legal_moves() {
+            self.make_move(mv);
+            nodes += self.perft(depth - 1, legality);
+            self.unmake_move();
+        }
+
+        nodes
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_perft_legal_starting_position() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(board.perft(1, MoveLegality::Legal), 20);
+        assert_eq!(board.perft(2, MoveLegality::Legal), 400);
+        assert_eq!(board.perft(1, MoveLegality::PseudoLegal), 20);
+    }
+
+    #[test]
+    fn test_perft_pseudo_legal_in_check() {
+        // The rook checks along the first rank, so Kd1 and Kf1 are generated but illegal
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1");
+        let color = board.current_turn;
+        let leaves_king_in_check = board
+            .get_all_moves()
+            .into_iter()
+            .filter(|mv| {
+                board.make_move(*mv);
+                let in_check = board.is_in_check(color);
+                board.unmake_move();
+                in_check
+            })
+            .count() as u64;
+
+        let legal = board.perft(1, MoveLegality::Legal);
+        let pseudo_legal = board.perft(1, MoveLegality::PseudoLegal);
+
+        assert_eq!(legal, 3);
+        assert_eq!(pseudo_legal, 5);
+        assert_eq!(pseudo_legal - legal, leaves_king_in_check);
+    }
+}
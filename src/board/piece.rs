@@ -40,6 +40,12 @@ impl Color {
     }
 }
 
+/// The material value of a pawn, knight, bishop, rook, and queen in centipawns
+///
+/// The evaluator, the static exchange evaluation, and `Board::material_balance` all take their
+/// piece values from this table, so they cannot drift apart.
+pub const PIECE_VALUES: [i64; 5] = [100, 300, 300, 500, 900];
+
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum Kind {
     Pawn(Color),
@@ -59,6 +65,25 @@ impl fmt::Display for Kind {
 }
 
 impl Kind {
+    /// Returns the material value of the piece in centipawns from `PIECE_VALUES`, or `None` for
+    /// the king, which can never be traded
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(Kind::Rook(Color::White).material_value(), Some(500));
+    /// assert_eq!(Kind::King(Color::Black).material_value(), None);
+    /// ```
+    pub const fn material_value(self) -> Option<i64> {
+        match self {
+            Self::Pawn(_) => Some(PIECE_VALUES[0]),
+            Self::Knight(_) => Some(PIECE_VALUES[1]),
+            Self::Bishop(_) => Some(PIECE_VALUES[2]),
+            Self::Rook(_) => Some(PIECE_VALUES[3]),
+            Self::Queen(_) => Some(PIECE_VALUES[4]),
+            Self::King(_) => None,
+        }
+    }
+
     pub const fn get_color(self) -> Color {
         match self {
             Self::Pawn(c)
@@ -104,17 +129,38 @@ impl Kind {
     }
 
     pub fn get_attacks(self, square: Square, board: &Board) -> Bitboard {
+        self.get_attacks_with_blockers(square, board.bitboards.all_pieces)
+    }
+
+    /// Returns the squares this piece attacks from `square` when only `blockers` are on the board
+    ///
+    /// This is useful for looking through pieces that have been removed hypothetically, such as
+    /// when resolving a sequence of captures.
+    pub fn get_attacks_with_blockers(self, square: Square, blockers: Bitboard) -> Bitboard {
         match self {
             Self::Pawn(color) => Pawn::get_attacks(square, color),
             Self::King(_) => King::get_attacks(square),
-            Self::Queen(_) => Queen::get_attacks(square, board.bitboards.all_pieces),
-            Self::Rook(_) => Rook::get_attacks(square, board.bitboards.all_pieces),
-            Self::Bishop(_) => Bishop::get_attacks(square, board.bitboards.all_pieces),
+            Self::Queen(_) => Queen::get_attacks(square, blockers),
+            Self::Rook(_) => Rook::get_attacks(square, blockers),
+            Self::Bishop(_) => Bishop::get_attacks(square, blockers),
             Self::Knight(_) => Knight::get_attacks(square),
         }
     }
 }
 
+/// Builds every precomputed attack table up front
+///
+/// The tables are otherwise built on first use, which would charge their construction to the
+/// clock of the first search.
+pub fn init_attack_tables() {
+    let square = Square::default();
+    King::get_attacks(square);
+    Knight::get_attacks(square);
+    Pawn::get_attacks(square, Color::White);
+    Rook::get_attacks(square, Bitboard::new(0));
+    Bishop::get_attacks(square, Bitboard::new(0));
+}
+
 pub trait Piece: Clone + PartialEq + Eq {
     const WHITE_SYMBOL: &'static str;
     const BLACK_SYMBOL: &'static str;
@@ -52,21 +52,22 @@ impl Magic for Bishop {
 
     #[allow(clippy::cast_possible_truncation)]
     fn get_attacks(square: Square, blockers: Bitboard) -> Bitboard {
-        let masked_blockers = blockers & MASKS.get_or_init(Self::init_masks)[square.u8() as usize];
-        let key: u64 = ((masked_blockers * Self::MAGICS[square.u8() as usize])
-            >> (64 - Self::INDEX_BITS[square.u8() as usize]).into())
+        let masked_blockers =
+            blockers & MASKS.get_or_init(Self::init_masks)[square.index() as usize];
+        let key: u64 = ((masked_blockers * Self::MAGICS[square.index() as usize])
+            >> (64 - Self::INDEX_BITS[square.index() as usize]).into())
         .into();
 
-        ATTACKS.get_or_init(Self::init_attacks)[square.u8() as usize][key as usize]
+        ATTACKS.get_or_init(Self::init_attacks)[square.index() as usize][key as usize]
     }
 
     fn get_attacks_slow(square: Square, blockers: Bitboard) -> Bitboard {
         let rays = RAYS.get_or_init(crate::board::square::rays::Rays::new).rays;
 
-        let northwest_ray = rays[square.u8() as usize][Direction::NorthWest as usize];
-        let northeast_ray = rays[square.u8() as usize][Direction::NorthEast as usize];
-        let southwest_ray = rays[square.u8() as usize][Direction::SouthWest as usize];
-        let southeast_ray = rays[square.u8() as usize][Direction::SouthEast as usize];
+        let northwest_ray = rays[square.index() as usize][Direction::NorthWest as usize];
+        let northeast_ray = rays[square.index() as usize][Direction::NorthEast as usize];
+        let southwest_ray = rays[square.index() as usize][Direction::SouthWest as usize];
+        let southeast_ray = rays[square.index() as usize][Direction::SouthEast as usize];
 
         let mut attacks = northwest_ray | northeast_ray | southwest_ray | southeast_ray;
 
@@ -183,7 +184,7 @@ impl Bishop {
                 );
                 let second_index = (blockers.wrapping_mul(Self::MAGICS[square as usize]))
                     >> (64 - Self::INDEX_BITS[square as usize]);
-                let value = Self::get_attacks_slow(Square::from(square), blockers);
+                let value = Self::get_attacks_slow(Square::from_index(square), blockers);
                 vector[second_index as usize] = value;
             }
 
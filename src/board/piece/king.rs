@@ -25,50 +25,15 @@ impl Piece for King {
         let squares: Vec<Square> = move_mask.into();
 
         let mut moveset: Vec<Ply> = squares.into_iter().map(|s| Ply::new(square, s)).collect();
-        if square == Square::from("e1") && color == Color::White {
-            if board
-                .castling_ability(CastlingKind::WhiteKingside)
-                .expect("Tried to castle for the wrong side!")
-                == CastlingStatus::Availiable
+        for kind in CastlingKind::for_color(color) {
+            if board.is_castling_square(square, kind)
+                && board
+                    .castling_ability(kind)
+                    .expect("Tried to castle for the wrong side!")
+                    == CastlingStatus::Availiable
             {
                 moveset.push(
-                    Ply::builder(square, Square::from("g1"))
-                        .castles(true)
-                        .build(),
-                );
-            }
-            if board
-                .castling_ability(CastlingKind::WhiteQueenside)
-                .expect("Tried to castle for the wrong side!")
-                == CastlingStatus::Availiable
-            {
-                moveset.push(
-                    Ply::builder(square, Square::from("c1"))
-                        .castles(true)
-                        .build(),
-                );
-            }
-        }
-
-        if square == Square::from("e8") && color == Color::Black {
-            if board
-                .castling_ability(CastlingKind::BlackKingside)
-                .expect("Tried to castle for the wrong side!")
-                == CastlingStatus::Availiable
-            {
-                moveset.push(
-                    Ply::builder(square, Square::from("g8"))
-                        .castles(true)
-                        .build(),
-                );
-            }
-            if board
-                .castling_ability(CastlingKind::BlackQueenside)
-                .expect("Tried to castle for the wrong side!")
-                == CastlingStatus::Availiable
-            {
-                moveset.push(
-                    Ply::builder(square, Square::from("c8"))
+                    Ply::builder(square, board.castling_dest(kind))
                         .castles(true)
                         .build(),
                 );
@@ -94,7 +59,7 @@ impl Precomputed for King {
     }
 
     fn get_attacks(square: Square) -> Bitboard {
-        ATTACKS.get_or_init(Self::init_attacks)[square.u8() as usize]
+        ATTACKS.get_or_init(Self::init_attacks)[square.index() as usize]
     }
 }
 
@@ -102,12 +67,40 @@ impl Precomputed for King {
 
 #[cfg(test)]
 mod tests {
-    use super::{Color, King, Piece, Ply, Square};
+    use super::{Bitboard, Color, King, Piece, Ply, Precomputed, Square};
     use crate::board::boardbuilder::BoardBuilder;
+    use crate::board::square::Delta;
     use crate::board::Kind;
     use crate::utils::tests::check_unique_equality;
     use pretty_assertions::{assert_eq, assert_ne};
 
+    #[test]
+    fn test_king_attack_table_matches_offsets() {
+        let offsets: [(i8, i8); 8] = [
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+        ];
+        for idx in 0..64 {
+            let square = Square::from_index(idx);
+            let expected = offsets
+                .iter()
+                .filter_map(|&(rank_delta, file_delta)| {
+                    square.try_add(Delta::new(rank_delta, file_delta))
+                })
+                .fold(Bitboard::new(0), |attacks, dest| {
+                    attacks | Bitboard::from(dest)
+                });
+
+            assert_eq!(King::get_attacks(square), expected, "{square}");
+        }
+    }
+
     #[test]
     fn test_king_derived_traits() {
         let piece = King {};
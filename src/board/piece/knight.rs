@@ -44,7 +44,7 @@ impl Precomputed for Knight {
     }
 
     fn get_attacks(square: Square) -> Bitboard {
-        ATTACKS.get_or_init(Self::init_attacks)[square.u8() as usize]
+        ATTACKS.get_or_init(Self::init_attacks)[square.index() as usize]
     }
 }
 
@@ -52,13 +52,41 @@ impl Precomputed for Knight {
 
 #[cfg(test)]
 mod tests {
-    use super::{Color, Knight, Piece, Ply, Square};
+    use super::{Bitboard, Color, Knight, Piece, Ply, Precomputed, Square};
+    use crate::board::square::Delta;
     use crate::board::BoardBuilder;
     use crate::board::Kind;
     use crate::utils::tests::check_unique_equality;
     use pretty_assertions::{assert_eq, assert_ne};
     use std::collections::HashSet;
 
+    #[test]
+    fn test_knight_attack_table_matches_offsets() {
+        let offsets: [(i8, i8); 8] = [
+            (2, 1),
+            (2, -1),
+            (-2, 1),
+            (-2, -1),
+            (1, 2),
+            (1, -2),
+            (-1, 2),
+            (-1, -2),
+        ];
+        for idx in 0..64 {
+            let square = Square::from_index(idx);
+            let expected = offsets
+                .iter()
+                .filter_map(|&(rank_delta, file_delta)| {
+                    square.try_add(Delta::new(rank_delta, file_delta))
+                })
+                .fold(Bitboard::new(0), |attacks, dest| {
+                    attacks | Bitboard::from(dest)
+                });
+
+            assert_eq!(Knight::get_attacks(square), expected, "{square}");
+        }
+    }
+
     #[test]
     fn test_knight_derived_traits() {
         let piece = Knight {};
@@ -60,24 +60,25 @@ impl Piece for Pawn {
         #[allow(clippy::cast_possible_truncation)]
         let next_square_mask = match color {
             Color::White => {
-                Bitboard::new(1) << u32::from(u8::from(square)) << NEXT_SQUARE_OFFSET as u32
+                Bitboard::new(1) << u32::from(square.index()) << NEXT_SQUARE_OFFSET as u32
             }
-            Color::Black => Bitboard::new(1) << u32::from(u8::from(square)) >> NEXT_SQUARE_OFFSET,
+            Color::Black => Bitboard::new(1) << u32::from(square.index()) >> NEXT_SQUARE_OFFSET,
         } & board.bitboards.all_pieces;
 
         #[allow(clippy::cast_possible_truncation)]
         let double_next_square_mask = match color {
             Color::White => {
-                Bitboard::new(1) << u32::from(u8::from(square)) << DOUBLE_NEXT_SQUARE_OFFSET as u32
+                Bitboard::new(1) << u32::from(square.index()) << DOUBLE_NEXT_SQUARE_OFFSET as u32
             }
             Color::Black => {
-                Bitboard::new(1) << u32::from(u8::from(square)) >> DOUBLE_NEXT_SQUARE_OFFSET
+                Bitboard::new(1) << u32::from(square.index()) >> DOUBLE_NEXT_SQUARE_OFFSET
             }
         } & board.bitboards.all_pieces;
 
         // Single pawn push
-        if next_square_mask.is_empty() {
-            moveset.push(Ply::new(square, square + direction));
+        let next_square = square.try_add(direction.into());
+        if let Some(dest) = next_square.filter(|_| next_square_mask.is_empty()) {
+            moveset.push(Ply::new(square, dest));
         }
 
         // Double pawn push
@@ -85,39 +86,25 @@ impl Piece for Pawn {
             && next_square_mask.is_empty()
             && double_next_square_mask.is_empty()
         {
-            moveset.push(
-                Ply::builder(square, square + direction + direction)
-                    .double_pawn_push(true)
-                    .build(),
-            );
+            if let Some(dest) = next_square.and_then(|next| next.try_add(direction.into())) {
+                moveset.push(Ply::builder(square, dest).double_pawn_push(true).build());
+            }
         }
 
         // En Passant
         if square.rank == en_passant_rank {
-            let dest_east = square + direction + Direction::East;
-            if board
-                .en_passant_file
-                .is_some_and(|file| file == dest_east.file)
-            {
-                moveset.push(
-                    Ply::builder(square, dest_east)
-                        .en_passant(true)
-                        .captured(Kind::Pawn(color.opposite()))
-                        .build(),
-                );
-            }
-
-            let dest_west = square + direction + Direction::West;
-            if board
-                .en_passant_file
-                .is_some_and(|file| file == dest_west.file)
-            {
-                moveset.push(
-                    Ply::builder(square, dest_west)
-                        .en_passant(true)
-                        .captured(Kind::Pawn(color.opposite()))
-                        .build(),
-                );
+            let destinations = [Direction::East, Direction::West]
+                .into_iter()
+                .filter_map(|side| next_square.and_then(|next| next.try_add(side.into())));
+            for dest in destinations {
+                if board.en_passant_file.is_some_and(|file| file == dest.file) {
+                    moveset.push(
+                        Ply::builder(square, dest)
+                            .en_passant(true)
+                            .captured(Kind::Pawn(color.opposite()))
+                            .build(),
+                    );
+                }
             }
         }
 
@@ -145,7 +132,7 @@ impl PrecomputedColor for Pawn {
     }
 
     fn get_attacks(square: Square, color: Color) -> Bitboard {
-        ATTACKS.get_or_init(Self::init_attacks)[color as usize][square.u8() as usize]
+        ATTACKS.get_or_init(Self::init_attacks)[color as usize][square.index() as usize]
     }
 }
 
@@ -52,21 +52,22 @@ impl Magic for Rook {
 
     #[allow(clippy::cast_possible_truncation)]
     fn get_attacks(square: Square, blockers: Bitboard) -> Bitboard {
-        let masked_blockers = blockers & MASKS.get_or_init(Self::init_masks)[square.u8() as usize];
-        let key: u64 = ((masked_blockers * Self::MAGICS[square.u8() as usize])
-            >> (64 - Self::INDEX_BITS[square.u8() as usize]).into())
+        let masked_blockers =
+            blockers & MASKS.get_or_init(Self::init_masks)[square.index() as usize];
+        let key: u64 = ((masked_blockers * Self::MAGICS[square.index() as usize])
+            >> (64 - Self::INDEX_BITS[square.index() as usize]).into())
         .into();
 
-        ATTACKS.get_or_init(Self::init_attacks)[square.u8() as usize][key as usize]
+        ATTACKS.get_or_init(Self::init_attacks)[square.index() as usize][key as usize]
     }
 
     fn get_attacks_slow(square: Square, blockers: Bitboard) -> Bitboard {
         let rays = RAYS.get_or_init(crate::board::square::rays::Rays::new).rays;
 
-        let north_ray = rays[square.u8() as usize][Direction::North as usize];
-        let east_ray = rays[square.u8() as usize][Direction::East as usize];
-        let south_ray = rays[square.u8() as usize][Direction::South as usize];
-        let west_ray = rays[square.u8() as usize][Direction::West as usize];
+        let north_ray = rays[square.index() as usize][Direction::North as usize];
+        let east_ray = rays[square.index() as usize][Direction::East as usize];
+        let south_ray = rays[square.index() as usize][Direction::South as usize];
+        let west_ray = rays[square.index() as usize][Direction::West as usize];
 
         let mut attacks = north_ray | east_ray | south_ray | west_ray;
 
@@ -183,7 +184,7 @@ impl Rook {
                 );
                 let second_index = (blockers.wrapping_mul(Self::MAGICS[square as usize]))
                     >> (64 - Self::INDEX_BITS[square as usize]);
-                let value = Self::get_attacks_slow(Square::from(square), blockers);
+                let value = Self::get_attacks_slow(Square::from_index(square), blockers);
                 vector[second_index as usize] = value;
             }
 
@@ -502,7 +503,7 @@ mod tests {
         board.add_piece(Square::from("a2"), Kind::Pawn(Color::White));
         let piece = Kind::Rook(Color::White);
         let start_square = Square::from("d4");
-        dbg!(start_square.u8());
+        dbg!(start_square.index());
 
         let result = piece.get_moveset(start_square, &board);
         let correct = vec![
@@ -529,7 +530,7 @@ mod tests {
         board.add_piece(Square::from("a2"), Kind::Pawn(Color::Black));
         let piece = Kind::Rook(Color::Black);
         let start_square = Square::from("d4");
-        dbg!(start_square.u8());
+        dbg!(start_square.index());
 
         let result = piece.get_moveset(start_square, &board);
         let correct = vec![
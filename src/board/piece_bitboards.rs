@@ -212,6 +212,34 @@ impl PieceBitboards {
         }
     }
 
+    /// Returns the bitboard holding every piece of the specified kind
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The piece kind to look up.
+    ///
+    /// # Examples
+    /// ```
+    /// let bb = BitBoards::default();
+    /// assert_eq!(bb.get_bitboard(Kind::Pawn(Color::White)), bb.white_pawns);
+    /// ```
+    pub const fn get_bitboard(&self, kind: Kind) -> Bitboard {
+        match kind {
+            Kind::Pawn(Color::White) => self.white_pawns,
+            Kind::Knight(Color::White) => self.white_knights,
+            Kind::Bishop(Color::White) => self.white_bishops,
+            Kind::Rook(Color::White) => self.white_rooks,
+            Kind::Queen(Color::White) => self.white_queens,
+            Kind::King(Color::White) => self.white_king,
+            Kind::Pawn(Color::Black) => self.black_pawns,
+            Kind::Knight(Color::Black) => self.black_knights,
+            Kind::Bishop(Color::Black) => self.black_bishops,
+            Kind::Rook(Color::Black) => self.black_rooks,
+            Kind::Queen(Color::Black) => self.black_queens,
+            Kind::King(Color::Black) => self.black_king,
+        }
+    }
+
     /// Adds the specified piece kind to the specified square
     ///
     /// # Arguments
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use super::move_error::MoveError;
+use super::piece::Color;
 use super::{piece::Kind, square::Square, CastlingStatus};
 
 mod builder;
@@ -8,6 +10,9 @@ pub mod castling;
 use builder::Builder;
 use castling::CastlingRights;
 
+/// A list of moves produced by move generation
+pub type MoveList = Vec<Ply>;
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Ply {
     pub start: Square,
@@ -87,6 +92,51 @@ impl Ply {
         Builder::new(start, dest)
     }
 
+    /// Returns whether the move neither captures nor promotes
+    pub const fn is_quiet(self) -> bool {
+        self.captured_piece.is_none() && self.promoted_to.is_none()
+    }
+
+    /// Reads a move in long algebraic notation, such as `e2e4` or `e7e8q`
+    ///
+    /// Only the squares and the promotion are known from the text, so a promotion is always given
+    /// as a white piece. `Board::try_make_move` fills in the rest from the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `notation` - The move in long algebraic notation
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Ply, MoveError>` - The move, or `MoveError::Malformed` if the text is not a move
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(Ply::parse("e2e4"), Ok(Ply::new(Square::from("e2"), Square::from("e4"))));
+    /// assert!(Ply::parse("e2").is_err());
+    /// ```
+    pub fn parse(notation: &str) -> Result<Self, MoveError> {
+        let malformed = || MoveError::Malformed(notation.to_string());
+        if !notation.is_ascii() || !(4..=5).contains(&notation.len()) {
+            return Err(malformed());
+        }
+
+        let mut ply = Self::new(
+            Square::parse(&notation[0..2]).map_err(|_| malformed())?,
+            Square::parse(&notation[2..4]).map_err(|_| malformed())?,
+        );
+        ply.promoted_to = match notation.get(4..) {
+            Some("") | None => None,
+            Some("q") => Some(Kind::Queen(Color::White)),
+            Some("r") => Some(Kind::Rook(Color::White)),
+            Some("b") => Some(Kind::Bishop(Color::White)),
+            Some("n") => Some(Kind::Knight(Color::White)),
+            Some(_) => return Err(malformed()),
+        };
+
+        Ok(ply)
+    }
+
     pub fn to_notation(self) -> String {
         let mut notation = format!("{}{}", self.start, self.dest);
 
@@ -158,6 +208,23 @@ mod tests {
         assert_eq!(result, correct);
     }
 
+    #[test]
+    fn test_parse() {
+        let ply = Ply::parse("e7e8n").unwrap();
+        assert_eq!(ply.start, Square::from("e7"));
+        assert_eq!(ply.dest, Square::from("e8"));
+        assert_eq!(ply.promoted_to, Some(Kind::Knight(Color::White)));
+        assert_eq!(Ply::parse("g1f3").unwrap().to_notation(), "g1f3");
+
+        for notation in ["", "e2", "e2e9", "e2e4x", "e2e4qq", "i2e4", "e2é4"] {
+            assert_eq!(
+                Ply::parse(notation),
+                Err(MoveError::Malformed(notation.to_string())),
+                "{notation}"
+            );
+        }
+    }
+
     #[test]
     fn test_builder() {
         let start = Square::from("f4");
@@ -1,3 +1,5 @@
+use crate::board::piece::Color;
+
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(clippy::module_name_repetitions)]
 pub enum CastlingStatus {
@@ -15,6 +17,105 @@ pub enum CastlingKind {
     BlackQueenside,
 }
 
+impl CastlingKind {
+    /// Returns both kinds of castling for `color`, kingside first
+    pub const fn for_color(color: Color) -> [Self; 2] {
+        match color {
+            Color::White => [Self::WhiteKingside, Self::WhiteQueenside],
+            Color::Black => [Self::BlackKingside, Self::BlackQueenside],
+        }
+    }
+
+    /// Returns the color of the player who castles this way
+    pub const fn color(self) -> Color {
+        match self {
+            Self::WhiteKingside | Self::WhiteQueenside => Color::White,
+            Self::BlackKingside | Self::BlackQueenside => Color::Black,
+        }
+    }
+
+    /// Returns whether this is castling towards the h-file
+    pub const fn is_kingside(self) -> bool {
+        matches!(self, Self::WhiteKingside | Self::BlackKingside)
+    }
+
+    /// Returns the rank the king and rook castle along
+    pub const fn rank(self) -> u8 {
+        match self.color() {
+            Color::White => 0,
+            Color::Black => 7,
+        }
+    }
+
+    /// Returns the file the king ends on, which is the same in standard chess and Chess960
+    pub const fn king_dest_file(self) -> u8 {
+        if self.is_kingside() {
+            6
+        } else {
+            2
+        }
+    }
+
+    /// Returns the file the rook ends on, which is the same in standard chess and Chess960
+    pub const fn rook_dest_file(self) -> u8 {
+        if self.is_kingside() {
+            5
+        } else {
+            3
+        }
+    }
+}
+
+/// The files the castling rooks started the game on
+///
+/// These are always the a- and h-files in standard chess, but can be any file on either side of
+/// the king in Chess960.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CastlingRooks {
+    pub white_kingside: u8,
+    pub white_queenside: u8,
+    pub black_kingside: u8,
+    pub black_queenside: u8,
+}
+
+impl Default for CastlingRooks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CastlingRooks {
+    pub const fn new() -> Self {
+        Self {
+            white_kingside: 7,
+            white_queenside: 0,
+            black_kingside: 7,
+            black_queenside: 0,
+        }
+    }
+
+    /// Returns the file of the rook used for the `kind` of castling
+    pub const fn file(self, kind: CastlingKind) -> u8 {
+        match kind {
+            CastlingKind::WhiteKingside => self.white_kingside,
+            CastlingKind::WhiteQueenside => self.white_queenside,
+            CastlingKind::BlackKingside => self.black_kingside,
+            CastlingKind::BlackQueenside => self.black_queenside,
+        }
+    }
+
+    /// Sets the file of the rook used for the `kind` of castling
+    pub const fn set_file(&mut self, kind: CastlingKind, file: u8) {
+        match kind {
+            CastlingKind::WhiteKingside => self.white_kingside = file,
+            CastlingKind::WhiteQueenside => self.white_queenside = file,
+            CastlingKind::BlackKingside => self.black_kingside = file,
+            CastlingKind::BlackQueenside => self.black_queenside = file,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(clippy::module_name_repetitions)]
 pub struct CastlingRights {
@@ -44,4 +145,14 @@ impl CastlingRights {
             black_queenside: CastlingStatus::Availiable,
         }
     }
+
+    /// Sets the status of the `kind` of castling
+    pub const fn set(&mut self, kind: CastlingKind, status: CastlingStatus) {
+        match kind {
+            CastlingKind::WhiteKingside => self.white_kingside = status,
+            CastlingKind::WhiteQueenside => self.white_queenside = status,
+            CastlingKind::BlackKingside => self.black_kingside = status,
+            CastlingKind::BlackQueenside => self.black_queenside = status,
+        }
+    }
 }
@@ -0,0 +1,241 @@
+use std::fmt::Write;
+
+use super::piece::Kind;
+use super::{Board, Ply};
+
+/// Returns the letter standard algebraic notation uses for a piece, or `None` for pawns
+const fn san_letter(piece: Kind) -> Option<char> {
+    match piece {
+        Kind::Pawn(_) => None,
+        Kind::King(_) => Some('K'),
+        Kind::Queen(_) => Some('Q'),
+        Kind::Rook(_) => Some('R'),
+        Kind::Bishop(_) => Some('B'),
+        Kind::Knight(_) => Some('N'),
+    }
+}
+
+impl Board {
+    #[allow(dead_code, clippy::wrong_self_convention)]
+    /// Returns a move written in standard algebraic notation, such as "Nbd2", "exd5", "O-O", or
+    /// "e8=Q#"
+    ///
+    /// The origin of a piece move is only given when another piece of the same kind could also
+    /// reach the destination, using the file if that is enough, then the rank, then both.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - A legal move for the side to move
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The move in standard algebraic notation
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no piece on the starting square of `ply`
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// let ply = board.find_move("g1f3").unwrap();
+    /// assert_eq!(board.to_san(ply), "Nf3");
+    /// ```
+    pub fn to_san(&mut self, ply: Ply) -> String {
+        let piece = self
+            .get_piece(ply.start)
+            .expect("No piece found on the starting square of the move!");
+        let is_capture = ply.captured_piece.is_some();
+
+        let mut san = if ply.is_castles {
+            if ply.dest.file > ply.start.file {
+                "O-O".to_string()
+            } else {
+                "O-O-O".to_string()
+            }
+        } else if let Some(letter) = san_letter(piece) {
+            let mut san = letter.to_string();
+            let rivals: Vec<Ply> = self
+                .get_legal_moves()
+                .into_iter()
+                .filter(|other| {
+                    other.dest == ply.dest
+                        && other.start != ply.start
+                        && self.get_piece(other.start) == Some(piece)
+                })
+                .collect();
+
+            if !rivals.is_empty() {
+                let start = ply.start.to_string();
+                if rivals
+                    .iter()
+                    .all(|other| other.start.file != ply.start.file)
+                {
+                    san.push_str(&start[..1]);
+                } else if rivals
+                    .iter()
+                    .all(|other| other.start.rank != ply.start.rank)
+                {
+                    san.push_str(&start[1..]);
+                } else {
+                    san.push_str(&start);
+                }
+            }
+            if is_capture {
+                san.push('x');
+            }
+            san.push_str(&ply.dest.to_string());
+            san
+        } else {
+            let mut san = String::new();
+            if is_capture {
+                san.push_str(&ply.start.to_string()[..1]);
+                san.push('x');
+            }
+            san.push_str(&ply.dest.to_string());
+            if let Some(promoted_to) = ply.promoted_to.and_then(san_letter) {
+                let _ = write!(san, "={promoted_to}");
+            }
+            san
+        };
+
+        if self.is_mate_after(ply) {
+            san.push('#');
+        } else if self.is_check_after(ply) {
+            san.push('+');
+        }
+
+        san
+    }
+
+    #[allow(dead_code)]
+    /// Finds the legal move written in standard algebraic notation
+    ///
+    /// Check, mate, and annotation symbols such as "+", "#", "!", and "?" are ignored, and
+    /// castling may be written with zeros as well as with the letter O.
+    ///
+    /// # Arguments
+    ///
+    /// * `san` - The move in standard algebraic notation, such as "Nf3", "exd5", or "O-O"
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Ply, &'static str>` - The matching legal move, or an error if there is none
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// let ply = board.find_move_san("Nf3").unwrap();
+    /// assert_eq!(ply, board.find_move("g1f3").unwrap());
+    /// ```
+    pub fn find_move_san(&mut self, san: &str) -> Result<Ply, &'static str> {
+        let strip = |san: &str| {
+            san.trim_end_matches(['+', '#', '!', '?'])
+                .replace("0-0-0", "O-O-O")
+                .replace("0-0", "O-O")
+        };
+        let wanted = strip(san.trim());
+
+        self.get_legal_moves()
+            .into_iter()
+            .find(|&ply| strip(&self.to_san(ply)) == wanted)
+            .ok_or("Move not found")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    fn san(fen: &str, notation: &str) -> String {
+        let mut board = Board::from_fen(fen);
+        let ply = board.find_move(notation).unwrap();
+        board.to_san(ply)
+    }
+
+    #[test]
+    fn test_to_san_piece_move() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let ply = board.find_move("g1f3").unwrap();
+        assert_eq!(board.to_san(ply), "Nf3");
+    }
+
+    #[test]
+    fn test_to_san_disambiguated_knight() {
+        let fen = "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1";
+        assert_eq!(san(fen, "b1d2"), "Nbd2");
+
+        let fen = "4k3/8/8/N7/8/8/8/N3K3 w - - 0 1";
+        assert_eq!(san(fen, "a1b3"), "N1b3");
+
+        let fen = "4k3/8/8/N7/8/8/8/N1N1K3 w - - 0 1";
+        assert_eq!(san(fen, "a1b3"), "Na1b3");
+    }
+
+    #[test]
+    fn test_to_san_pawn_moves() {
+        let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+        assert_eq!(san(fen, "e4d5"), "exd5");
+        assert_eq!(san(fen, "e4e5"), "e5");
+
+        let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        assert_eq!(san(fen, "e5d6"), "exd6");
+    }
+
+    #[test]
+    fn test_to_san_castling() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(san(fen, "e1g1"), "O-O");
+        assert_eq!(san(fen, "e1c1"), "O-O-O");
+    }
+
+    #[test]
+    fn test_to_san_check_and_mate() {
+        assert_eq!(san("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8"), "Ra8+");
+        assert_eq!(san("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"), "Ra8#");
+        assert_eq!(san("7k/4P3/8/8/8/8/8/K7 w - - 0 1", "e7e8q"), "e8=Q+");
+    }
+
+    #[test]
+    fn test_find_move_san_round_trip() {
+        let fens = [
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+            "4k3/8/8/N7/8/8/8/N1N1K3 w - - 0 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            "3r2k1/4P3/8/8/8/8/8/4K3 w - - 0 1",
+            "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1",
+        ];
+
+        for fen in fens {
+            let mut board = Board::from_fen(fen);
+            for ply in board.get_legal_moves() {
+                let san = board.to_san(ply);
+                assert_eq!(board.find_move_san(&san), Ok(ply), "{san} in {fen}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_move_san_annotations() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+        let castle = board.find_move("e1g1").unwrap();
+        assert_eq!(board.find_move_san("0-0!?"), Ok(castle));
+
+        let check = board.find_move("a1a8").unwrap();
+        assert_eq!(board.find_move_san("Rxa8+"), Ok(check));
+        assert_eq!(board.find_move_san("Rxa8"), Ok(check));
+    }
+
+    #[test]
+    fn test_find_move_san_invalid() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        assert!(board.find_move_san("Nf6").is_err());
+        assert!(board.find_move_san("e5").is_err());
+        assert!(board.find_move_san("").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use super::bitboard::Bitboard;
+use super::piece::{Color, Kind};
+use super::{Board, Ply, Square};
+
+/// The largest number of captures that can happen on a single square
+const MAX_EXCHANGE_LENGTH: usize = 32;
+
+/// The value of the king in an exchange, large enough that capturing it outweighs everything else
+const KING_SEE_VALUE: i64 = 20_000;
+
+/// Returns the material value of a piece for the purposes of exchange evaluation
+pub const fn see_value(kind: Kind) -> i64 {
+    match kind.material_value() {
+        Some(value) => value,
+        None => KING_SEE_VALUE,
+    }
+}
+
+impl Board {
+    /// Returns the material the side to move should expect to win by playing `ply`, assuming both
+    /// sides keep recapturing on the destination square with their least valuable piece for as
+    /// long as it is profitable
+    ///
+    /// Quiet moves are evaluated the same way, so a negative value means the moved piece can be
+    /// won by the opponent. A promotion gains the value of the new piece over the pawn, and it is
+    /// the new piece that the opponent can then capture.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The move to evaluate. It must be a pseudo-legal move on this board.
+    ///
+    /// # Returns
+    ///
+    /// * `i64` - The expected material gain in centipawns
+    ///
+    /// # Examples
+    /// ```
+    /// let mut board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+    /// let ply = board.find_move("e4d5").unwrap();
+    /// assert_eq!(board.see(ply), 100);
+    /// ```
+    pub fn see(&self, ply: Ply) -> i64 {
+        let target = ply.dest;
+        let mut gain = [0i64; MAX_EXCHANGE_LENGTH];
+        let mut depth = 0;
+
+        let mut attacker = self
+            .get_piece(ply.start)
+            .expect("No piece found on the starting square of the move!");
+        let mut side = attacker.get_color();
+        let mut occupancy = self.bitboards.all_pieces & !Bitboard::from(ply.start);
+        if ply.en_passant {
+            occupancy &= !Bitboard::from(Square {
+                rank: ply.start.rank,
+                file: ply.dest.file,
+            });
+        }
+
+        gain[0] = ply.captured_piece.map_or(0, see_value);
+        if let Some(promoted_to) = ply.promoted_to {
+            gain[0] += see_value(promoted_to) - see_value(attacker);
+            attacker = promoted_to;
+        }
+
+        loop {
+            depth += 1;
+            side = side.opposite();
+            gain[depth] = see_value(attacker) - gain[depth - 1];
+            if (-gain[depth - 1]).max(gain[depth]) < 0 || depth + 1 >= MAX_EXCHANGE_LENGTH {
+                break;
+            }
+
+            let attackers = self.attackers_to_with_blockers(target, occupancy) & occupancy;
+            let Some((square, piece)) = self.least_valuable_attacker(attackers, side) else {
+                break;
+            };
+            occupancy &= !Bitboard::from(square);
+            attacker = piece;
+        }
+
+        while depth > 1 {
+            depth -= 1;
+            gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+        }
+
+        gain[0]
+    }
+
+    /// Returns every piece of either color that attacks `square` when only `occupancy` is on the
+    /// board
+    pub fn attackers_to_with_blockers(&self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let bitboards = &self.bitboards;
+        let diagonal = bitboards.white_bishops
+            | bitboards.black_bishops
+            | bitboards.white_queens
+            | bitboards.black_queens;
+        let orthogonal = bitboards.white_rooks
+            | bitboards.black_rooks
+            | bitboards.white_queens
+            | bitboards.black_queens;
+
+        (Kind::Pawn(Color::Black).get_attacks_with_blockers(square, occupancy)
+            & bitboards.white_pawns)
+            | (Kind::Pawn(Color::White).get_attacks_with_blockers(square, occupancy)
+                & bitboards.black_pawns)
+            | (Kind::Knight(Color::White).get_attacks_with_blockers(square, occupancy)
+                & (bitboards.white_knights | bitboards.black_knights))
+            | (Kind::King(Color::White).get_attacks_with_blockers(square, occupancy)
+                & (bitboards.white_king | bitboards.black_king))
+            | (Kind::Bishop(Color::White).get_attacks_with_blockers(square, occupancy) & diagonal)
+            | (Kind::Rook(Color::White).get_attacks_with_blockers(square, occupancy) & orthogonal)
+    }
+
+    /// Returns the pieces of the side to move that are in danger of being lost
+    ///
+    /// A piece is threatened when an enemy piece worth less attacks it, or when it is attacked
+    /// and nothing defends it. Kings are left out, since attacks on them are checks.
+    ///
+    /// # Returns
+    ///
+    /// * `Bitboard` - The squares of the threatened pieces
+    ///
+    /// # Examples
+    /// ```
+    /// let board = Board::from_fen("3rk3/8/8/8/3B4/8/8/4K3 w - - 0 1");
+    /// assert_eq!(board.threats(), Bitboard::from(Square::from("d4")));
+    /// ```
+    pub fn threats(&self) -> Bitboard {
+        let color = self.current_turn;
+        let (own_pieces, own_king) = match color {
+            Color::White => (self.bitboards.white_pieces, self.bitboards.white_king),
+            Color::Black => (self.bitboards.black_pieces, self.bitboards.black_king),
+        };
+        let occupancy = self.bitboards.all_pieces;
+
+        let mut threats = Bitboard::new(0);
+        for square in own_pieces & !own_king {
+            let attackers = self.attackers_to_with_blockers(square, occupancy);
+            let Some((_, attacker)) = self.least_valuable_attacker(attackers, color.opposite())
+            else {
+                continue;
+            };
+            let victim = self
+                .get_piece(square)
+                .expect("No piece found where the bitboard claimed a piece was!");
+
+            if see_value(attacker) < see_value(victim) || (attackers & own_pieces).is_empty() {
+                threats |= Bitboard::from(square);
+            }
+        }
+
+        threats
+    }
+
+    /// Returns the square and kind of the cheapest piece of `color` within `attackers`
+    fn least_valuable_attacker(&self, attackers: Bitboard, color: Color) -> Option<(Square, Kind)> {
+        let candidates = [
+            Kind::Pawn(color),
+            Kind::Knight(color),
+            Kind::Bishop(color),
+            Kind::Rook(color),
+            Kind::Queen(color),
+            Kind::King(color),
+        ];
+
+        candidates.into_iter().find_map(|kind| {
+            let matching = self.bitboards.get_bitboard(kind) & attackers;
+            #[allow(clippy::cast_possible_truncation)]
+            (!matching.is_empty())
+                .then(|| (Square::from_index(matching.bitscan_forward() as u8), kind))
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_see_free_pawn() {
+        let mut board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+        let ply = board.find_move("e4d5").unwrap();
+        assert_eq!(board.see(ply), 100);
+    }
+
+    #[test]
+    fn test_see_defended_pawn_by_queen() {
+        let mut board = Board::from_fen("4k3/8/4p3/3p4/8/8/3Q4/4K3 w - - 0 1");
+        let ply = board.find_move("d2d5").unwrap();
+        assert_eq!(board.see(ply), 100 - 900);
+    }
+
+    #[test]
+    fn test_see_equal_trade() {
+        let mut board = Board::from_fen("4k3/8/1n6/3n4/8/4N3/8/4K3 w - - 0 1");
+        let ply = board.find_move("e3d5").unwrap();
+        assert_eq!(board.see(ply), 0);
+    }
+
+    #[test]
+    fn test_see_xray_recapture() {
+        // The second rook recaptures through the first one
+        let mut board = Board::from_fen("3rk3/3r4/8/3p4/8/8/3R4/3RK3 w - - 0 1");
+        let ply = board.find_move("d2d5").unwrap();
+        assert_eq!(board.see(ply), 100 - 500);
+    }
+
+    #[test]
+    fn test_see_quiet_move_to_attacked_square() {
+        let mut board = Board::from_fen("4k3/8/8/4p3/8/8/8/3QK3 w - - 0 1");
+        let ply = board.find_move("d1d4").unwrap();
+        assert_eq!(board.see(ply), -900);
+    }
+
+    #[test]
+    fn test_see_en_passant() {
+        let mut board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+        let ply = board.find_move("e5d6").unwrap();
+        assert_eq!(board.see(ply), 100);
+    }
+
+    #[test]
+    fn test_see_capture_promotion() {
+        let mut board = Board::from_fen("3r2k1/4P3/8/8/8/8/8/4K3 w - - 0 1");
+        let capture_promotion = board.find_move("e7d8q").unwrap();
+        let capture_only = Ply {
+            promoted_to: None,
+            ..capture_promotion
+        };
+
+        assert_eq!(board.see(capture_only), 500);
+        assert_eq!(board.see(capture_promotion), 500 + 900 - 100);
+    }
+
+    #[test]
+    fn test_see_quiet_promotion() {
+        let mut board = Board::from_fen("6k1/4P3/8/8/8/8/8/4K3 w - - 0 1");
+        let ply = board.find_move("e7e8q").unwrap();
+        assert_eq!(board.see(ply), 900 - 100);
+    }
+
+    #[test]
+    fn test_threats_marks_hanging_bishop() {
+        let hanging = Board::from_fen("3rk3/8/8/8/3B4/8/8/4K3 w - - 0 1");
+        let defended = Board::from_fen("3rk3/8/8/8/3B4/4P3/8/4K3 w - - 0 1");
+        let attacked_by_pawn = Board::from_fen("4k3/8/8/4p3/3B4/4P3/8/4K3 w - - 0 1");
+
+        assert_eq!(hanging.threats(), Bitboard::from(Square::from("d4")));
+        assert!(defended.threats().is_empty());
+        assert_eq!(
+            attacked_by_pawn.threats(),
+            Bitboard::from(Square::from("d4"))
+        );
+    }
+}
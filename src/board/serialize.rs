@@ -1,4 +1,5 @@
-use super::{Board, BoardBuilder, CastlingKind, CastlingStatus, Color, Ply, Square};
+use super::{Board, BoardBuilder, CastlingKind, CastlingStatus, Color, Kind, Ply, Square};
+use std::fmt::Write;
 
 pub enum FENInstruction<'a> {
     Bitboard(&'a mut u64),
@@ -72,6 +73,26 @@ fn current_turn(builder: BoardBuilder, str: &str) -> BoardBuilder {
     }
 }
 
+/// Returns the files of `color`'s rooks on its back rank, or of its king when given `king`
+fn back_rank_files(builder: &BoardBuilder, color: Color, king: bool) -> Vec<u8> {
+    let bitboards = &builder.bitboards;
+    let (pieces, rank) = match (color, king) {
+        (Color::White, true) => (bitboards.white_king, 0),
+        (Color::White, false) => (bitboards.white_rooks, 0),
+        (Color::Black, true) => (bitboards.black_king, 7),
+        (Color::Black, false) => (bitboards.black_rooks, 7),
+    };
+
+    (0..8)
+        .filter(|file| pieces & (1 << (rank * 8 + file)) != 0)
+        .collect()
+}
+
+/// Reads the castling field, which is either standard `KQkq` or, for Chess960, Shredder-FEN
+/// rook files such as `HAha`
+///
+/// A `K` or `Q` uses the outermost rook on that side of the king, as in X-FEN. Any castling that
+/// needs a king off the e-file or a rook off the a- and h-files marks the board as Chess960.
 fn castling_rights(mut builder: BoardBuilder, str: &str) -> BoardBuilder {
     builder = builder
         .castling(CastlingKind::WhiteKingside, CastlingStatus::Unavailiable)
@@ -80,14 +101,56 @@ fn castling_rights(mut builder: BoardBuilder, str: &str) -> BoardBuilder {
         .castling(CastlingKind::BlackQueenside, CastlingStatus::Unavailiable);
 
     for chr in str.chars() {
-        builder = match chr {
-            'K' => builder.castling(CastlingKind::WhiteKingside, CastlingStatus::Availiable),
-            'k' => builder.castling(CastlingKind::BlackKingside, CastlingStatus::Availiable),
-            'Q' => builder.castling(CastlingKind::WhiteQueenside, CastlingStatus::Availiable),
-            'q' => builder.castling(CastlingKind::BlackQueenside, CastlingStatus::Availiable),
-            '-' => builder,
+        if chr == '-' {
+            continue;
+        }
+
+        let color = if chr.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let [kingside, queenside] = CastlingKind::for_color(color);
+        let king_file = back_rank_files(&builder, color, true).first().copied();
+        let rook_files = back_rank_files(&builder, color, false);
+        let (kind, file) = match chr.to_ascii_lowercase() {
+            'k' => (
+                kingside,
+                rook_files
+                    .iter()
+                    .rev()
+                    .find(|&&file| king_file.is_none_or(|king| file > king))
+                    .copied()
+                    .unwrap_or(7),
+            ),
+            'q' => (
+                queenside,
+                rook_files
+                    .iter()
+                    .find(|&&file| king_file.is_none_or(|king| file < king))
+                    .copied()
+                    .unwrap_or(0),
+            ),
+            letter @ 'a'..='h' => {
+                let file = letter as u8 - b'a';
+                let kind = if file > king_file.unwrap_or(4) {
+                    kingside
+                } else {
+                    queenside
+                };
+                builder = builder.chess960(true);
+                (kind, file)
+            }
             _ => panic!("Unknown FEN castling notation: {chr}"),
         };
+
+        let standard_file = if kind.is_kingside() { 7 } else { 0 };
+        if file != standard_file || king_file.is_some_and(|king| king != 4) {
+            builder = builder.chess960(true);
+        }
+        builder = builder
+            .castling(kind, CastlingStatus::Availiable)
+            .castling_rook(kind, file);
     }
 
     builder
@@ -130,14 +193,98 @@ fn history(mut builder: BoardBuilder) -> BoardBuilder {
 }
 
 fn halfmove_clock(builder: BoardBuilder, str: &str) -> BoardBuilder {
-    builder.halfmove_clock(str.parse().ok().unwrap())
+    builder.halfmove_clock(str.parse().unwrap_or(0))
 }
 
 fn fullmove_counter(builder: BoardBuilder, str: &str) -> BoardBuilder {
-    builder.fullmove_counter(str.parse().ok().unwrap())
+    builder.fullmove_counter(str.parse().unwrap_or(1).max(1))
+}
+
+const fn fen_symbol(piece: Kind) -> char {
+    match piece {
+        Kind::Pawn(Color::White) => 'P',
+        Kind::King(Color::White) => 'K',
+        Kind::Queen(Color::White) => 'Q',
+        Kind::Rook(Color::White) => 'R',
+        Kind::Bishop(Color::White) => 'B',
+        Kind::Knight(Color::White) => 'N',
+        Kind::Pawn(Color::Black) => 'p',
+        Kind::King(Color::Black) => 'k',
+        Kind::Queen(Color::Black) => 'q',
+        Kind::Rook(Color::Black) => 'r',
+        Kind::Bishop(Color::Black) => 'b',
+        Kind::Knight(Color::Black) => 'n',
+    }
+}
+
+/// Checks that the piece placement, side to move, castling, and en passant fields of a FEN
+/// string can be read, so that building a board from it cannot panic
+///
+/// The move counters are optional and fall back to their defaults, so they are not checked.
+fn validate_fen(fen: &str) -> Result<(), String> {
+    let fields: Vec<&str> = fen.split_ascii_whitespace().collect();
+    let [placement, turn, castling, en_passant, ..] = fields[..] else {
+        return Err(format!("Expected at least four FEN fields: {fen}"));
+    };
+
+    let ranks: Vec<&str> = placement.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(format!(
+            "Expected eight ranks in FEN piece placement: {placement}"
+        ));
+    }
+    for rank in ranks {
+        let mut squares = 0;
+        for chr in rank.chars() {
+            squares += match chr {
+                'P' | 'K' | 'Q' | 'R' | 'B' | 'N' | 'p' | 'k' | 'q' | 'r' | 'b' | 'n' => 1,
+                '1'..='8' => chr as u32 - '0' as u32,
+                _ => return Err(format!("Unknown FEN instruction: {chr}")),
+            };
+        }
+        if squares != 8 {
+            return Err(format!("Expected eight squares in FEN rank: {rank}"));
+        }
+    }
+
+    if !matches!(turn, "w" | "b") {
+        return Err(format!("The side to move must be 'w' or 'b': {turn}"));
+    }
+    if castling != "-"
+        && !castling
+            .chars()
+            .all(|chr| matches!(chr, 'K' | 'Q' | 'k' | 'q' | 'A'..='H' | 'a'..='h'))
+    {
+        return Err(format!("Unknown FEN castling notation: {castling}"));
+    }
+    if en_passant != "-" && !matches!(en_passant.as_bytes(), [b'a'..=b'h', b'3' | b'6']) {
+        return Err(format!("Unknown FEN en passant notation: {en_passant}"));
+    }
+
+    Ok(())
 }
 
 impl Board {
+    /// Returns a new board given a FEN string, or a message explaining why it is malformed
+    ///
+    /// # Arguments
+    ///
+    /// * `fen` - The FEN string, whose move counters may be left out
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Board, String>` - The board, or a message naming the field that could not be read
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(Board::try_from_fen("8/8/8/8/8/8/8/8 w - - 0 1").is_ok());
+    /// assert!(Board::try_from_fen("8/8/8/8/8/8/8/8 x - - 0 1").is_err());
+    /// ```
+    pub fn try_from_fen(fen: &str) -> Result<Self, String> {
+        validate_fen(fen)?;
+        Ok(Self::from_fen(fen))
+    }
+
     /// Returns a new board given a FEN string
     ///
     /// # Examples
@@ -159,6 +306,111 @@ impl Board {
 
         builder.build()
     }
+
+    /// Returns the square a pawn of the side to move could capture en passant on, if any
+    ///
+    /// A double pawn push only counts when an enemy pawn is next to it and can actually make
+    /// the capture.
+    fn en_passant_target(&self) -> Option<Square> {
+        let file = self.en_passant_file?;
+        let rank = match self.current_turn {
+            Color::White => 5,
+            Color::Black => 2,
+        };
+        let target = Square { rank, file };
+
+        // A pawn attacks the target exactly when an enemy pawn on the target would attack it
+        let capturers = Kind::Pawn(self.current_turn.opposite())
+            .get_attacks_with_blockers(target, self.bitboards.all_pieces)
+            & self.bitboards.get_bitboard(Kind::Pawn(self.current_turn));
+
+        (!capturers.is_empty()).then_some(target)
+    }
+
+    /// Returns the FEN string describing the board
+    ///
+    /// # Examples
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    ///     board.to_fen()
+    /// );
+    /// ```
+    #[allow(dead_code)]
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                if let Some(piece) = self.get_piece(Square { rank, file }) {
+                    if empty > 0 {
+                        write!(fen, "{empty}").unwrap();
+                        empty = 0;
+                    }
+                    fen.push(fen_symbol(piece));
+                } else {
+                    empty += 1;
+                }
+            }
+            if empty > 0 {
+                write!(fen, "{empty}").unwrap();
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match self.current_turn {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        let castling: String = [
+            (CastlingKind::WhiteKingside, 'K'),
+            (CastlingKind::WhiteQueenside, 'Q'),
+            (CastlingKind::BlackKingside, 'k'),
+            (CastlingKind::BlackQueenside, 'q'),
+        ]
+        .into_iter()
+        .filter(|(kind, _)| self.castle_status(*kind) == CastlingStatus::Availiable)
+        .map(|(kind, symbol)| {
+            if !self.is_chess960() {
+                return symbol;
+            }
+            // Shredder-FEN names the file of the rook instead
+            let file = char::from(b'a' + self.castling_rook(kind).file);
+            match kind.color() {
+                Color::White => file.to_ascii_uppercase(),
+                Color::Black => file,
+            }
+        })
+        .collect();
+        if castling.is_empty() {
+            fen.push('-');
+        } else {
+            fen.push_str(&castling);
+        }
+
+        fen.push(' ');
+        match self.en_passant_target() {
+            Some(target) => write!(fen, "{target}").unwrap(),
+            None => fen.push('-'),
+        }
+
+        write!(
+            fen,
+            " {} {}",
+            self.get_halfmove_clock(),
+            self.fullmove_counter
+        )
+        .unwrap();
+
+        fen
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -168,6 +420,30 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn try_from_fen_rejects_malformed_fields() {
+        let malformed = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQxq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e9 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq",
+        ];
+
+        for fen in malformed {
+            assert!(Board::try_from_fen(fen).is_err(), "{fen}");
+        }
+    }
+
+    #[test]
+    fn try_from_fen_matches_from_fen() {
+        let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1";
+        assert_eq!(Board::try_from_fen(fen), Ok(Board::from_fen(fen)));
+        assert!(Board::try_from_fen("4k3/8/8/8/8/8/8/4K3 b - -").is_ok());
+    }
+
     #[test]
     fn from_fen_starting_position() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
@@ -232,4 +508,127 @@ mod tests {
         let from_fen = Board::from_fen(fen);
         assert_eq!(from_fen, correct);
     }
+
+    #[test]
+    fn from_fen_mixed_rows() {
+        let board = Board::from_fen("p7/4p3/8/rnbqkbnr/7p/1P1P1P1P/8/3K4 w - - 0 1");
+        let bitboards = board.bitboards;
+
+        assert_eq!(
+            u64::from(bitboards.black_pawns),
+            1 << 56 | 1 << 52 | 1 << 31
+        );
+        assert_eq!(u64::from(bitboards.black_rooks), 1 << 32 | 1 << 39);
+        assert_eq!(u64::from(bitboards.black_knights), 1 << 33 | 1 << 38);
+        assert_eq!(u64::from(bitboards.black_bishops), 1 << 34 | 1 << 37);
+        assert_eq!(u64::from(bitboards.black_queens), 1 << 35);
+        assert_eq!(u64::from(bitboards.black_king), 1 << 36);
+        assert_eq!(
+            u64::from(bitboards.white_pawns),
+            1 << 17 | 1 << 19 | 1 << 21 | 1 << 23
+        );
+        assert_eq!(u64::from(bitboards.white_king), 1 << 3);
+        assert_eq!(u64::from(bitboards.all_pieces).count_ones(), 16);
+    }
+
+    #[test]
+    fn from_fen_row_edges() {
+        // Pieces on the first and last file of every rank, with skips on either side of them
+        let board = Board::from_fen("7r/r7/6r1/1r6/R6R/8/8/K6k w - - 0 1");
+        let bitboards = board.bitboards;
+
+        assert_eq!(
+            u64::from(bitboards.black_rooks),
+            1 << 63 | 1 << 48 | 1 << 46 | 1 << 33
+        );
+        assert_eq!(u64::from(bitboards.white_rooks), 1 << 24 | 1 << 31);
+        assert_eq!(u64::from(bitboards.white_king), 1);
+        assert_eq!(u64::from(bitboards.black_king), 1 << 7);
+    }
+
+    #[test]
+    fn from_fen_empty_ranks() {
+        let board = Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 1");
+        assert_eq!(u64::from(board.bitboards.all_pieces), 0);
+
+        let board = Board::from_fen("8/8/8/8/8/8/8/7K w - - 0 1");
+        assert_eq!(u64::from(board.bitboards.all_pieces), 1 << 7);
+    }
+
+    #[test]
+    fn from_fen_missing_move_counters() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - -");
+        assert_eq!(board.get_halfmove_clock(), 0);
+        assert_eq!(board.fullmove_counter, 1);
+    }
+
+    #[test]
+    fn from_fen_move_counters_survive_unmake() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 37 52");
+        assert_eq!(board.get_halfmove_clock(), 37);
+        assert_eq!(board.fullmove_counter, 52);
+
+        let black_move = board.find_move("e8d7").unwrap();
+        board.make_move(black_move);
+        let white_move = board.find_move("e1d2").unwrap();
+        board.make_move(white_move);
+        assert_eq!(board.get_halfmove_clock(), 39);
+        assert_eq!(board.fullmove_counter, 53);
+
+        board.unmake_move();
+        board.unmake_move();
+        assert_eq!(board.get_halfmove_clock(), 37);
+        assert_eq!(board.fullmove_counter, 52);
+    }
+
+    #[test]
+    fn to_fen_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "1k1r3r/p6p/1pp1pp2/2Np1qp1/1Q1P4/2P1PP2/PP4PP/R4nK1 w - - 0 21",
+            "5b2/pp1N2pk/2pn1q1p/3n1p1Q/3P1P2/2PB3R/PP3KPP/R1B1r3 b - - 12 31",
+            "r1bqkb1r/pp1n1ppp/2n1p3/2ppP3/3P1P2/2N1BN2/PPP3PP/R2QKB1R b KQkq - 0 1",
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w Kq d6 0 3",
+        ] {
+            let board = Board::from_fen(fen);
+            assert_eq!(board.to_fen(), fen);
+            assert_eq!(Board::from_fen(&board.to_fen()), board);
+        }
+    }
+
+    #[test]
+    fn to_fen_after_moves() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        for notation in ["e2e4", "c7c5", "g1f3"] {
+            let ply = board.find_move(notation).unwrap();
+            board.make_move(ply);
+        }
+
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+        );
+
+        // The move history is not part of a FEN, so only the position itself can round-trip
+        let reloaded = Board::from_fen(&board.to_fen());
+        assert_eq!(reloaded.bitboards, board.bitboards);
+        assert_eq!(reloaded.to_fen(), board.to_fen());
+    }
+
+    #[test]
+    fn to_fen_en_passant_only_when_capturable() {
+        let no_capturer =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert_eq!(
+            no_capturer.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+        );
+
+        let capturer =
+            Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3");
+        assert_eq!(
+            capturer.to_fen(),
+            "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3"
+        );
+    }
 }
@@ -3,18 +3,44 @@ use std::fmt;
 
 pub mod rays;
 
+/// A square of the board, given by its rank and file counted from 0 on White's side and the a-file
+///
+/// Squares are indexed from 0 to 63 in the same order as the bits of a bitboard: the index is
+/// `rank * 8 + file`, so a1 is 0, h1 is 7, a8 is 56 and h8 is 63. See `Square::index` and
+/// `Square::from_index`.
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Default, Ord, PartialOrd)]
 pub struct Square {
     pub rank: u8,
     pub file: u8,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Delta {
     rank_delta: i8,
     file_delta: i8,
 }
 
-#[derive(Clone, Copy)]
+impl Delta {
+    /// Creates a new offset between two squares
+    ///
+    /// # Arguments
+    ///
+    /// * `rank_delta` - The number of ranks to move, positive towards Black's side
+    /// * `file_delta` - The number of files to move, positive towards the h-file
+    ///
+    /// # Example
+    /// ```
+    /// let knight_jump = Delta::new(2, 1);
+    /// ```
+    pub const fn new(rank_delta: i8, file_delta: i8) -> Self {
+        Self {
+            rank_delta,
+            file_delta,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Direction {
     North,
     NorthEast,
@@ -26,6 +52,43 @@ pub enum Direction {
     NorthWest,
 }
 
+impl Direction {
+    /// Returns the direction pointing the opposite way
+    ///
+    /// # Example
+    /// ```
+    /// assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+    /// ```
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::NorthEast => Self::SouthWest,
+            Self::East => Self::West,
+            Self::SouthEast => Self::NorthWest,
+            Self::South => Self::North,
+            Self::SouthWest => Self::NorthEast,
+            Self::West => Self::East,
+            Self::NorthWest => Self::SouthEast,
+        }
+    }
+}
+
+impl From<Direction> for Delta {
+    /// Creates the offset of a single step in `direction`
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => Self::new(1, 0),
+            Direction::NorthEast => Self::new(1, 1),
+            Direction::East => Self::new(0, 1),
+            Direction::SouthEast => Self::new(-1, 1),
+            Direction::South => Self::new(-1, 0),
+            Direction::SouthWest => Self::new(-1, -1),
+            Direction::West => Self::new(0, -1),
+            Direction::NorthWest => Self::new(1, -1),
+        }
+    }
+}
+
 // TODO: Change this into a TryFrom
 #[allow(clippy::fallible_impl_from)]
 impl From<&str> for Square {
@@ -42,22 +105,12 @@ impl From<&str> for Square {
     /// let squareA1 = Square::from("a1");
     /// let squareD4 = Square::from("d4");
     /// ```
-    #[allow(clippy::cast_possible_truncation)]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `algebraic_notation` is not a valid square, see `Square::parse`
     fn from(algebraic_notation: &str) -> Self {
-        let mut iter = algebraic_notation.chars();
-        let filechar: char = iter.next().unwrap();
-
-        let file: u8 = filechar as u8 - b'a';
-        let rank: u8 = (iter
-            .next()
-            .unwrap()
-            .to_string()
-            .parse::<u16>()
-            .ok()
-            .unwrap()
-            - 1) as u8;
-
-        Self { rank, file }
+        Self::parse(algebraic_notation).expect("Invalid square in algebraic notation")
     }
 }
 
@@ -82,48 +135,82 @@ impl From<String> for Square {
 }
 
 impl From<u8> for Square {
-    /// Creates a new square from an integer
+    /// Creates a new square from its index, see `Square::from_index`
     ///
     /// # Arguments
     ///
-    /// * `value` - A number that represents the given square, with h1 being 0 and a8 being 63
+    /// * `value` - The index of the square, with a1 being 0 and h8 being 63
     ///
     /// # Examples
     /// ```
-    /// let squareA1 = Square::from(55);
-    /// let squareA8 = Square::from(0);
+    /// let squareA1 = Square::from(0);
+    /// let squareA8 = Square::from(56);
     /// ```
     fn from(value: u8) -> Self {
-        let file: u8 = value % 8;
-        let rank: u8 = value >> 3;
-        Self { rank, file }
+        Self::from_index(value)
     }
 }
 
 impl From<Square> for u8 {
-    /// Converts a square to a u8 representation, where 0 is in the bottom right corner and 63 is in the top left
+    /// Converts a square to its index, see `Square::index`
     ///
     /// # Arguments
     ///
-    /// * `value` - A number that represents the given square, with h1 being 0 and a8 being 63
+    /// * `value` - The square to convert
     ///
     /// # Examples
     /// ```
-    /// let squareA1 = Square::from(Square { rank: 0, file: 0 });
-    /// let squareA8 = Square::from(Square { rank: 7, file: 0 });
+    /// assert_eq!(u8::from(Square::from("a1")), 0);
+    /// assert_eq!(u8::from(Square::from("a8")), 56);
     /// ```
     fn from(value: Square) -> Self {
-        value.rank * 8 + value.file
+        value.index()
     }
 }
 
 impl From<Square> for u64 {
     fn from(square: Square) -> Self {
-        1u64 << u8::from(square)
+        1u64 << square.index()
     }
 }
 
 impl Square {
+    /// Parses a square from algebraic notation, rejecting anything that is not a lowercase file
+    /// from a to h followed by a rank from 1 to 8
+    ///
+    /// Prefer this over `Square::from` for input that comes from outside of the engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `algebraic_notation` - A string that represents the square in algebraic notation
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Square, &'static str>` - The square, or a message describing why the input is
+    ///   not a square
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(Square::parse("e2"), Ok(Square { rank: 1, file: 4 }));
+    /// assert!(Square::parse("z9").is_err());
+    /// ```
+    pub fn parse(algebraic_notation: &str) -> Result<Self, &'static str> {
+        let &[file, rank] = algebraic_notation.as_bytes() else {
+            return Err("Square must be a file followed by a rank");
+        };
+        if !(b'a'..=b'h').contains(&file) {
+            return Err("File must be between a and h");
+        }
+        if !(b'1'..=b'8').contains(&rank) {
+            return Err("Rank must be between 1 and 8");
+        }
+
+        Ok(Self {
+            rank: rank - b'1',
+            file: file - b'a',
+        })
+    }
+
     /// Returns a u64 mask filled with 0s except for a 1 in the designated square
     ///
     /// # Arguments
@@ -166,19 +253,71 @@ impl Square {
         0x_01010101_01010101 << self.file
     }
 
-    /// Converts a square to it's u8 representation, where 0 is in the bottom right corner and 63 is in the top left
+    /// Creates a square from its index, the position of its bit in a bitboard
+    ///
+    /// The index counts along each rank from the a-file to the h-file and then up the ranks from
+    /// White's side, so a1 is 0, h1 is 7, a8 is 56 and h8 is 63.
     ///
     /// # Arguments
     ///
-    /// * `square` - The square to be converted
+    /// * `idx` - The index of the square, which must be less than 64
+    ///
+    /// # Returns
+    ///
+    /// * `Square` - The square with the given index
     ///
     /// # Examples
     /// ```
-    /// let num = Square::new("a1").u8();
+    /// assert_eq!(Square::from_index(0), Square::from("a1"));
+    /// assert_eq!(Square::from_index(63), Square::from("h8"));
+    /// ```
+    pub const fn from_index(idx: u8) -> Self {
+        Self {
+            rank: idx / 8,
+            file: idx % 8,
+        }
+    }
+
+    /// Returns the index of the square, the position of its bit in a bitboard
+    ///
+    /// This is the inverse of `Square::from_index`, so a1 is 0, h1 is 7, a8 is 56 and h8 is 63.
+    ///
+    /// # Returns
+    ///
+    /// * `u8` - The index of the square, from 0 to 63
+    ///
+    /// # Examples
     /// ```
-    pub const fn u8(self) -> u8 {
+    /// assert_eq!(Square::from("c5").index(), 34);
+    /// ```
+    pub const fn index(self) -> u8 {
         self.rank * 8 + self.file
     }
+
+    /// Returns the square `delta` away from this one, or `None` if it would be off the board
+    ///
+    /// Unlike adding a `Delta` directly, this never constructs a square outside of the board, so
+    /// move generators do not have to filter out invalid destinations afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The offset to move by
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Square>` - The square moved to, or `None` if it is off the board
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(Square::from("a1").try_add(Direction::North.into()), Some(Square::from("a2")));
+    /// assert_eq!(Square::from("a1").try_add(Direction::West.into()), None);
+    /// ```
+    pub fn try_add(self, delta: Delta) -> Option<Self> {
+        let rank = self.rank.checked_add_signed(delta.rank_delta)?;
+        let file = self.file.checked_add_signed(delta.file_delta)?;
+
+        (rank < 8 && file < 8).then_some(Self { rank, file })
+    }
 }
 
 impl std::ops::Add<Delta> for Square {
@@ -197,42 +336,7 @@ impl std::ops::Add<Direction> for Square {
     type Output = Self;
 
     fn add(self, direction: Direction) -> Self {
-        let delta = match direction {
-            Direction::North => Delta {
-                rank_delta: 1,
-                file_delta: 0,
-            },
-            Direction::NorthEast => Delta {
-                rank_delta: 1,
-                file_delta: 1,
-            },
-            Direction::East => Delta {
-                rank_delta: 0,
-                file_delta: 1,
-            },
-            Direction::SouthEast => Delta {
-                rank_delta: -1,
-                file_delta: 1,
-            },
-            Direction::South => Delta {
-                rank_delta: -1,
-                file_delta: 0,
-            },
-            Direction::SouthWest => Delta {
-                rank_delta: -1,
-                file_delta: -1,
-            },
-            Direction::West => Delta {
-                rank_delta: 0,
-                file_delta: -1,
-            },
-            Direction::NorthWest => Delta {
-                rank_delta: 1,
-                file_delta: -1,
-            },
-        };
-
-        self + delta
+        self + Delta::from(direction)
     }
 }
 
@@ -253,7 +357,7 @@ impl fmt::Display for Square {
 mod tests {
     use super::*;
     use crate::board::bitboard::Bitboard;
-    use pretty_assertions::assert_eq;
+    use pretty_assertions::{assert_eq, assert_ne};
 
     #[test]
     fn test_derived_traits() {
@@ -273,6 +377,22 @@ mod tests {
         assert_eq!(result, correct);
     }
 
+    #[test]
+    fn test_parse_valid() {
+        assert_eq!(Square::parse("a1"), Ok(Square { rank: 0, file: 0 }));
+        assert_eq!(Square::parse("e2"), Ok(Square { rank: 1, file: 4 }));
+        assert_eq!(Square::parse("h8"), Ok(Square { rank: 7, file: 7 }));
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        for input in [
+            "", "e", "z9", "i1", "a0", "a9", "E2", "2e", "e22", "e2 ", "é2",
+        ] {
+            assert!(Square::parse(input).is_err(), "{input:?} should not parse");
+        }
+    }
+
     #[test]
     fn test_display_oob_rank() {
         let square = Square { rank: 9, file: 5 };
@@ -375,6 +495,76 @@ mod tests {
         assert_eq!(before.file - 1, after.file);
     }
 
+    #[test]
+    fn test_try_add_from_a1() {
+        let a1 = Square::from("a1");
+
+        assert_eq!(
+            a1.try_add(Direction::North.into()),
+            Some(Square::from("a2"))
+        );
+        assert_eq!(
+            a1.try_add(Direction::NorthEast.into()),
+            Some(Square::from("b2"))
+        );
+        assert_eq!(a1.try_add(Direction::East.into()), Some(Square::from("b1")));
+        assert_eq!(a1.try_add(Direction::SouthEast.into()), None);
+        assert_eq!(a1.try_add(Direction::South.into()), None);
+        assert_eq!(a1.try_add(Direction::SouthWest.into()), None);
+        assert_eq!(a1.try_add(Direction::West.into()), None);
+        assert_eq!(a1.try_add(Direction::NorthWest.into()), None);
+    }
+
+    #[test]
+    fn test_try_add_from_h8() {
+        let h8 = Square::from("h8");
+
+        assert_eq!(h8.try_add(Direction::North.into()), None);
+        assert_eq!(h8.try_add(Direction::NorthEast.into()), None);
+        assert_eq!(h8.try_add(Direction::East.into()), None);
+        assert_eq!(h8.try_add(Direction::SouthEast.into()), None);
+        assert_eq!(
+            h8.try_add(Direction::South.into()),
+            Some(Square::from("h7"))
+        );
+        assert_eq!(
+            h8.try_add(Direction::SouthWest.into()),
+            Some(Square::from("g7"))
+        );
+        assert_eq!(h8.try_add(Direction::West.into()), Some(Square::from("g8")));
+        assert_eq!(h8.try_add(Direction::NorthWest.into()), None);
+    }
+
+    #[test]
+    fn test_try_add_knight_jump_off_the_board() {
+        assert_eq!(Square::from("g1").try_add(Delta::new(1, 2)), None);
+        assert_eq!(
+            Square::from("g1").try_add(Delta::new(2, 1)),
+            Some(Square::from("h3"))
+        );
+    }
+
+    #[test]
+    fn test_direction_opposite() {
+        let square = Square::from("d4");
+        let directions = [
+            Direction::North,
+            Direction::NorthEast,
+            Direction::East,
+            Direction::SouthEast,
+            Direction::South,
+            Direction::SouthWest,
+            Direction::West,
+            Direction::NorthWest,
+        ];
+
+        for direction in directions {
+            assert_ne!(direction, direction.opposite());
+            assert_eq!(direction, direction.opposite().opposite());
+            assert_eq!(square, square + direction + direction.opposite());
+        }
+    }
+
     #[test]
     fn test_direction_inverse() {
         let square = Square { rank: 4, file: 4 };
@@ -524,75 +714,93 @@ mod tests {
     }
 
     #[test]
-    fn test_u8_a1() {
+    fn test_index_a1() {
         let start_square = Square::from("a1");
-        let result = start_square.u8();
+        let result = start_square.index();
         let correct = 0;
 
         assert_eq!(result, correct);
     }
 
     #[test]
-    fn test_u8_h1() {
+    fn test_index_h1() {
         let start_square = Square::from("h1");
-        let result = start_square.u8();
+        let result = start_square.index();
         let correct = 7;
 
         assert_eq!(result, correct);
     }
 
     #[test]
-    fn test_u8_a8() {
+    fn test_index_a8() {
         let start_square = Square::from("a8");
-        let result = start_square.u8();
+        let result = start_square.index();
         let correct = 56;
 
         assert_eq!(result, correct);
     }
 
     #[test]
-    fn test_u8_h8() {
+    fn test_index_h8() {
         let start_square = Square::from("h8");
-        let result = start_square.u8();
+        let result = start_square.index();
         let correct = 63;
 
         assert_eq!(result, correct);
     }
 
     #[test]
-    fn test_u8_c5() {
+    fn test_index_c5() {
         let start_square = Square::from("c5");
-        let result = start_square.u8();
+        let result = start_square.index();
         let correct = 34;
 
         assert_eq!(result, correct);
     }
 
     #[test]
-    fn test_u8_f3() {
+    fn test_index_f3() {
         let start_square = Square::from("f3");
-        let result = start_square.u8();
+        let result = start_square.index();
         let correct = 21;
 
         assert_eq!(result, correct);
     }
 
     #[test]
-    fn test_u8_identity() {
+    fn test_index_identity() {
         for i in 0..64 {
-            let square = Square::from(i);
-            let result = square.u8();
+            let square = Square::from_index(i);
+            let result = square.index();
             assert_eq!(result, i);
         }
     }
 
+    #[test]
+    fn test_from_index_round_trip() {
+        for rank in 0..8 {
+            for file in 0..8 {
+                let square = Square { rank, file };
+                assert_eq!(Square::from_index(square.index()), square);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_index_corners() {
+        assert_eq!(Square::from_index(0), Square::from("a1"));
+        assert_eq!(Square::from_index(7), Square::from("h1"));
+        assert_eq!(Square::from_index(56), Square::from("a8"));
+        assert_eq!(Square::from_index(63), Square::from("h8"));
+    }
+
     #[test]
     fn test_from() {
         for file in (b'a'..=b'h').map(char::from) {
             for rank in 1..=8 {
                 let square = Square::from(format!("{}{}", file, rank));
-                let num = square.u8();
-                assert_eq!(square, Square::from(num));
+                let num = square.index();
+                assert_eq!(square, Square::from_index(num));
             }
         }
     }
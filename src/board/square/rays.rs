@@ -32,7 +32,7 @@ fn init_rays() -> [[Bitboard; 8]; 64] {
 
         rays_at_square[Direction::West as usize] = Bitboard::new((1 << idx) - (1 << (idx & 56)));
 
-        let square = Square::from(idx as u8);
+        let square = Square::from_index(idx as u8);
 
         rays_at_square[Direction::NorthEast as usize] = Bitboard::new(0x8040_2010_0804_0200)
             .shift_east(square.file)
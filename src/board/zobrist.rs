@@ -0,0 +1,133 @@
+use super::piece::{Color, Kind};
+use super::ply::castling::CastlingKind;
+use super::square::Square;
+use std::sync::OnceLock;
+
+/// Random keys used to incrementally hash a position
+///
+/// Every piece on every square, every castling right, every en passant file, and the side to move
+/// has a key. A position's hash is the XOR of the keys of everything present in it.
+pub struct Zobrist {
+    pieces: [[u64; 64]; 12],
+    castling: [u64; 4],
+    en_passant: [u64; 8],
+    black_to_move: u64,
+}
+
+pub static ZOBRIST: OnceLock<Zobrist> = OnceLock::new();
+
+const SEED: u64 = 0x5EED_C0FF_EE00_2024;
+
+/// Advances the `SplitMix64` generator, returning the next pseudo-random number
+const fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Zobrist {
+    /// Generates the keys from a fixed seed, so hashes are reproducible between runs
+    pub fn new() -> Self {
+        let mut state = SEED;
+
+        let mut pieces = [[0; 64]; 12];
+        for piece in &mut pieces {
+            for key in piece.iter_mut() {
+                *key = splitmix64(&mut state);
+            }
+        }
+
+        let mut castling = [0; 4];
+        for key in &mut castling {
+            *key = splitmix64(&mut state);
+        }
+
+        let mut en_passant = [0; 8];
+        for key in &mut en_passant {
+            *key = splitmix64(&mut state);
+        }
+
+        Self {
+            pieces,
+            castling,
+            en_passant,
+            black_to_move: splitmix64(&mut state),
+        }
+    }
+
+    /// Returns the shared keys, generating them on first use
+    pub fn get() -> &'static Self {
+        ZOBRIST.get_or_init(Self::new)
+    }
+
+    /// Returns the key for `piece` standing on `square`
+    pub const fn piece(&self, piece: Kind, square: Square) -> u64 {
+        let kind_idx = match piece {
+            Kind::Pawn(_) => 0,
+            Kind::Knight(_) => 1,
+            Kind::Bishop(_) => 2,
+            Kind::Rook(_) => 3,
+            Kind::Queen(_) => 4,
+            Kind::King(_) => 5,
+        };
+        let color_offset = match piece.get_color() {
+            Color::White => 0,
+            Color::Black => 6,
+        };
+
+        self.pieces[kind_idx + color_offset][square.index() as usize]
+    }
+
+    /// Returns the key for the castling right `kind` being available
+    pub const fn castling(&self, kind: CastlingKind) -> u64 {
+        self.castling[kind as usize]
+    }
+
+    /// Returns the key for en passant being possible on `file`
+    pub const fn en_passant(&self, file: u8) -> u64 {
+        self.en_passant[file as usize]
+    }
+
+    /// Returns the key toggled whenever the side to move changes
+    pub const fn black_to_move(&self) -> u64 {
+        self.black_to_move
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_keys_are_reproducible() {
+        let first = Zobrist::new();
+        let second = Zobrist::new();
+
+        assert_eq!(first.pieces, second.pieces);
+        assert_eq!(first.castling, second.castling);
+        assert_eq!(first.en_passant, second.en_passant);
+        assert_eq!(first.black_to_move, second.black_to_move);
+    }
+
+    #[test]
+    fn test_keys_are_unique() {
+        let keys = Zobrist::get();
+        let mut seen = HashSet::new();
+
+        for key in keys
+            .pieces
+            .iter()
+            .flatten()
+            .chain(keys.castling.iter())
+            .chain(keys.en_passant.iter())
+            .chain(std::iter::once(&keys.black_to_move))
+        {
+            assert!(seen.insert(*key));
+        }
+    }
+}
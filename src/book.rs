@@ -0,0 +1,365 @@
+use crate::board::piece::{Color, Kind};
+use crate::board::ply::castling::{CastlingKind, CastlingStatus};
+use crate::board::square::Square;
+use crate::board::{Board, Ply};
+use rand::Rng;
+use std::fs;
+
+pub mod random;
+
+use random::RANDOM64;
+
+/// The number of bytes each entry takes up in a Polyglot book
+const ENTRY_SIZE: usize = 16;
+
+/// Where the castling rights start in `RANDOM64`
+const CASTLING_OFFSET: usize = 768;
+/// Where the en passant files start in `RANDOM64`
+const EN_PASSANT_OFFSET: usize = 772;
+/// Where the side to move is in `RANDOM64`
+const TURN_OFFSET: usize = 780;
+
+/// A single move suggested for a position by a Polyglot book
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BookEntry {
+    key: u64,
+    mv: u16,
+    weight: u16,
+}
+
+/// An opening book in the Polyglot format
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Book {
+    entries: Vec<BookEntry>,
+}
+
+impl Book {
+    /// Parses a Polyglot book from its raw contents
+    ///
+    /// Each entry is 16 big-endian bytes: the position's key, the move, the move's weight, and
+    /// learning data, which is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of a `.bin` book
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - The book, or a message explaining why it could not be parsed
+    ///
+    /// # Example
+    /// ```
+    /// let book = Book::from_bytes(&[]).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(format!(
+                "A Polyglot book must be a multiple of {ENTRY_SIZE} bytes long, but this one is {}",
+                bytes.len()
+            ));
+        }
+
+        let mut entries: Vec<BookEntry> = bytes
+            .chunks_exact(ENTRY_SIZE)
+            .map(|chunk| BookEntry {
+                key: u64::from_be_bytes([
+                    chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], chunk[5], chunk[6], chunk[7],
+                ]),
+                mv: u16::from_be_bytes([chunk[8], chunk[9]]),
+                weight: u16::from_be_bytes([chunk[10], chunk[11]]),
+            })
+            .collect();
+        // Books are written sorted by key, but nothing enforces it
+        entries.sort_by_key(|entry| entry.key);
+
+        Ok(Self { entries })
+    }
+
+    /// Reads a Polyglot book from a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the `.bin` book
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - The book, or a message explaining why it could not be read
+    ///
+    /// # Example
+    /// ```
+    /// let book = Book::load("book.bin");
+    /// ```
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read book {path}: {e}"))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Picks one of the book's moves for the position at random, favoring moves with a higher
+    /// weight
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to look up
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Ply>` - A legal move from the book, or `None` if the position is not in it
+    ///
+    /// # Example
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// let book = Book::load("book.bin").unwrap();
+    /// let book_move = book.probe(&mut board);
+    /// ```
+    pub fn probe(&self, board: &mut Board) -> Option<Ply> {
+        let key = polyglot_key(board);
+        let first = self.entries.partition_point(|entry| entry.key < key);
+        let candidates: Vec<(Ply, u32)> = self.entries[first..]
+            .iter()
+            .take_while(|entry| entry.key == key)
+            .filter_map(|entry| Some((decode_move(board, entry.mv)?, u32::from(entry.weight))))
+            .collect();
+
+        let total: u32 = candidates.iter().map(|&(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rand::thread_rng().gen_range(0..total);
+        for (ply, weight) in candidates {
+            if roll < weight {
+                return Some(ply);
+            }
+            roll -= weight;
+        }
+
+        None
+    }
+}
+
+/// Returns the index of a piece within the piece section of `RANDOM64`
+const fn piece_index(kind: Kind) -> usize {
+    let (offset, color) = match kind {
+        Kind::Pawn(color) => (0, color),
+        Kind::Knight(color) => (2, color),
+        Kind::Bishop(color) => (4, color),
+        Kind::Rook(color) => (6, color),
+        Kind::Queen(color) => (8, color),
+        Kind::King(color) => (10, color),
+    };
+
+    match color {
+        Color::White => offset + 1,
+        Color::Black => offset,
+    }
+}
+
+/// Returns the key Polyglot books use for the position
+///
+/// This is a Zobrist hash like `Board::zobrist_key`, but built from the fixed numbers in
+/// `RANDOM64` so that it matches the books. The en passant file only counts when a pawn of the
+/// side to move stands ready to capture.
+///
+/// # Arguments
+///
+/// * `board` - The position to hash
+///
+/// # Returns
+///
+/// * `u64` - The Polyglot key of the position
+///
+/// # Example
+/// ```
+/// let board = BoardBuilder::construct_starting_board().build();
+/// assert_eq!(polyglot_key(&board), 0x463B_9618_1691_FC9C);
+/// ```
+pub fn polyglot_key(board: &Board) -> u64 {
+    let mut key = 0;
+
+    for index in 0..64u8 {
+        if let Some(kind) = board.get_piece(Square::from_index(index)) {
+            key ^= RANDOM64[64 * piece_index(kind) + usize::from(index)];
+        }
+    }
+
+    let castling = [
+        CastlingKind::WhiteKingside,
+        CastlingKind::WhiteQueenside,
+        CastlingKind::BlackKingside,
+        CastlingKind::BlackQueenside,
+    ];
+    for (idx, kind) in castling.into_iter().enumerate() {
+        if board.castle_status(kind) == CastlingStatus::Availiable {
+            key ^= RANDOM64[CASTLING_OFFSET + idx];
+        }
+    }
+
+    if let Some(file) = board.en_passant_file() {
+        let rank = match board.current_turn {
+            Color::White => 4,
+            Color::Black => 3,
+        };
+        let pawn = Some(Kind::Pawn(board.current_turn));
+        let can_capture = [file.wrapping_sub(1), file + 1]
+            .into_iter()
+            .filter(|&file| file < 8)
+            .any(|file| board.get_piece(Square { rank, file }) == pawn);
+
+        if can_capture {
+            key ^= RANDOM64[EN_PASSANT_OFFSET + usize::from(file)];
+        }
+    }
+
+    if board.current_turn == Color::White {
+        key ^= RANDOM64[TURN_OFFSET];
+    }
+
+    key
+}
+
+/// Converts a move from a Polyglot book into a legal move on the board, if it is one
+///
+/// Polyglot writes castling as the king capturing its own rook, so outside of Chess960, which
+/// writes castling the same way, those moves are translated to the king's real destination first.
+fn decode_move(board: &mut Board, mv: u16) -> Option<Ply> {
+    #[allow(clippy::cast_possible_truncation)]
+    let square = |bits: u16| Square {
+        rank: ((bits >> 3) & 7) as u8,
+        file: (bits & 7) as u8,
+    };
+    let start = square(mv >> 6);
+    let mut dest = square(mv);
+
+    let is_king = matches!(board.get_piece(start), Some(Kind::King(_)));
+    if is_king && !board.is_chess960() && start.file == 4 && dest.rank == start.rank {
+        match dest.file {
+            7 => dest.file = 6,
+            0 => dest.file = 2,
+            _ => (),
+        }
+    }
+
+    let promotion = match (mv >> 12) & 7 {
+        1 => "n",
+        2 => "b",
+        3 => "r",
+        4 => "q",
+        _ => "",
+    };
+
+    board.find_move(&format!("{start}{dest}{promotion}")).ok()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    /// Encodes a book entry the way it is stored in a `.bin` file
+    fn entry_bytes(key: u64, mv: u16, weight: u16) -> Vec<u8> {
+        let mut bytes = key.to_be_bytes().to_vec();
+        bytes.extend(mv.to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+
+        bytes
+    }
+
+    /// Encodes a move in the Polyglot format from its start and destination square indexes
+    const fn encode_move(start: u16, dest: u16) -> u16 {
+        start << 6 | dest
+    }
+
+    #[test]
+    fn test_polyglot_key_reference_positions() {
+        // The positions and keys given in the Polyglot format's specification
+        let positions = [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                0x463B_9618_1691_FC9C,
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                0x823C_9B50_FD11_4196,
+            ),
+            (
+                "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+                0x0756_B944_61C5_0FB0,
+            ),
+            (
+                "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2",
+                0x662F_AFB9_65DB_29D4,
+            ),
+            (
+                "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+                0x22A4_8B5A_8E47_FF78,
+            ),
+            (
+                "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3",
+                0x652A_607C_A3F2_42C1,
+            ),
+            (
+                "rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4",
+                0x00FD_D303_C946_BDD9,
+            ),
+            (
+                "rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3",
+                0x3C81_23EA_7B06_7637,
+            ),
+            (
+                "rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4",
+                0x5C3F_9B82_9B27_9560,
+            ),
+        ];
+
+        for (fen, key) in positions {
+            assert_eq!(polyglot_key(&Board::from_fen(fen)), key, "{fen}");
+        }
+    }
+
+    #[test]
+    fn test_probe_start_position() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let e2e4 = encode_move(12, 28);
+        let book = Book::from_bytes(&entry_bytes(0x463B_9618_1691_FC9C, e2e4, 1)).unwrap();
+
+        assert_eq!(book.probe(&mut board).unwrap().to_string(), "e2e4");
+    }
+
+    #[test]
+    fn test_probe_skips_zero_weights() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let key = polyglot_key(&board);
+        let mut bytes = entry_bytes(key, encode_move(11, 27), 0);
+        bytes.extend(entry_bytes(key, encode_move(6, 21), 5));
+        bytes.extend(entry_bytes(key + 1, encode_move(12, 28), 5));
+        let book = Book::from_bytes(&bytes).unwrap();
+
+        for _ in 0..10 {
+            assert_eq!(book.probe(&mut board).unwrap().to_string(), "g1f3");
+        }
+    }
+
+    #[test]
+    fn test_probe_castling() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let e1h1 = encode_move(4, 7);
+        let book = Book::from_bytes(&entry_bytes(polyglot_key(&board), e1h1, 1)).unwrap();
+
+        assert_eq!(book.probe(&mut board).unwrap().to_string(), "e1g1");
+    }
+
+    #[test]
+    fn test_probe_missing_position() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(Book::default().probe(&mut board), None);
+    }
+
+    #[test]
+    fn test_from_bytes_truncated() {
+        assert!(Book::from_bytes(&[0; 17]).is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::board::{Board, Ply};
+use crate::evaluate::Evaluator;
+use crate::search::Search;
+use std::collections::HashMap;
+
+/// A position from an EPD test suite, along with the operations that describe it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Epd {
+    /// The piece placement, side to move, castling rights, and en passant square
+    pub fen: String,
+    /// Every operation's operand, keyed by its opcode, with any quotes removed
+    pub operations: HashMap<String, String>,
+}
+
+impl Epd {
+    #[allow(dead_code)]
+    /// Parses a single EPD line
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The four FEN fields followed by operations, each ending with a semicolon
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, String>` - The parsed line, or a message explaining why it is malformed,
+    ///   including when its FEN fields do not describe a board
+    ///
+    /// # Example
+    /// ```
+    /// let epd = Epd::parse("6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id \"mate\";").unwrap();
+    /// assert_eq!(epd.id(), Some("mate"));
+    /// ```
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = Vec::new();
+        let mut rest = line.trim();
+        for _ in 0..4 {
+            if rest.is_empty() {
+                return Err(format!("Expected four FEN fields in EPD line: {line}"));
+            }
+            let (field, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            fields.push(field);
+            rest = remainder.trim_start();
+        }
+        let fen = fields.join(" ");
+        Board::try_from_fen(&fen)?;
+
+        let operations = rest
+            .split(';')
+            .map(str::trim)
+            .filter(|operation| !operation.is_empty())
+            .map(|operation| {
+                let (opcode, operand) = operation.split_once(' ').unwrap_or((operation, ""));
+                (
+                    opcode.to_string(),
+                    operand.trim().trim_matches('"').to_string(),
+                )
+            })
+            .collect();
+
+        Ok(Self { fen, operations })
+    }
+
+    #[allow(dead_code)]
+    /// Returns the board the line describes, using its `hmvc` and `fmvn` operations for the move
+    /// counters when they are given
+    ///
+    /// # Example
+    /// ```
+    /// let epd = Epd::parse("6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#;").unwrap();
+    /// let board = epd.board();
+    /// ```
+    pub fn board(&self) -> Board {
+        let halfmove_clock = self.operations.get("hmvc").map_or("0", String::as_str);
+        let fullmove_counter = self.operations.get("fmvn").map_or("1", String::as_str);
+
+        Board::from_fen(&format!("{} {halfmove_clock} {fullmove_counter}", self.fen))
+    }
+
+    #[allow(dead_code)]
+    /// Returns the `id` operation naming the position, if there is one
+    pub fn id(&self) -> Option<&str> {
+        self.operations.get("id").map(String::as_str)
+    }
+
+    #[allow(dead_code)]
+    /// Returns the moves given by the `bm` operation, which a solver is expected to find
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Ply>, String>` - The best moves, which are empty if there is no `bm`
+    ///   operation, or a message naming a move that is not legal in the position
+    ///
+    /// # Example
+    /// ```
+    /// let epd = Epd::parse("6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#;").unwrap();
+    /// assert_eq!(epd.best_moves().unwrap()[0].to_string(), "a1a8");
+    /// ```
+    pub fn best_moves(&self) -> Result<Vec<Ply>, String> {
+        self.moves("bm")
+    }
+
+    #[allow(dead_code)]
+    /// Returns the moves given by the `am` operation, which a solver is expected to avoid
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Ply>, String>` - The moves to avoid, which are empty if there is no `am`
+    ///   operation, or a message naming a move that is not legal in the position
+    pub fn avoid_moves(&self) -> Result<Vec<Ply>, String> {
+        self.moves("am")
+    }
+
+    /// Resolves the space separated SAN moves of the operation `opcode`
+    fn moves(&self, opcode: &str) -> Result<Vec<Ply>, String> {
+        let Some(operand) = self.operations.get(opcode) else {
+            return Ok(Vec::new());
+        };
+        let mut board = self.board();
+
+        operand
+            .split_whitespace()
+            .map(|san| {
+                board
+                    .find_move_san(san)
+                    .map_err(|e| format!("Invalid {opcode} move {san}: {e}"))
+            })
+            .collect()
+    }
+}
+
+#[allow(dead_code)]
+/// Searches the position of an EPD line and returns whether the engine's choice satisfies it
+///
+/// The move found must be one of the `bm` moves, if there are any, and none of the `am` moves.
+///
+/// # Arguments
+///
+/// * `epd` - The EPD line to solve
+/// * `evaluator` - The evaluator to search with
+/// * `depth` - The depth to search to
+///
+/// # Returns
+///
+/// * `Result<bool, String>` - Whether the position was solved, or a message explaining why the
+///   line could not be used
+///
+/// # Example
+/// ```
+/// let solved = solves("6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#;", &SimpleEvaluator::new(), 2);
+/// assert_eq!(solved, Ok(true));
+/// ```
+pub fn solves<T: Evaluator>(epd: &str, evaluator: &T, depth: usize) -> Result<bool, String> {
+    let epd = Epd::parse(epd)?;
+    let best_moves = epd.best_moves()?;
+    let avoid_moves = epd.avoid_moves()?;
+
+    let mut search = Search::new(&epd.board(), evaluator, None);
+    let found = search
+        .search(Some(depth))
+        .ok_or("The position has no legal moves")?;
+    let same_move = |mv: &Ply| {
+        mv.start == found.start && mv.dest == found.dest && mv.promoted_to == found.promoted_to
+    };
+
+    Ok((best_moves.is_empty() || best_moves.iter().any(same_move))
+        && !avoid_moves.iter().any(same_move))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluate::simple_evaluator::SimpleEvaluator;
+    use pretty_assertions::assert_eq;
+
+    const WAC_001: &str =
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";";
+    const WAC_002: &str = "8/7p/5k2/5p2/p1p2P2/Pr1pPK2/1P1R3P/8 b - - bm Rxb2; id \"WAC.002\";";
+
+    #[test]
+    fn test_parse_wac() {
+        let epd = Epd::parse(WAC_001).unwrap();
+
+        assert_eq!(
+            epd.fen,
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - -"
+        );
+        assert_eq!(epd.id(), Some("WAC.001"));
+        assert_eq!(
+            epd.board(),
+            Board::from_fen("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1")
+        );
+
+        let best_moves = epd.best_moves().unwrap();
+        assert_eq!(best_moves.len(), 1);
+        assert_eq!(best_moves[0].to_string(), "g3g6");
+        assert_eq!(epd.avoid_moves(), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_black_to_move_capture() {
+        let epd = Epd::parse(WAC_002).unwrap();
+        let best_moves = epd.best_moves().unwrap();
+
+        assert_eq!(epd.id(), Some("WAC.002"));
+        assert_eq!(best_moves[0].to_string(), "b3b2");
+        assert!(best_moves[0].captured_piece.is_some());
+    }
+
+    #[test]
+    fn test_parse_avoid_moves_and_counters() {
+        let epd = Epd::parse("4k3/8/8/8/8/8/4P3/4K3 w - - am e3 Kd1; hmvc 7; fmvn 30;").unwrap();
+        let avoid_moves = epd.avoid_moves().unwrap();
+        let board = epd.board();
+
+        assert_eq!(avoid_moves.len(), 2);
+        assert_eq!(avoid_moves[1].to_string(), "e1d1");
+        assert_eq!(board.get_halfmove_clock(), 7);
+        assert_eq!(board.fullmove_counter, 30);
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        assert!(Epd::parse("4k3/8/8/8/8/8/4P3/4K3 w").is_err());
+        assert!(Epd::parse("4k3/8/8/8/8/4P3/4K3 w - - bm Kd1;").is_err());
+        assert!(Epd::parse("4k3/8/8/8/8/8/4P3/4X3 w - - bm Kd1;").is_err());
+        assert!(Epd::parse("4k3/8/8/8/8/8/4P3/4K3 white - - bm Kd1;").is_err());
+        assert!(solves(
+            "4k3/8/8/8/8/8/4P3/4K3 w KQ? - bm Kd1;",
+            &SimpleEvaluator::new(),
+            1
+        )
+        .is_err());
+
+        let epd = Epd::parse("4k3/8/8/8/8/8/4P3/4K3 w - - bm Qh5;").unwrap();
+        assert!(epd.best_moves().is_err());
+    }
+
+    #[test]
+    fn test_solves() {
+        let evaluator = SimpleEvaluator::new();
+
+        assert_eq!(
+            solves("6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#;", &evaluator, 2),
+            Ok(true)
+        );
+        assert_eq!(
+            solves("6k1/5ppp/8/8/8/8/8/R5K1 w - - am Ra8#;", &evaluator, 2),
+            Ok(false)
+        );
+    }
+}
@@ -1,7 +1,40 @@
 use super::board::Board;
 
+pub mod coordination;
+pub mod endgame;
+pub mod king_safety;
+pub mod mobility;
+pub mod params;
+pub mod pawn_structure;
+pub mod piece_square_tables;
 pub mod simple_evaluator;
+pub mod texel;
+
+/// The largest magnitude a static evaluation may have
+///
+/// Everything beyond this is left to the search for mate scores, so no amount of material on a
+/// constructed board can be mistaken for a forced mate.
+pub const MAX_EVAL: i64 = 30_000;
 
 pub trait Evaluator: Clone {
     fn evaluate(&self, board: &mut Board) -> i64;
 }
+
+/// Clamps a raw evaluation into `-MAX_EVAL..=MAX_EVAL`
+///
+/// The clamp is symmetric, so negating a clamped score for the other side is always exact.
+///
+/// # Example
+/// ```
+/// assert_eq!(clamp_eval(i64::MAX), MAX_EVAL);
+/// assert_eq!(clamp_eval(i64::MIN), -MAX_EVAL);
+/// ```
+pub const fn clamp_eval(score: i64) -> i64 {
+    if score > MAX_EVAL {
+        MAX_EVAL
+    } else if score < -MAX_EVAL {
+        -MAX_EVAL
+    } else {
+        score
+    }
+}
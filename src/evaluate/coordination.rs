@@ -0,0 +1,61 @@
+use crate::board::piece::{Color, Kind};
+use crate::board::square::Square;
+use crate::board::Board;
+
+/// Returns how many of `color`'s knights and bishops are defended by one of its own pawns
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose minor pieces are counted
+///
+/// # Returns
+///
+/// * `i64` - The number of pawn-defended minor pieces
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/3N4/4P3/8/8/4K3 w - - 0 1");
+/// assert_eq!(pawn_defended_minors(&board, Color::White), 1);
+/// ```
+pub fn pawn_defended_minors(board: &Board, color: Color) -> i64 {
+    let pawns = board.bitboards.get_bitboard(Kind::Pawn(color));
+    let minors: Vec<Square> = (board.bitboards.get_bitboard(Kind::Knight(color))
+        | board.bitboards.get_bitboard(Kind::Bishop(color)))
+    .into();
+
+    // A pawn defends a square exactly when an enemy pawn standing there would attack it
+    let defended = minors
+        .into_iter()
+        .filter(|&square| {
+            !(Kind::Pawn(color.opposite())
+                .get_attacks_with_blockers(square, board.bitboards.all_pieces)
+                & pawns)
+                .is_empty()
+        })
+        .count();
+
+    i64::try_from(defended).unwrap_or(i64::MAX)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_pawn_defended_minors() {
+        let board = Board::from_fen("4k3/8/2b5/3N4/1p2P3/8/8/4K3 w - - 0 1");
+
+        assert_eq!(pawn_defended_minors(&board, Color::White), 1);
+        assert_eq!(pawn_defended_minors(&board, Color::Black), 0);
+    }
+
+    #[test]
+    fn test_pawn_in_front_does_not_defend() {
+        let board = Board::from_fen("4k3/8/8/3P4/3N4/8/8/4K3 w - - 0 1");
+        assert_eq!(pawn_defended_minors(&board, Color::White), 0);
+    }
+}
@@ -0,0 +1,271 @@
+use super::params::EvalParams;
+use crate::board::piece::{Color, Kind};
+use crate::board::square::Square;
+use crate::board::Board;
+
+/// Returns the pieces `color` has on the board, including its king
+const fn pieces(board: &Board, color: Color) -> u32 {
+    match color {
+        Color::White => board.bitboards.white_pieces.count_ones(),
+        Color::Black => board.bitboards.black_pieces.count_ones(),
+    }
+}
+
+/// Returns whether one side has only two knights against a bare king
+///
+/// Two knights cannot force mate against a lone king, so the extra material is worthless. Once
+/// the weaker side has anything else, such as a pawn, it can be stalemate-proofed and mated, so
+/// the position is not treated as a draw.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+///
+/// # Returns
+///
+/// * `bool` - Whether the position is a two knights against a bare king fortress
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1");
+/// assert!(is_two_knights_fortress(&board));
+/// ```
+pub fn is_two_knights_fortress(board: &Board) -> bool {
+    [Color::White, Color::Black].into_iter().any(|strong| {
+        pieces(board, strong) == 3
+            && board.get_piece_count(Kind::Knight(strong)) == 2
+            && pieces(board, strong.opposite()) == 1
+    })
+}
+
+/// Returns the number of king steps between two squares
+fn distance(from: Square, to: Square) -> i64 {
+    i64::from(from.rank.abs_diff(to.rank).max(from.file.abs_diff(to.file)))
+}
+
+/// Returns `square` as seen from `color`'s side of the board, so that its pawns always advance
+/// up the ranks
+const fn relative(square: Square, color: Color) -> Square {
+    match color {
+        Color::White => square,
+        Color::Black => Square {
+            rank: 7 - square.rank,
+            file: square.file,
+        },
+    }
+}
+
+/// Returns the squares that win the ending if the stronger king stands on one, whoever is to
+/// move, for a pawn on `pawn` seen from its own side of the board
+fn key_squares(pawn: Square) -> Vec<Square> {
+    let (files, ranks) = match pawn.file {
+        // A rook pawn is only won once the king shuts the defender out of the corner
+        0 | 7 => {
+            let file = if pawn.file == 0 { 1 } else { 6 };
+            (file..=file, 6..=7)
+        }
+        _ => {
+            let ranks = match pawn.rank {
+                0..=3 => pawn.rank + 2..=pawn.rank + 2,
+                4 => pawn.rank + 1..=pawn.rank + 2,
+                _ => pawn.rank + 1..=pawn.rank + 1,
+            };
+            (pawn.file - 1..=pawn.file + 1, ranks)
+        }
+    };
+
+    ranks
+        .flat_map(|rank| files.clone().map(move |file| Square { rank, file }))
+        .filter(|&square| {
+            square
+                != Square {
+                    rank: 7,
+                    file: pawn.file,
+                }
+        })
+        .collect()
+}
+
+/// Scores a king and pawn against king ending from the point of view of the side to move
+///
+/// The ending is drawn if the defending king can take the pawn straight away or has reached the
+/// corner in front of a rook pawn, and won if the pawn outruns the defending king to promotion or
+/// the stronger king stands on one of the pawn's key squares. Other positions score about a pawn
+/// for the stronger side, less the distance of its king from the key squares.
+///
+/// # Arguments
+///
+/// * `board` - The board to score
+/// * `params` - The params holding the scores of won and unclear endings
+///
+/// # Returns
+///
+/// * `Option<i64>` - The score for the side to move, or `None` if the position is not a king and
+///   pawn against king ending
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("7k/8/8/8/P7/8/8/K7 w - - 0 1");
+/// assert!(king_pawn_versus_king(&board, &EvalParams::DEFAULT) > Some(0));
+/// ```
+pub fn king_pawn_versus_king(board: &Board, params: &EvalParams) -> Option<i64> {
+    let strong = [Color::White, Color::Black].into_iter().find(|&color| {
+        pieces(board, color) == 2
+            && board.get_piece_count(Kind::Pawn(color)) == 1
+            && pieces(board, color.opposite()) == 1
+    })?;
+    let weak = strong.opposite();
+    let square_of = |kind: Kind| board.bitboards.get_bitboard(kind).into_iter().next();
+
+    let pawn = relative(square_of(Kind::Pawn(strong))?, strong);
+    let strong_king = relative(square_of(Kind::King(strong))?, strong);
+    let weak_king = relative(square_of(Kind::King(weak))?, strong);
+    let weak_to_move = board.current_turn == weak;
+
+    let score = if weak_to_move && distance(weak_king, pawn) == 1 && distance(strong_king, pawn) > 1
+    {
+        0
+    } else if outruns_king(pawn, strong_king, weak_king, weak_to_move)
+        || key_squares(pawn).contains(&strong_king)
+    {
+        params.known_win + params.pawn_advance_bonus * i64::from(pawn.rank)
+            - distance(strong_king, pawn)
+    } else if matches!(pawn.file, 0 | 7)
+        && weak_king.file.abs_diff(pawn.file) <= 1
+        && weak_king.rank > pawn.rank
+    {
+        0
+    } else {
+        let nearest_key_square = key_squares(pawn)
+            .into_iter()
+            .map(|square| distance(strong_king, square))
+            .min()
+            .unwrap_or(0);
+        params.unclear_pawn + params.pawn_advance_bonus * i64::from(pawn.rank)
+            - params.key_square_distance_penalty * nearest_key_square
+    };
+
+    Some(if weak_to_move { -score } else { score })
+}
+
+/// Returns whether a pawn reaches promotion before the defending king can catch it, by the rule
+/// of the square
+///
+/// All squares are seen from the pawn's side of the board.
+fn outruns_king(pawn: Square, strong_king: Square, weak_king: Square, weak_to_move: bool) -> bool {
+    let promotion = Square {
+        rank: 7,
+        file: pawn.file,
+    };
+    // The stronger king would block its own pawn's path
+    if strong_king.file == pawn.file && strong_king.rank > pawn.rank {
+        return false;
+    }
+
+    // The pawn crosses two ranks on its first move
+    let pawn_moves = i64::from(7 - pawn.rank) - i64::from(pawn.rank == 1);
+    distance(weak_king, promotion) - i64::from(weak_to_move) > pawn_moves
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    const KNOWN_WIN: i64 = EvalParams::DEFAULT.known_win;
+
+    fn kpk(board: &Board) -> Option<i64> {
+        king_pawn_versus_king(board, &EvalParams::DEFAULT)
+    }
+
+    #[test]
+    fn test_two_knights_against_bare_king() {
+        let white = Board::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1");
+        let black = Board::from_fen("1n2k1n1/8/8/8/8/8/8/4K3 w - - 0 1");
+
+        assert!(is_two_knights_fortress(&white));
+        assert!(is_two_knights_fortress(&black));
+    }
+
+    #[test]
+    fn test_two_knights_against_king_and_pawn() {
+        let board = Board::from_fen("4k3/4p3/8/8/8/8/8/1N2K1N1 w - - 0 1");
+        assert!(!is_two_knights_fortress(&board));
+    }
+
+    #[test]
+    fn test_other_material_is_not_a_fortress() {
+        let knight_and_bishop = Board::from_fen("4k3/8/8/8/8/8/8/1N2KB2 w - - 0 1");
+        let three_knights = Board::from_fen("4k3/8/8/8/8/8/8/1N1NK1N1 w - - 0 1");
+
+        assert!(!is_two_knights_fortress(&knight_and_bishop));
+        assert!(!is_two_knights_fortress(&three_knights));
+        assert!(!is_two_knights_fortress(
+            &BoardBuilder::construct_starting_board().build()
+        ));
+    }
+
+    #[test]
+    fn test_pawn_outruns_king() {
+        let mut board = Board::from_fen("7k/8/8/8/P7/8/8/K7 w - - 0 1");
+        assert!(kpk(&board).unwrap() >= KNOWN_WIN);
+        board.switch_turn();
+        assert!(kpk(&board).unwrap() <= -KNOWN_WIN);
+    }
+
+    #[test]
+    fn test_king_on_key_square_wins() {
+        let board = Board::from_fen("4k3/8/3K4/8/4P3/8/8/8 b - - 0 1");
+        assert!(kpk(&board).unwrap() <= -KNOWN_WIN);
+    }
+
+    #[test]
+    fn test_black_pawn_outruns_king() {
+        let board = Board::from_fen("k7/p7/8/8/8/8/8/7K w - - 0 1");
+        assert!(kpk(&board).unwrap() <= -KNOWN_WIN);
+    }
+
+    #[test]
+    fn test_further_advanced_pawn_scores_higher() {
+        let behind = Board::from_fen("7k/8/8/8/P7/8/8/K7 w - - 0 1");
+        let ahead = Board::from_fen("7k/8/8/P7/8/8/8/K7 w - - 0 1");
+        assert!(kpk(&ahead) > kpk(&behind));
+    }
+
+    #[test]
+    fn test_rook_pawn_with_defender_in_corner_is_drawn() {
+        let mut board = Board::from_fen("k7/8/8/8/8/1K6/P7/8 w - - 0 1");
+        assert_eq!(kpk(&board), Some(0));
+        board.switch_turn();
+        assert_eq!(kpk(&board), Some(0));
+    }
+
+    #[test]
+    fn test_defender_takes_undefended_pawn() {
+        let board = Board::from_fen("8/8/8/8/3k4/4P3/8/K7 b - - 0 1");
+        assert_eq!(kpk(&board), Some(0));
+    }
+
+    #[test]
+    fn test_unclear_ending_rewards_approaching_key_squares() {
+        let near = Board::from_fen("4k3/8/8/8/2K5/4P3/8/8 w - - 0 1");
+        let far = Board::from_fen("4k3/8/8/8/8/4P3/8/K7 w - - 0 1");
+        let near_score = kpk(&near).unwrap();
+
+        assert!(near_score > 0 && near_score < KNOWN_WIN);
+        assert!(near_score > kpk(&far).unwrap());
+    }
+
+    #[test]
+    fn test_other_endings_are_not_scored() {
+        let two_pawns = Board::from_fen("4k3/8/8/8/8/4P3/4P3/4K3 w - - 0 1");
+        let pawn_each = Board::from_fen("4k3/4p3/8/8/8/8/4P3/4K3 w - - 0 1");
+
+        assert_eq!(kpk(&two_pawns), None);
+        assert_eq!(kpk(&pawn_each), None);
+        assert_eq!(kpk(&BoardBuilder::construct_starting_board().build()), None);
+    }
+}
@@ -0,0 +1,283 @@
+use super::mobility::PieceOf;
+use super::params::EvalParams;
+use crate::board::bitboard::Bitboard;
+use crate::board::piece::{Color, Kind};
+use crate::board::ply::castling::{CastlingKind, CastlingStatus};
+use crate::board::square::Square;
+use crate::board::{Board, FileStatus};
+
+/// The pieces whose attacks on the squares around the king add to its danger, in the order of
+/// `EvalParams::king_attacker_weights`
+const KING_ATTACKERS: [PieceOf; 4] = [Kind::Knight, Kind::Bishop, Kind::Rook, Kind::Queen];
+
+/// Returns how safe `color`'s king is behind its pawn shield and from the enemy pieces around it
+///
+/// Each of the three files in front of the king is penalized when its pawn has advanced two
+/// squares or is missing entirely. Every enemy knight, bishop, rook, and queen attacking the king
+/// or the squares next to it adds to the danger, which grows with the number of attackers.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose king is scored
+/// * `params` - The params holding the shield penalties and attacker weights
+///
+/// # Returns
+///
+/// * `i64` - The safety of the king, where higher is better for `color` and zero is an intact
+///   shield with no attackers
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("6k1/5ppp/8/8/6PP/8/5P2/6K1 w - - 0 1");
+/// let safety = |color| king_safety(&board, color, &EvalParams::DEFAULT);
+/// assert!(safety(Color::White) < safety(Color::Black));
+/// ```
+pub fn king_safety(board: &Board, color: Color, params: &EvalParams) -> i64 {
+    let king = board.bitboards.get_bitboard(Kind::King(color));
+    if king.is_empty() {
+        return 0;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let king_square = Square::from_index(king.bitscan_forward() as u8);
+    let own_pawns = board.bitboards.get_bitboard(Kind::Pawn(color));
+
+    let rank_ahead = |distance: u8| {
+        let rank = match color {
+            Color::White => king_square
+                .rank
+                .checked_add(distance)
+                .filter(|&rank| rank < 8),
+            Color::Black => king_square.rank.checked_sub(distance),
+        };
+        rank.map_or(Bitboard::new(0), |rank| {
+            Bitboard::new(Square { rank, file: 0 }.get_rank_mask())
+        })
+    };
+    let (first, second) = (rank_ahead(1), rank_ahead(2));
+
+    let shield_penalty: i64 = (king_square.file.saturating_sub(1)..=(king_square.file + 1).min(7))
+        .map(|file| {
+            let file_mask = Bitboard::new(Square { rank: 0, file }.get_file_mask());
+            if !(own_pawns & file_mask & first).is_empty() {
+                0
+            } else if !(own_pawns & file_mask & second).is_empty() {
+                params.shield_advanced_penalty
+            } else {
+                params.shield_missing_penalty
+            }
+        })
+        .sum();
+
+    let king_zone = Kind::King(color).get_attacks(king_square, board) | king;
+    #[allow(clippy::cast_possible_wrap)]
+    let (attackers, attack_weight) = KING_ATTACKERS
+        .iter()
+        .zip(params.king_attacker_weights)
+        .fold(
+            (0usize, 0i64),
+            |(attackers, attack_weight), (kind, weight)| {
+                let piece = kind(color.opposite());
+                let squares: Vec<Square> = board.bitboards.get_bitboard(piece).into();
+                let attacking = squares
+                    .into_iter()
+                    .filter(|&square| !(piece.get_attacks(square, board) & king_zone).is_empty())
+                    .count();
+
+                (
+                    attackers + attacking,
+                    attack_weight + weight * attacking as i64,
+                )
+            },
+        );
+    let scale = &params.king_attacker_scale;
+    let attack_danger = attack_weight * scale[attackers.min(scale.len() - 1)] / 100;
+
+    -(shield_penalty + attack_danger)
+}
+
+/// Returns how well `color`'s king has been tucked away by castling
+///
+/// A king on one of the back rank squares castling would take it to, with both castling rights
+/// spent, is assumed to have castled. A king anywhere else is penalized, and more so once it can
+/// no longer castle to safety.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose king is scored
+/// * `params` - The params holding the castling bonus and penalties
+///
+/// # Returns
+///
+/// * `i64` - The score of the king's placement, where higher is better for `color`
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R4RK1 w kq - 0 1");
+/// let score = |color| castling_score(&board, color, &EvalParams::DEFAULT);
+/// assert!(score(Color::White) > score(Color::Black));
+/// ```
+pub fn castling_score(board: &Board, color: Color, params: &EvalParams) -> i64 {
+    let king = board.bitboards.get_bitboard(Kind::King(color));
+    if king.is_empty() {
+        return 0;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let king_square = Square::from_index(king.bitscan_forward() as u8);
+
+    let (back_rank, kingside, queenside) = match color {
+        Color::White => (0, CastlingKind::WhiteKingside, CastlingKind::WhiteQueenside),
+        Color::Black => (7, CastlingKind::BlackKingside, CastlingKind::BlackQueenside),
+    };
+    let can_castle = [kingside, queenside]
+        .into_iter()
+        .any(|kind| board.castle_status(kind) == CastlingStatus::Availiable);
+    let sheltered = king_square.rank == back_rank && !(3..=5).contains(&king_square.file);
+
+    match (sheltered, can_castle) {
+        (true, false) => params.castled_bonus,
+        (true, true) => 0,
+        (false, true) => -params.uncastled_penalty,
+        (false, false) => -params.stuck_king_penalty,
+    }
+}
+
+/// Returns how threatening the enemy pawns advancing on `color`'s king are
+///
+/// Only pawns on the king's file and the files next to it are counted, and pawns are more
+/// dangerous the further they have advanced.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose king is being stormed
+/// * `params` - The params holding the danger of each advanced pawn
+///
+/// # Returns
+///
+/// * `i64` - The danger to the king, where higher is worse for `color`
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/8/6pp/8/8/6K1 w - - 0 1");
+/// assert!(pawn_storm_danger(&board, Color::White, &EvalParams::DEFAULT) > 0);
+/// ```
+pub fn pawn_storm_danger(board: &Board, color: Color, params: &EvalParams) -> i64 {
+    let king = board.bitboards.get_bitboard(Kind::King(color));
+    if king.is_empty() {
+        return 0;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let king_square = Square::from_index(king.bitscan_forward() as u8);
+    let enemy_pawns = board.bitboards.get_bitboard(Kind::Pawn(color.opposite()));
+
+    let mut danger = 0;
+    for file in king_square.file.saturating_sub(1)..=(king_square.file + 1).min(7) {
+        if board.file_status(file, color.opposite()) != FileStatus::Closed {
+            continue;
+        }
+
+        let file_mask = Bitboard::new(Square { rank: 0, file }.get_file_mask());
+        let pawns: Vec<Square> = (enemy_pawns & file_mask).into();
+
+        for pawn in pawns {
+            let advanced = match color {
+                Color::White => 7 - pawn.rank,
+                Color::Black => pawn.rank,
+            };
+            danger += params.pawn_storm_danger[advanced as usize];
+        }
+    }
+
+    danger
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const PARAMS: EvalParams = EvalParams::DEFAULT;
+
+    #[test]
+    fn test_castling_score() {
+        let castled = Board::from_fen("r3k2r/8/8/8/8/8/8/R4RK1 w kq - 0 1");
+        let stuck = Board::from_fen("r3k2r/8/8/8/8/8/8/R2K3R w kq - 0 1");
+
+        assert_eq!(
+            castling_score(&castled, Color::White, &PARAMS),
+            PARAMS.castled_bonus
+        );
+        assert_eq!(
+            castling_score(&castled, Color::Black, &PARAMS),
+            -PARAMS.uncastled_penalty
+        );
+        assert_eq!(
+            castling_score(&stuck, Color::White, &PARAMS),
+            -PARAMS.stuck_king_penalty
+        );
+    }
+
+    #[test]
+    fn test_pawn_storm_quiet_flank() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+        assert_eq!(pawn_storm_danger(&board, Color::White, &PARAMS), 0);
+        assert_eq!(pawn_storm_danger(&board, Color::Black, &PARAMS), 0);
+    }
+
+    #[test]
+    fn test_pawn_storm_advanced_pawns_are_more_dangerous() {
+        let stormed = Board::from_fen("6k1/5p2/8/8/6pp/8/5PPP/6K1 w - - 0 1");
+        let far_storm = Board::from_fen("6k1/5p2/6pp/8/8/8/5PPP/6K1 w - - 0 1");
+
+        let danger = pawn_storm_danger(&stormed, Color::White, &PARAMS);
+        assert!(danger > pawn_storm_danger(&far_storm, Color::White, &PARAMS));
+        assert!(danger > pawn_storm_danger(&stormed, Color::Black, &PARAMS));
+    }
+
+    #[test]
+    fn test_pawn_storm_ignores_distant_files() {
+        let board = Board::from_fen("6k1/8/8/8/pp6/8/5PPP/6K1 w - - 0 1");
+        assert_eq!(pawn_storm_danger(&board, Color::White, &PARAMS), 0);
+    }
+
+    #[test]
+    fn test_king_safety_intact_shield() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+        assert_eq!(king_safety(&board, Color::White, &PARAMS), 0);
+        assert_eq!(king_safety(&board, Color::Black, &PARAMS), 0);
+    }
+
+    #[test]
+    fn test_king_safety_advanced_shield_pawns() {
+        let intact = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+        let advanced = Board::from_fen("6k1/5ppp/8/8/8/6PP/5P2/6K1 w - - 0 1");
+        let pushed = Board::from_fen("6k1/5ppp/8/8/6PP/8/5P2/6K1 w - - 0 1");
+
+        assert_eq!(
+            king_safety(&advanced, Color::White, &PARAMS),
+            -2 * PARAMS.shield_advanced_penalty
+        );
+        assert_eq!(
+            king_safety(&pushed, Color::White, &PARAMS),
+            -2 * PARAMS.shield_missing_penalty
+        );
+        assert!(
+            king_safety(&intact, Color::White, &PARAMS)
+                > king_safety(&advanced, Color::White, &PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_king_safety_attackers() {
+        // The queen alone attacks h2, then the knight on f4 joins in by attacking g2 and h3
+        let lone = Board::from_fen("6k1/5ppp/8/8/7q/8/5PPP/6K1 w - - 0 1");
+        let joined = Board::from_fen("6k1/5ppp/8/8/5n1q/8/5PPP/6K1 w - - 0 1");
+
+        assert_eq!(king_safety(&lone, Color::White, &PARAMS), 0);
+        assert!(king_safety(&joined, Color::White, &PARAMS) < 0);
+    }
+}
@@ -0,0 +1,89 @@
+use super::params::EvalParams;
+use crate::board::piece::{Color, Kind};
+use crate::board::square::Square;
+use crate::board::Board;
+
+/// Builds a piece of some kind for either color
+pub type PieceOf = fn(Color) -> Kind;
+
+/// The pieces whose moves are counted, in the order of `EvalParams::mobility_values`
+const MOBILE_PIECES: [PieceOf; 4] = [Kind::Knight, Kind::Bishop, Kind::Rook, Kind::Queen];
+
+/// Returns the weighted number of squares `color`'s knights, bishops, rooks, and queens can move to
+///
+/// Each piece counts the squares it attacks that are not occupied by its own side, so a square
+/// reachable by two pieces counts once for each. Pawns and the king are left out, since their
+/// freedom of movement is covered by the pawn structure and king safety terms instead.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose pieces are counted
+/// * `params` - The params holding the value of a square for each kind of piece
+///
+/// # Returns
+///
+/// * `i64` - The sum over every piece of its destination squares times its value
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+/// assert_eq!(mobility(&board, Color::White, &EvalParams::DEFAULT), 22);
+/// ```
+pub fn mobility(board: &Board, color: Color, params: &EvalParams) -> i64 {
+    let own_pieces = match color {
+        Color::White => board.bitboards.white_pieces,
+        Color::Black => board.bitboards.black_pieces,
+    };
+
+    MOBILE_PIECES
+        .iter()
+        .zip(params.mobility_values)
+        .map(|(kind, weight)| {
+            let piece = kind(color);
+            let squares: Vec<Square> = board.bitboards.get_bitboard(piece).into();
+            let destinations: u32 = squares
+                .into_iter()
+                .map(|square| (piece.get_attacks(square, board) & !own_pieces).count_ones())
+                .sum();
+
+            i64::from(destinations) * weight
+        })
+        .sum()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    const PARAMS: EvalParams = EvalParams::DEFAULT;
+
+    #[test]
+    fn test_mobility_starting_position_is_symmetric() {
+        let board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(
+            mobility(&board, Color::White, &PARAMS),
+            mobility(&board, Color::Black, &PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_mobility_open_rook_beats_boxed_rook() {
+        let open = Board::from_fen("4k3/8/8/8/8/8/1PPPPPPP/R3K3 w - - 0 1");
+        let boxed = Board::from_fen("4k3/8/8/8/8/8/PPPPPPPP/R3K3 w - - 0 1");
+
+        assert!(mobility(&open, Color::White, &PARAMS) > mobility(&boxed, Color::White, &PARAMS));
+    }
+
+    #[test]
+    fn test_mobility_ignores_own_pieces() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/P7/RN2K3 w - - 0 1");
+
+        // The knight reaches a3, c3, and d2, while the rook is hemmed in by its pawn and knight
+        assert_eq!(mobility(&board, Color::White, &PARAMS), 12);
+    }
+}
@@ -0,0 +1,283 @@
+use super::simple_evaluator::FEATURE_COUNT;
+use crate::board::piece::PIECE_VALUES;
+
+/// A bonus for each square a piece can stand on, laid out as seen by white with a8 first and h1
+/// last
+pub type Table = [i64; 64];
+
+/// Encourages central pawn breaks while the king still needs its shelter
+#[rustfmt::skip]
+const PAWN_MIDDLEGAME_TABLE: Table = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+/// Pushes pawns towards promotion once there is little left to stop them
+#[rustfmt::skip]
+const PAWN_ENDGAME_TABLE: Table = [
+      0,  0,  0,  0,  0,  0,  0,  0,
+     90, 90, 90, 90, 90, 90, 90, 90,
+     50, 50, 50, 50, 50, 50, 50, 50,
+     30, 30, 30, 30, 30, 30, 30, 30,
+     15, 15, 15, 15, 15, 15, 15, 15,
+      5,  5,  5,  5,  5,  5,  5,  5,
+      0,  0,  0,  0,  0,  0,  0,  0,
+      0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: Table = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: Table = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: Table = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: Table = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+/// Keeps the king sheltered behind its pawns while there are pieces left to attack it
+#[rustfmt::skip]
+const KING_MIDDLEGAME_TABLE: Table = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+/// Brings the king towards the center once the board has emptied
+#[rustfmt::skip]
+const KING_ENDGAME_TABLE: Table = [
+    -50,-40,-30,-20,-20,-30,-40,-50,
+    -30,-20,-10,  0,  0,-10,-20,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-30,  0,  0,  0,  0,-30,-30,
+    -50,-30,-30,-30,-30,-30,-30,-50,
+];
+
+/// The bonuses each piece gets for the square it stands on, in the middlegame and the endgame
+///
+/// The board keeps running totals of these for the default tables, so any other tables are
+/// scored by rescanning the pieces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PieceSquareTables {
+    pub pawn_middlegame: Table,
+    pub pawn_endgame: Table,
+    pub knight: Table,
+    pub bishop: Table,
+    pub rook: Table,
+    pub queen: Table,
+    pub king_middlegame: Table,
+    pub king_endgame: Table,
+    /// The value of pawns, knights, bishops, rooks, and queens in the endgame, where pawns and
+    /// the long-range pieces gain in worth
+    pub endgame_values: [i64; 5],
+}
+
+impl PieceSquareTables {
+    /// The tables the engine plays with
+    pub const DEFAULT: Self = Self {
+        pawn_middlegame: PAWN_MIDDLEGAME_TABLE,
+        pawn_endgame: PAWN_ENDGAME_TABLE,
+        knight: KNIGHT_TABLE,
+        bishop: BISHOP_TABLE,
+        rook: ROOK_TABLE,
+        queen: QUEEN_TABLE,
+        king_middlegame: KING_MIDDLEGAME_TABLE,
+        king_endgame: KING_ENDGAME_TABLE,
+        endgame_values: [120, 280, 310, 530, 950],
+    };
+}
+
+/// The weights, tables, and bonuses the evaluation is made of
+///
+/// Every number the evaluator scores a position with lives here, so tuning and A/B testing only
+/// need a different set of params instead of edits to literals spread across the evaluator. The
+/// weights multiply whole terms, while the tables and bonuses shape the terms themselves.
+///
+/// The king has no material weight, since both sides always have exactly one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvalParams {
+    pub queen_value: i64,
+    pub rook_value: i64,
+    pub bishop_value: i64,
+    pub knight_value: i64,
+    pub pawn_value: i64,
+    pub piece_squares: PieceSquareTables,
+    pub pawn_storm_weight: i64,
+    /// The danger each enemy pawn near the king adds, indexed by the pawn's rank counted from its
+    /// own side of the board
+    pub pawn_storm_danger: [i64; 8],
+    pub defended_minor_weight: i64,
+    pub tapered_weight: i64,
+    pub mobility_weight: i64,
+    /// How much each square a knight, bishop, rook, and queen can move to is worth
+    ///
+    /// Pieces with fewer moves gain more from each extra square, so the values fall as the
+    /// piece's usual number of moves rises.
+    pub mobility_values: [i64; 4],
+    /// Subtracted for each pawn on a file beyond the first
+    pub doubled_pawn_penalty: i64,
+    /// Subtracted for each pawn with no friendly pawns on the files next to it
+    pub isolated_pawn_penalty: i64,
+    pub passed_pawn_weight: i64,
+    /// The bonus for a passed pawn, indexed by the pawn's rank counted from its own side of the
+    /// board
+    pub passed_pawn_bonus: [i64; 8],
+    pub castling_weight: i64,
+    /// The bonus for a king that has castled into the shelter of its pawns
+    pub castled_bonus: i64,
+    /// The penalty for a king that has not castled yet but still can
+    pub uncastled_penalty: i64,
+    /// The penalty for a king stuck in the center after losing the right to castle
+    pub stuck_king_penalty: i64,
+    pub king_safety_weight: i64,
+    /// The penalty for each file in front of the king whose shield pawn has advanced two squares
+    pub shield_advanced_penalty: i64,
+    /// The penalty for each file in front of the king without a shield pawn on either of the two
+    /// ranks ahead of it
+    pub shield_missing_penalty: i64,
+    /// How much each enemy knight, bishop, rook, and queen that attacks the squares around the
+    /// king adds to the danger
+    pub king_attacker_weights: [i64; 4],
+    /// The percentage of the attackers' weight that counts as danger, indexed by the number of
+    /// attackers
+    ///
+    /// A lone attacker can rarely break through on its own, so the danger grows with every piece
+    /// that joins the attack.
+    pub king_attacker_scale: [i64; 8],
+    /// The score of a king and pawn ending the stronger side is known to win, before the pawn's
+    /// progress is added
+    pub known_win: i64,
+    /// The score of a king and pawn ending that is neither known to be won nor drawn, roughly the
+    /// value of the pawn
+    pub unclear_pawn: i64,
+    /// The bonus for each rank the pawn of a king and pawn ending has advanced, which pushes it
+    /// toward promotion
+    pub pawn_advance_bonus: i64,
+    /// The penalty for each step the stronger king is away from the pawn's nearest key square
+    pub key_square_distance_penalty: i64,
+}
+
+impl EvalParams {
+    /// The params the engine plays with
+    pub const DEFAULT: Self = Self {
+        queen_value: PIECE_VALUES[4],
+        rook_value: PIECE_VALUES[3],
+        bishop_value: PIECE_VALUES[2],
+        knight_value: PIECE_VALUES[1],
+        pawn_value: PIECE_VALUES[0],
+        piece_squares: PieceSquareTables::DEFAULT,
+        pawn_storm_weight: 1,
+        pawn_storm_danger: [0, 0, 0, 10, 25, 40, 50, 0],
+        defended_minor_weight: 15,
+        tapered_weight: 1,
+        mobility_weight: 1,
+        mobility_values: [4, 4, 2, 1],
+        doubled_pawn_penalty: 15,
+        isolated_pawn_penalty: 10,
+        passed_pawn_weight: 1,
+        passed_pawn_bonus: [0, 10, 15, 25, 40, 60, 90, 0],
+        castling_weight: 1,
+        castled_bonus: 20,
+        uncastled_penalty: 15,
+        stuck_king_penalty: 40,
+        king_safety_weight: 1,
+        shield_advanced_penalty: 10,
+        shield_missing_penalty: 25,
+        king_attacker_weights: [20, 20, 40, 80],
+        king_attacker_scale: [0, 0, 50, 75, 88, 94, 97, 99],
+        known_win: 2_000,
+        unclear_pawn: 100,
+        pawn_advance_bonus: 20,
+        key_square_distance_penalty: 10,
+    };
+
+    /// Returns the weights in the same order as the terms of `SimpleEvaluator::features`
+    ///
+    /// Penalties are negated, since their terms count how many more weaknesses a side has.
+    ///
+    /// # Returns
+    ///
+    /// * `[i64; FEATURE_COUNT]` - The weight of each evaluation term
+    ///
+    /// # Example
+    /// ```
+    /// let weights = EvalParams::DEFAULT.weights();
+    /// assert_eq!(weights[0], 900);
+    /// ```
+    pub const fn weights(&self) -> [i64; FEATURE_COUNT] {
+        [
+            self.queen_value,
+            self.rook_value,
+            self.bishop_value,
+            self.knight_value,
+            self.pawn_value,
+            self.pawn_storm_weight,
+            self.defended_minor_weight,
+            self.tapered_weight,
+            self.mobility_weight,
+            -self.doubled_pawn_penalty,
+            -self.isolated_pawn_penalty,
+            self.passed_pawn_weight,
+            self.castling_weight,
+            self.king_safety_weight,
+        ]
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,196 @@
+use super::params::EvalParams;
+use crate::board::bitboard::Bitboard;
+use crate::board::piece::{Color, Kind};
+use crate::board::square::Square;
+use crate::board::Board;
+
+/// Returns the squares on `file` and the files next to it
+fn adjacent_files_mask(file: u8) -> Bitboard {
+    (file.saturating_sub(1)..=(file + 1).min(7))
+        .map(|file| Bitboard::new(Square { rank: 0, file }.get_file_mask()))
+        .fold(Bitboard::new(0), |mask, file_mask| mask | file_mask)
+}
+
+/// Returns the squares on the ranks in front of `square` from `color`'s point of view
+fn ranks_ahead_mask(square: Square, color: Color) -> Bitboard {
+    let below = 1u64.checked_shl(8 * u32::from(square.rank)).unwrap_or(0) - 1;
+    let through = 1u64
+        .checked_shl(8 * u32::from(square.rank + 1))
+        .unwrap_or(0)
+        .wrapping_sub(1);
+
+    Bitboard::new(match color {
+        Color::White => !through,
+        Color::Black => below,
+    })
+}
+
+/// Returns the number of `color`'s pawns that share a file with another of its pawns, not
+/// counting the first pawn on each file
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose pawns are counted
+///
+/// # Returns
+///
+/// * `i64` - The number of doubled pawns
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/8/8/4P3/4P3/4K3 w - - 0 1");
+/// assert_eq!(doubled_pawns(&board, Color::White), 1);
+/// ```
+pub fn doubled_pawns(board: &Board, color: Color) -> i64 {
+    let own_pawns = board.bitboards.get_bitboard(Kind::Pawn(color));
+
+    (0..8)
+        .map(|file| {
+            (own_pawns & Bitboard::new(Square { rank: 0, file }.get_file_mask())).count_ones()
+        })
+        .map(|on_file| i64::from(on_file.saturating_sub(1)))
+        .sum()
+}
+
+/// Returns the number of `color`'s pawns with no friendly pawns on the files next to them
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose pawns are counted
+///
+/// # Returns
+///
+/// * `i64` - The number of isolated pawns
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/8/8/8/P1P5/4K3 w - - 0 1");
+/// assert_eq!(isolated_pawns(&board, Color::White), 2);
+/// ```
+pub fn isolated_pawns(board: &Board, color: Color) -> i64 {
+    let own_pawns = board.bitboards.get_bitboard(Kind::Pawn(color));
+
+    (0..8)
+        .filter_map(|file| {
+            let file_mask = Bitboard::new(Square { rank: 0, file }.get_file_mask());
+            (own_pawns & adjacent_files_mask(file) & !file_mask)
+                .is_empty()
+                .then(|| i64::from((own_pawns & file_mask).count_ones()))
+        })
+        .sum()
+}
+
+/// Returns the bonus for `color`'s passed pawns, which no enemy pawn can stop or capture on their
+/// way to promotion, rewarding them more the further they have advanced
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose pawns are scored
+/// * `params` - The params holding the bonus for each rank
+///
+/// # Returns
+///
+/// * `i64` - The total bonus of the passed pawns
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/4P3/8/8/8/8/4K3 w - - 0 1");
+/// assert!(passed_pawn_score(&board, Color::White, &EvalParams::DEFAULT) > 0);
+/// ```
+pub fn passed_pawn_score(board: &Board, color: Color, params: &EvalParams) -> i64 {
+    let own_pawns = board.bitboards.get_bitboard(Kind::Pawn(color));
+    let enemy_pawns = board.bitboards.get_bitboard(Kind::Pawn(color.opposite()));
+
+    own_pawns
+        .into_iter()
+        .filter(|&pawn| {
+            (enemy_pawns & adjacent_files_mask(pawn.file) & ranks_ahead_mask(pawn, color))
+                .is_empty()
+        })
+        .map(|pawn| {
+            let advanced = match color {
+                Color::White => pawn.rank,
+                Color::Black => 7 - pawn.rank,
+            };
+            params.passed_pawn_bonus[advanced as usize]
+        })
+        .sum()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    const PARAMS: EvalParams = EvalParams::DEFAULT;
+
+    #[test]
+    fn test_pawn_structure_starting_position() {
+        let board = BoardBuilder::construct_starting_board().build();
+
+        for color in [Color::White, Color::Black] {
+            assert_eq!(doubled_pawns(&board, color), 0);
+            assert_eq!(isolated_pawns(&board, color), 0);
+            assert_eq!(passed_pawn_score(&board, color, &PARAMS), 0);
+        }
+    }
+
+    #[test]
+    fn test_doubled_pawns() {
+        let doubled = Board::from_fen("4k3/pppp4/8/8/8/2P5/PPPP4/4K3 w - - 0 1");
+        let tripled = Board::from_fen("4k3/pppp4/8/8/2P5/2P5/PPPP4/4K3 w - - 0 1");
+
+        assert_eq!(doubled_pawns(&doubled, Color::White), 1);
+        assert_eq!(doubled_pawns(&tripled, Color::White), 2);
+        assert_eq!(doubled_pawns(&doubled, Color::Black), 0);
+    }
+
+    #[test]
+    fn test_isolated_pawn() {
+        let board = Board::from_fen("4k3/pppppppp/8/8/8/8/P1PPPPPP/4K3 w - - 0 1");
+
+        assert_eq!(isolated_pawns(&board, Color::White), 1);
+        assert_eq!(isolated_pawns(&board, Color::Black), 0);
+    }
+
+    #[test]
+    fn test_isolated_doubled_pawns_count_each_pawn() {
+        let board = Board::from_fen("4k3/8/8/8/8/P7/P7/4K3 w - - 0 1");
+
+        assert_eq!(isolated_pawns(&board, Color::White), 2);
+        assert_eq!(doubled_pawns(&board, Color::White), 1);
+    }
+
+    #[test]
+    fn test_passed_pawn_grows_as_it_advances() {
+        let blocked = Board::from_fen("4k3/3p4/8/8/8/8/4P3/4K3 w - - 0 1");
+        let passed = Board::from_fen("4k3/2p5/8/8/8/8/4P3/4K3 w - - 0 1");
+        let advanced = Board::from_fen("4k3/2p1P3/8/8/8/8/8/4K3 w - - 0 1");
+
+        assert_eq!(passed_pawn_score(&blocked, Color::White, &PARAMS), 0);
+        assert_eq!(
+            passed_pawn_score(&passed, Color::White, &PARAMS),
+            PARAMS.passed_pawn_bonus[1]
+        );
+        assert_eq!(
+            passed_pawn_score(&advanced, Color::White, &PARAMS),
+            PARAMS.passed_pawn_bonus[6]
+        );
+    }
+
+    #[test]
+    fn test_passed_pawn_for_black() {
+        let board = Board::from_fen("4k3/8/8/8/8/3p4/7P/4K3 w - - 0 1");
+
+        assert_eq!(
+            passed_pawn_score(&board, Color::Black, &PARAMS),
+            PARAMS.passed_pawn_bonus[5]
+        );
+    }
+}
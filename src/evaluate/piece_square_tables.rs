@@ -0,0 +1,308 @@
+use super::params::EvalParams;
+use crate::board::piece::{Color, Kind, PIECE_VALUES};
+use crate::board::square::Square;
+use crate::board::{Board, MAX_PHASE};
+
+/// The number of piece-square table entries, one for each square and kind of piece
+pub const PIECE_SQUARE_COUNT: usize = 6 * 64;
+
+/// The value of each piece in the middlegame, matching the default material weights of the
+/// evaluator, which the endgame values are measured against
+const MIDDLEGAME_VALUES: [i64; 5] = PIECE_VALUES;
+
+/// Returns the index into a table of `square` for a piece of `color`, mirroring the board for
+/// black so that both sides read the tables from their own point of view
+const fn table_index(square: Square, color: Color) -> usize {
+    let rank = match color {
+        Color::White => 7 - square.rank,
+        Color::Black => square.rank,
+    };
+
+    rank as usize * 8 + square.file as usize
+}
+
+/// Returns which of the six tables, from pawns to the king, scores `kind`
+const fn table_number(kind: Kind) -> usize {
+    match kind {
+        Kind::Pawn(_) => 0,
+        Kind::Knight(_) => 1,
+        Kind::Bishop(_) => 2,
+        Kind::Rook(_) => 3,
+        Kind::Queen(_) => 4,
+        Kind::King(_) => 5,
+    }
+}
+
+/// Returns the middlegame and endgame bonus of a single piece standing on `square`
+///
+/// Every piece is scored by a middlegame and an endgame piece-square table, and pieces other
+/// than the king also gain or lose the difference between their endgame and middlegame values
+/// as the endgame nears. The middlegame material itself is left to the material terms of the
+/// evaluator.
+///
+/// # Arguments
+///
+/// * `kind` - The piece
+/// * `square` - The square the piece stands on
+/// * `params` - The params holding the piece-square tables
+///
+/// # Returns
+///
+/// * `(i64, i64)` - The middlegame and endgame bonus, from the point of view of the piece's side
+///
+/// # Example
+/// ```
+/// let (knight, d4) = (Kind::Knight(Color::White), Square::from("d4"));
+/// assert_eq!(piece_square_value(knight, d4, &EvalParams::DEFAULT), (20, 0));
+/// ```
+pub const fn piece_square_value(kind: Kind, square: Square, params: &EvalParams) -> (i64, i64) {
+    let tables = &params.piece_squares;
+    let (middlegame, endgame, value_idx) = match kind {
+        Kind::Pawn(_) => (&tables.pawn_middlegame, &tables.pawn_endgame, Some(0)),
+        Kind::Knight(_) => (&tables.knight, &tables.knight, Some(1)),
+        Kind::Bishop(_) => (&tables.bishop, &tables.bishop, Some(2)),
+        Kind::Rook(_) => (&tables.rook, &tables.rook, Some(3)),
+        Kind::Queen(_) => (&tables.queen, &tables.queen, Some(4)),
+        Kind::King(_) => (&tables.king_middlegame, &tables.king_endgame, None),
+    };
+    let idx = table_index(square, kind.get_color());
+    let value_shift = match value_idx {
+        Some(value_idx) => tables.endgame_values[value_idx] - MIDDLEGAME_VALUES[value_idx],
+        None => 0,
+    };
+
+    (middlegame[idx], endgame[idx] + value_shift)
+}
+
+/// Returns how many more of white's pieces than black's each piece-square table entry scores
+///
+/// Entry `64 * table + index` counts the pieces read from square `index` of a table, with the
+/// tables ordered from pawns to the king and black's squares mirrored as the evaluation reads
+/// them. Summing each count times its middlegame or endgame bonus gives the totals the board
+/// keeps, so a tuner can fit every entry of the tables rather than only their overall weight.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+///
+/// # Returns
+///
+/// * `[i64; PIECE_SQUARE_COUNT]` - The difference in pieces on each table entry
+///
+/// # Example
+/// ```
+/// let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+/// let counts = piece_square_counts(&board);
+/// assert_eq!(counts.iter().sum::<i64>(), 0);
+/// ```
+pub fn piece_square_counts(board: &Board) -> [i64; PIECE_SQUARE_COUNT] {
+    let mut counts = [0; PIECE_SQUARE_COUNT];
+    for square in board.bitboards.all_pieces {
+        let Some(kind) = board.get_piece(square) else {
+            continue;
+        };
+        let color = kind.get_color();
+        let entry = table_number(kind) * 64 + table_index(square, color);
+        counts[entry] += match color {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+    }
+
+    counts
+}
+
+/// A score measured separately for the middlegame and the endgame
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct TaperedScore {
+    middlegame: i64,
+    endgame: i64,
+}
+
+impl TaperedScore {
+    /// Blends the two scores linearly, from all middlegame at `MAX_PHASE` to all endgame at 0
+    fn taper(self, phase: u32) -> i64 {
+        let phase = i64::from(phase.min(MAX_PHASE));
+        let max_phase = i64::from(MAX_PHASE);
+
+        (self.middlegame * phase + self.endgame * (max_phase - phase)) / max_phase
+    }
+}
+
+/// Returns the positional bonus of `color`'s pieces, blended between the middlegame and the
+/// endgame by the material left on the board
+///
+/// This scores every piece from scratch, so the evaluator only relies on it for tables other
+/// than the default ones the board keeps running totals of for `tapered_difference`.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side whose pieces are scored
+/// * `params` - The params holding the piece-square tables
+///
+/// # Returns
+///
+/// * `i64` - The tapered bonus of every piece of `color`
+///
+/// # Example
+/// ```
+/// let board = BoardBuilder::construct_starting_board().build();
+/// let score = |color| tapered_score(&board, color, &EvalParams::DEFAULT);
+/// assert_eq!(score(Color::White), score(Color::Black));
+/// ```
+pub fn tapered_score(board: &Board, color: Color, params: &EvalParams) -> i64 {
+    let total = board
+        .bitboards
+        .all_pieces
+        .into_iter()
+        .filter_map(|square| Some((square, board.get_piece(square)?)))
+        .filter(|(_, kind)| kind.get_color() == color)
+        .map(|(square, kind)| piece_square_value(kind, square, params))
+        .fold(TaperedScore::default(), |total, (middlegame, endgame)| {
+            TaperedScore {
+                middlegame: total.middlegame + middlegame,
+                endgame: total.endgame + endgame,
+            }
+        });
+
+    total.taper(board.total_material_phase())
+}
+
+/// Returns the positional bonus of `color`'s pieces minus that of the enemy's, blended between
+/// the middlegame and the endgame by the material left on the board
+///
+/// The board keeps the middlegame and endgame totals of the default tables up to date as pieces
+/// move, so nothing is rescanned here.
+///
+/// # Arguments
+///
+/// * `board` - The board to inspect
+/// * `color` - The side the difference is measured for
+///
+/// # Returns
+///
+/// * `i64` - The difference in tapered bonuses
+///
+/// # Example
+/// ```
+/// let board = BoardBuilder::construct_starting_board().build();
+/// assert_eq!(tapered_difference(&board, Color::White), 0);
+/// ```
+pub fn tapered_difference(board: &Board, color: Color) -> i64 {
+    let score = TaperedScore {
+        middlegame: board.mg_score(),
+        endgame: board.eg_score(),
+    }
+    .taper(board.total_material_phase());
+    match color {
+        Color::White => score,
+        Color::Black => -score,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    const PARAMS: EvalParams = EvalParams::DEFAULT;
+
+    #[test]
+    fn test_starting_position_is_symmetric() {
+        let board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(
+            tapered_score(&board, Color::White, &PARAMS),
+            tapered_score(&board, Color::Black, &PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_tables_are_mirrored_by_color() {
+        let white = Board::from_fen("4k3/8/8/8/8/2N5/8/4K3 w - - 0 1");
+        let black = Board::from_fen("4k3/8/2n5/8/8/8/8/4K3 w - - 0 1");
+        assert_eq!(
+            tapered_score(&white, Color::White, &PARAMS),
+            tapered_score(&black, Color::Black, &PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_taper() {
+        let score = TaperedScore {
+            middlegame: 100,
+            endgame: -20,
+        };
+
+        assert_eq!(score.taper(MAX_PHASE), 100);
+        assert_eq!(score.taper(MAX_PHASE / 2), 40);
+        assert_eq!(score.taper(0), -20);
+    }
+
+    #[test]
+    fn test_king_placement_depends_on_queens() {
+        // With queens on the king belongs behind its pawns, without them it belongs in the center
+        let queens_sheltered = Board::from_fen("r2qk2r/8/8/8/8/8/5PPP/R2Q1RK1 w - - 0 1");
+        let queens_central = Board::from_fen("r2qk2r/8/8/8/4K3/8/5PPP/R2Q1R2 w - - 0 1");
+        let queenless_sheltered = Board::from_fen("r3k2r/8/8/8/8/8/5PPP/R4RK1 w - - 0 1");
+        let queenless_central = Board::from_fen("r3k2r/8/8/8/4K3/8/5PPP/R4R2 w - - 0 1");
+
+        assert!(
+            tapered_score(&queens_sheltered, Color::White, &PARAMS)
+                > tapered_score(&queens_central, Color::White, &PARAMS)
+        );
+        assert!(
+            tapered_score(&queenless_central, Color::White, &PARAMS)
+                > tapered_score(&queenless_sheltered, Color::White, &PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_king_centralizes_in_endgame() {
+        let corner = Board::from_fen("4k3/8/8/8/8/8/8/K7 w - - 0 1");
+        let center = Board::from_fen("4k3/8/8/8/3K4/8/8/8 w - - 0 1");
+        assert!(
+            tapered_score(&center, Color::White, &PARAMS)
+                > tapered_score(&corner, Color::White, &PARAMS)
+        );
+    }
+
+    #[test]
+    fn test_piece_square_counts_reproduce_the_board_totals() {
+        let board = Board::from_fen("r2qk2r/8/2n5/8/4K3/8/5PPP/R2Q1R2 b kq - 0 1");
+        let kinds = [
+            Kind::Pawn(Color::White),
+            Kind::Knight(Color::White),
+            Kind::Bishop(Color::White),
+            Kind::Rook(Color::White),
+            Kind::Queen(Color::White),
+            Kind::King(Color::White),
+        ];
+        let counts = piece_square_counts(&board);
+        let (mut middlegame, mut endgame) = (0, 0);
+        for (table, kind) in kinds.into_iter().enumerate() {
+            for idx in 0..64u8 {
+                // Undo the table layout, where white reads a8 first
+                let square = Square::from_index((7 - idx / 8) * 8 + idx % 8);
+                let (mg, eg) = piece_square_value(kind, square, &PARAMS);
+                middlegame += counts[table * 64 + usize::from(idx)] * mg;
+                endgame += counts[table * 64 + usize::from(idx)] * eg;
+            }
+        }
+
+        assert_eq!((middlegame, endgame), (board.mg_score(), board.eg_score()));
+    }
+
+    #[test]
+    fn test_tapered_difference_matches_tapered_score() {
+        let board = Board::from_fen("r2qk2r/8/2n5/8/4K3/8/5PPP/R2Q1R2 b kq - 0 1");
+        let full = tapered_score(&board, Color::White, &PARAMS)
+            - tapered_score(&board, Color::Black, &PARAMS);
+
+        assert_eq!(tapered_difference(&board, Color::White), full);
+        assert_eq!(tapered_difference(&board, Color::Black), -full);
+    }
+}
@@ -1,48 +1,308 @@
-use super::Evaluator;
-use crate::board::piece::Kind;
-use crate::board::square::Square;
-use crate::board::Board;
+use super::coordination::pawn_defended_minors;
+use super::endgame::{is_two_knights_fortress, king_pawn_versus_king};
+use super::king_safety::{castling_score, king_safety, pawn_storm_danger};
+use super::mobility::mobility;
+use super::params::EvalParams;
+use super::pawn_structure::{doubled_pawns, isolated_pawns, passed_pawn_score};
+use super::piece_square_tables::{tapered_difference, tapered_score};
+use super::{clamp_eval, Evaluator};
+use crate::board::piece::{Color, Kind};
+use crate::board::{Board, MAX_PHASE};
+
+/// The number of terms the evaluation is made of
+pub const FEATURE_COUNT: usize = 14;
 
 /// A simple evaluator that assigns a value to each piece and sums them up.
 #[derive(Clone)]
-pub struct SimpleEvaluator;
+pub struct SimpleEvaluator {
+    params: EvalParams,
+    weights: [i64; FEATURE_COUNT],
+    /// Whether the piece-square tables are the default ones the board keeps running totals of,
+    /// so they can be read from the board instead of rescanning every piece
+    board_totals: bool,
+}
 
 impl SimpleEvaluator {
-    const KING_VALUE: i64 = i32::MAX as i64;
-    const QUEEN_VALUE: i64 = 900;
-    const ROOK_VALUE: i64 = 500;
-    const BISHOP_VALUE: i64 = 300;
-    const KNIGHT_VALUE: i64 = 300;
-    const PAWN_VALUE: i64 = 100;
+    pub fn new() -> Self {
+        Self::with_params(&EvalParams::DEFAULT)
+    }
+
+    /// Creates an evaluator that scores positions with `params` instead of the defaults
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The weights, tables, and bonuses of the evaluation terms
+    ///
+    /// # Example
+    /// ```
+    /// let params = EvalParams { pawn_value: 120, ..EvalParams::DEFAULT };
+    /// let evaluator = SimpleEvaluator::with_params(&params);
+    /// ```
+    pub fn with_params(params: &EvalParams) -> Self {
+        Self {
+            params: *params,
+            weights: params.weights(),
+            board_totals: params.piece_squares == EvalParams::DEFAULT.piece_squares,
+        }
+    }
+
+    /// Returns the terms the evaluation is made of, before they are weighted
+    ///
+    /// The evaluation is the sum of each term multiplied by its weight in `EvalParams`, which lets
+    /// an external tuner fit the weights. The tables and bonuses of the params shape the terms
+    /// themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board to evaluate
+    /// * `color` - The side the terms are measured for
+    ///
+    /// # Returns
+    ///
+    /// * `[i64; FEATURE_COUNT]` - The difference in queens, rooks, bishops, knights, and pawns,
+    ///   followed by the difference in pawn storm danger scaled by the game phase and the
+    ///   difference in minor pieces defended by pawns, in piece-square table bonuses tapered by
+    ///   the game phase, in mobility, in doubled pawns, in isolated pawns, in passed pawn bonuses,
+    ///   in king shelter from castling scaled by the game phase, and in king safety scaled by the
+    ///   game phase
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// assert_eq!(evaluator.features(&board, Color::White), [0; FEATURE_COUNT]);
+    /// ```
+    pub fn features(&self, board: &Board, color: Color) -> [i64; FEATURE_COUNT] {
+        let params = &self.params;
+        let enemy = color.opposite();
+        let material = |kind: fn(Color) -> Kind| {
+            i64::from(board.get_piece_count(kind(color)))
+                - i64::from(board.get_piece_count(kind(enemy)))
+        };
 
-    pub const fn new() -> Self {
-        Self {}
+        // A pawn storm is only a threat while there are pieces left to follow it up
+        let storm = (pawn_storm_danger(board, enemy, params)
+            - pawn_storm_danger(board, color, params))
+            * i64::from(board.total_material_phase())
+            / i64::from(MAX_PHASE);
+
+        // The king belongs in the center once the pieces that could attack it are traded off
+        let castling = (castling_score(board, color, params)
+            - castling_score(board, enemy, params))
+            * i64::from(board.total_material_phase())
+            / i64::from(MAX_PHASE);
+
+        // A missing shield and pieces swarming the king matter less as the attackers are traded off
+        let safety = (king_safety(board, color, params) - king_safety(board, enemy, params))
+            * i64::from(board.total_material_phase())
+            / i64::from(MAX_PHASE);
+
+        let tapered = if self.board_totals {
+            tapered_difference(board, color)
+        } else {
+            tapered_score(board, color, params) - tapered_score(board, enemy, params)
+        };
+
+        [
+            material(Kind::Queen),
+            material(Kind::Rook),
+            material(Kind::Bishop),
+            material(Kind::Knight),
+            material(Kind::Pawn),
+            storm,
+            pawn_defended_minors(board, color) - pawn_defended_minors(board, enemy),
+            tapered,
+            mobility(board, color, params) - mobility(board, enemy, params),
+            doubled_pawns(board, color) - doubled_pawns(board, enemy),
+            isolated_pawns(board, color) - isolated_pawns(board, enemy),
+            passed_pawn_score(board, color, params) - passed_pawn_score(board, enemy, params),
+            castling,
+            safety,
+        ]
     }
 }
 
 impl Evaluator for SimpleEvaluator {
     fn evaluate(&self, board: &mut Board) -> i64 {
-        let mut score: i64 = 0;
-
-        for square in 0..64u8 {
-            if let Some(piece) = board.get_piece(Square::from(square)) {
-                let piece_value = match piece {
-                    Kind::King(_) => Self::KING_VALUE,
-                    Kind::Queen(_) => Self::QUEEN_VALUE,
-                    Kind::Rook(_) => Self::ROOK_VALUE,
-                    Kind::Bishop(_) => Self::BISHOP_VALUE,
-                    Kind::Knight(_) => Self::KNIGHT_VALUE,
-                    Kind::Pawn(_) => Self::PAWN_VALUE,
-                };
-
-                if piece.get_color() == board.current_turn {
-                    score = score.saturating_add(piece_value);
-                } else {
-                    score = score.saturating_sub(piece_value);
-                }
-            }
+        if is_two_knights_fortress(board) {
+            return 0;
         }
+        if let Some(score) = king_pawn_versus_king(board, &self.params) {
+            return score;
+        }
+
+        let score = self
+            .features(board, board.current_turn)
+            .iter()
+            .zip(self.weights)
+            .fold(0i64, |score, (feature, weight)| {
+                score.saturating_add(feature.saturating_mul(weight))
+            });
+
+        clamp_eval(score)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use crate::evaluate::MAX_EVAL;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_evaluate_starting_position() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(SimpleEvaluator::new().evaluate(&mut board), 0);
+    }
+
+    #[test]
+    fn test_evaluate_many_promoted_queens_is_clamped() {
+        let mut board = Board::from_fen(
+            "QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQKk w - - 0 1",
+        );
+        let evaluator = SimpleEvaluator::new();
+
+        assert_eq!(evaluator.evaluate(&mut board), MAX_EVAL);
+        board.switch_turn();
+        assert_eq!(evaluator.evaluate(&mut board), -MAX_EVAL);
+    }
+
+    #[test]
+    fn test_evaluate_promoted_queens_both_sides() {
+        let mut board = Board::from_fen("qqqqkqqq/qqqqqqqq/8/8/8/8/QQQQQQQQ/QQQQKQQQ w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let score = evaluator.evaluate(&mut board);
+
+        assert_eq!(score, 0);
+        assert!((-MAX_EVAL..=MAX_EVAL).contains(&score));
+    }
+
+    #[test]
+    fn test_evaluate_pawn_storm() {
+        // Both sides have the same material, but black's g and h pawns are bearing down on the
+        // white king
+        let mut stormed = Board::from_fen("r5k1/5p2/8/8/6pp/8/5PPP/R5K1 w - - 0 1");
+        let mut quiet = Board::from_fen("r5k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+
+        assert_eq!(evaluator.evaluate(&mut quiet), 0);
+        assert!(evaluator.evaluate(&mut stormed) < 0);
+    }
+
+    #[test]
+    fn test_evaluate_developed_knight() {
+        let mut developed = Board::from_fen("4k3/8/8/8/8/5N2/8/4K3 w - - 0 1");
+        let mut rim = Board::from_fen("4k3/8/8/8/8/7N/8/4K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+
+        assert!(evaluator.evaluate(&mut developed) > evaluator.evaluate(&mut rim));
+    }
+
+    #[test]
+    fn test_evaluate_is_from_the_side_to_move() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/5N2/8/4K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let white = evaluator.evaluate(&mut board);
+        board.switch_turn();
+
+        assert_eq!(evaluator.evaluate(&mut board), -white);
+    }
+
+    #[test]
+    fn test_evaluate_passed_pawn_is_from_the_side_to_move() {
+        let mut board = Board::from_fen("4k3/8/8/8/3P4/8/8/4K3 w - - 0 1");
+        let features = SimpleEvaluator::new().features(&board, Color::White);
+        let evaluator = SimpleEvaluator::new();
+        let white = evaluator.evaluate(&mut board);
+        board.switch_turn();
+
+        assert!(features[11] > 0);
+        assert_eq!(evaluator.evaluate(&mut board), -white);
+    }
+
+    #[test]
+    fn test_evaluate_castled_king() {
+        let castled = Board::from_fen(
+            "r1bq1rk1/ppp2ppp/2np1n2/2b1p3/2B1P3/2NP1N2/PPP2PPP/R1BQ1RK1 w - - 0 1",
+        );
+        let central =
+            Board::from_fen("r1bq1rk1/ppp2ppp/2np1n2/2b1p3/2B1P3/2NP1N2/PPP2PPP/R1BQK2R w - - 0 1");
+        let castled_features = SimpleEvaluator::new().features(&castled, Color::White);
+        let central_features = SimpleEvaluator::new().features(&central, Color::White);
+
+        assert_eq!(castled_features[12], 0);
+        assert!(central_features[12] < 0);
+    }
+
+    #[test]
+    fn test_evaluate_king_safety() {
+        let intact = Board::from_fen(
+            "r1bq1rk1/ppp2ppp/2np1n2/2b1p3/2B1P3/2NP1N2/PPP2PPP/R1BQ1RK1 w - - 0 1",
+        );
+        let pushed = Board::from_fen(
+            "r1bq1rk1/ppp2ppp/2np1n2/2b1p3/2B1P1PP/2NP1N2/PPP2P2/R1BQ1RK1 w - - 0 1",
+        );
+        let endgame = Board::from_fen("6k1/5ppp/8/8/6PP/8/5P2/6K1 w - - 0 1");
+
+        assert_eq!(
+            SimpleEvaluator::new().features(&intact, Color::White)[13],
+            0
+        );
+        assert!(SimpleEvaluator::new().features(&pushed, Color::White)[13] < 0);
+        assert_eq!(
+            SimpleEvaluator::new().features(&endgame, Color::White)[13],
+            0
+        );
+    }
+
+    #[test]
+    fn test_evaluate_two_knights() {
+        let mut bare_king = Board::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1");
+        let mut king_and_pawn = Board::from_fen("4k3/4p3/8/8/8/8/8/1N2K1N1 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+
+        assert_eq!(evaluator.evaluate(&mut bare_king), 0);
+        assert!(evaluator.evaluate(&mut king_and_pawn) > 0);
+    }
+
+    #[test]
+    fn test_evaluate_pawn_defended_knight() {
+        let mut defended = Board::from_fen("4k3/pp6/8/3N4/4P3/8/8/4K3 w - - 0 1");
+        let mut undefended = Board::from_fen("4k3/pp6/8/3N4/8/4P3/8/4K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+
+        assert!(evaluator.evaluate(&mut defended) > evaluator.evaluate(&mut undefended));
+    }
+
+    #[test]
+    fn test_evaluate_with_params() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1");
+        let params = EvalParams {
+            knight_value: 2 * EvalParams::DEFAULT.knight_value,
+            ..EvalParams::DEFAULT
+        };
+
+        let default = SimpleEvaluator::new().evaluate(&mut board);
+        let doubled = SimpleEvaluator::with_params(&params).evaluate(&mut board);
+
+        assert_eq!(doubled - default, EvalParams::DEFAULT.knight_value);
+    }
+
+    #[test]
+    fn test_evaluate_with_other_tables_rescans_the_board() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/5N2/8/4K3 w - - 0 1");
+        let mut params = EvalParams::DEFAULT;
+
+        // No queen stands on a8, so only the board's running totals are bypassed
+        params.piece_squares.queen[0] += 50;
+        let rescanned = SimpleEvaluator::with_params(&params).evaluate(&mut board);
+        assert_eq!(rescanned, SimpleEvaluator::new().evaluate(&mut board));
 
-        score
+        // The tables are laid out with a8 first, so the knight on f3 reads entry 45
+        params.piece_squares.knight[45] += 100;
+        assert!(SimpleEvaluator::with_params(&params).evaluate(&mut board) > rescanned);
     }
 }
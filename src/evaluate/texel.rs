@@ -0,0 +1,185 @@
+use super::endgame::{is_two_knights_fortress, king_pawn_versus_king};
+use super::params::EvalParams;
+use super::piece_square_tables::{piece_square_counts, PIECE_SQUARE_COUNT};
+use super::simple_evaluator::{SimpleEvaluator, FEATURE_COUNT};
+use super::MAX_EVAL;
+use crate::board::piece::Color;
+use crate::board::Board;
+use std::fmt;
+
+/// A single training position for tuning the evaluation weights with Texel's method
+#[derive(Clone, Debug, PartialEq)]
+pub struct TexelEntry {
+    /// The unweighted evaluation terms, measured for white
+    pub features: [i64; FEATURE_COUNT],
+    /// How many more white pieces than black pieces each piece-square table entry scores, which
+    /// the tapered piece-square term of `features` sums up
+    pub piece_squares: [i64; PIECE_SQUARE_COUNT],
+    /// The material left on the board, which blends the middlegame and endgame tables
+    pub phase: u32,
+    /// The outcome of the game for white: 1 for a win, 0.5 for a draw, and 0 for a loss
+    pub result: f64,
+}
+
+/// Parses a game result written as `1-0`, `1/2-1/2`, `0-1`, or directly as white's score
+fn parse_result(result: &str) -> Result<f64, String> {
+    match result {
+        "1-0" => Ok(1.0),
+        "1/2-1/2" => Ok(0.5),
+        "0-1" => Ok(0.0),
+        _ => result
+            .parse()
+            .ok()
+            .filter(|score| (0.0..=1.0).contains(score))
+            .ok_or_else(|| format!("Invalid game result: {result}")),
+    }
+}
+
+/// Extracts the evaluation terms of a position along with the result of the game it came from
+///
+/// The evaluation of an extracted position is exactly its features multiplied by the weights.
+/// Positions where that does not hold are rejected so they stay out of the training data: the
+/// endgames `SimpleEvaluator` scores by rule instead of by its terms, and positions whose score
+/// with the default weights is beyond `MAX_EVAL` and would be clamped.
+///
+/// # Arguments
+///
+/// * `fen` - The position
+/// * `result` - The result of the game, such as `1-0` or `0.5`
+///
+/// # Returns
+///
+/// * `Result<TexelEntry, String>` - The training entry, or a message explaining why the position
+///   or the result was rejected
+///
+/// # Example
+/// ```
+/// let entry = extract("4k3/4p3/8/8/8/8/4P3/4K3 w - - 0 1", "1/2-1/2").unwrap();
+/// println!("{entry}");
+/// assert!(extract("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", "1-0").is_err());
+/// ```
+#[allow(dead_code)]
+pub fn extract(fen: &str, result: &str) -> Result<TexelEntry, String> {
+    let board = Board::try_from_fen(fen)?;
+    if is_two_knights_fortress(&board)
+        || king_pawn_versus_king(&board, &EvalParams::DEFAULT).is_some()
+    {
+        return Err(format!("Position is scored by an endgame rule: {fen}"));
+    }
+
+    let features = SimpleEvaluator::new().features(&board, Color::White);
+    let score = features
+        .iter()
+        .zip(EvalParams::DEFAULT.weights())
+        .fold(0i64, |score, (feature, weight)| {
+            score.saturating_add(feature.saturating_mul(weight))
+        });
+    if score.abs() > MAX_EVAL {
+        return Err(format!("Position is beyond the evaluation limit: {fen}"));
+    }
+
+    Ok(TexelEntry {
+        features,
+        piece_squares: piece_square_counts(&board),
+        phase: board.total_material_phase(),
+        result: parse_result(result)?,
+    })
+}
+
+impl fmt::Display for TexelEntry {
+    /// Formats the entry as the result followed by every feature and the phase, separated by
+    /// spaces, and then each occupied piece-square entry as `entry:count`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1}", self.result)?;
+        for feature in self.features {
+            write!(f, " {feature}")?;
+        }
+        write!(f, " {}", self.phase)?;
+        for (entry, count) in self.piece_squares.iter().enumerate() {
+            if *count != 0 {
+                write!(f, " {entry}:{count}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluate::params::EvalParams;
+    use crate::evaluate::Evaluator;
+    use pretty_assertions::assert_eq;
+
+    fn dot(features: [i64; FEATURE_COUNT]) -> i64 {
+        features
+            .iter()
+            .zip(EvalParams::DEFAULT.weights())
+            .map(|(feature, weight)| feature * weight)
+            .sum()
+    }
+
+    #[test]
+    fn test_features_dot_weights_is_evaluation() {
+        let fen = "r5k1/5p2/2n5/8/6pp/2N5/3Q1PPP/R5K1 w - - 0 1";
+        let entry = extract(fen, "1-0").unwrap();
+        let mut board = Board::from_fen(fen);
+
+        assert_eq!(
+            dot(entry.features),
+            SimpleEvaluator::new().evaluate(&mut board)
+        );
+    }
+
+    #[test]
+    fn test_features_are_measured_for_white() {
+        let fen = "r5k1/5p2/2n5/8/6pp/2N5/3Q1PPP/R5K1 b - - 0 1";
+        let entry = extract(fen, "0-1").unwrap();
+        let mut board = Board::from_fen(fen);
+
+        assert_eq!(
+            dot(entry.features),
+            -SimpleEvaluator::new().evaluate(&mut board)
+        );
+    }
+
+    #[test]
+    fn test_extract_rejects_malformed_fen() {
+        assert!(extract("4k3/8/8/8/8/8/4P3/4K3 x - - 0 1", "1-0").is_err());
+        assert!(extract("4k3/8/8/8/8/4P3/4K3 w - - 0 1", "1-0").is_err());
+        assert!(extract("not a fen", "1-0").is_err());
+    }
+
+    #[test]
+    fn test_extract_rejects_positions_that_are_not_linear() {
+        let two_knights = "4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1";
+        let king_and_pawn = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+        let clamped =
+            "QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQQQ/QQQQQQKk w - - 0 1";
+
+        assert!(extract(two_knights, "1/2-1/2").is_err());
+        assert!(extract(king_and_pawn, "1-0").is_err());
+        assert!(extract(clamped, "1-0").is_err());
+    }
+
+    #[test]
+    fn test_parse_result() {
+        let draw = parse_result("1/2-1/2").unwrap();
+        let loss = parse_result("0.0").unwrap();
+
+        assert!((draw - 0.5).abs() < f64::EPSILON);
+        assert!(loss.abs() < f64::EPSILON);
+        assert!(parse_result("2-0").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let entry = extract("4k3/8/4p3/8/8/8/4P3/4K3 w - - 0 1", "1/2-1/2").unwrap();
+        assert_eq!(
+            entry.to_string(),
+            "0.5 0 0 0 0 0 0 0 -5 0 0 0 0 0 0 0 44:-1 52:1"
+        );
+    }
+}
@@ -11,12 +11,22 @@
 extern crate strum_macros;
 extern crate derive_more;
 
+mod bench;
 mod board;
+mod book;
+mod epd;
 mod evaluate;
+mod perft;
 mod search;
 mod uci;
 mod utils;
 
 fn main() {
+    if std::env::args().nth(1).as_deref() == Some("bench") {
+        println!("{}", bench::bench(bench::BENCH_DEPTH));
+        return;
+    }
+
+    board::piece::init_attack_tables();
     uci::start();
 }
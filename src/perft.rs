@@ -0,0 +1,309 @@
+use crate::board::perft::MoveLegality;
+use crate::board::{Board, Ply};
+
+/// Returns the number of positions reachable from the board in exactly `depth` legal moves
+///
+/// # Arguments
+///
+/// * `board` - The board to count from
+/// * `depth` - The number of plies to search
+///
+/// # Returns
+///
+/// * `u64` - The number of leaf nodes
+///
+/// # Example
+/// ```
+/// let mut board = BoardBuilder::construct_starting_board().build();
+/// assert_eq!(8902, perft(&mut board, 3));
+/// ```
+pub fn perft(board: &mut Board, depth: u8) -> u64 {
+    board.perft(depth, MoveLegality::Legal)
+}
+
+/// Counts the positions below each legal move of the board and prints one line per move
+/// followed by the total, in the format other engines use so that the output can be diffed
+///
+/// # Arguments
+///
+/// * `board` - The board to count from
+/// * `depth` - The number of plies to search, including the root moves
+///
+/// # Returns
+///
+/// * `Vec<(Ply, u64)>` - Every root move with the number of leaf nodes below it, sorted by move
+///
+/// # Example
+/// ```
+/// let mut board = BoardBuilder::construct_starting_board().build();
+/// let divided = perft_divide(&mut board, 2);
+/// assert_eq!(20, divided.len());
+/// ```
+pub fn perft_divide(board: &mut Board, depth: u8) -> Vec<(Ply, u64)> {
+    let mut divided: Vec<(Ply, u64)> = board
+        .get_legal_moves()
+        .into_iter()
+        .map(|mv| {
+            board.make_move(mv);
+            let nodes = perft(board, depth.saturating_sub(1));
+            board.unmake_move();
+            (mv, nodes)
+        })
+        .collect();
+    divided.sort_by_key(|(mv, _)| mv.to_string());
+
+    for (mv, nodes) in &divided {
+        println!("{mv}: {nodes}");
+    }
+    println!();
+    println!(
+        "Nodes searched: {}",
+        divided.iter().map(|(_, nodes)| nodes).sum::<u64>()
+    );
+
+    divided
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+    use test::Bencher;
+
+    #[test]
+    fn test_perft_divide_sums_to_perft() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let divided = perft_divide(&mut board, 3);
+
+        assert_eq!(divided.len(), 20);
+        assert_eq!(divided.iter().map(|(_, nodes)| nodes).sum::<u64>(), 8902);
+        let (e2e4, nodes) = divided
+            .iter()
+            .find(|(mv, _)| mv.to_string() == "e2e4")
+            .unwrap();
+        assert_eq!(e2e4.to_string(), "e2e4");
+        assert_eq!(*nodes, 600);
+    }
+
+    #[test]
+    fn test_perft_kiwipete() {
+        let mut board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        assert_eq!(perft(&mut board, 1), 48);
+        assert_eq!(perft(&mut board, 2), 2039);
+        assert_eq!(perft(&mut board, 3), 97862);
+    }
+
+    #[test]
+    fn test_perft_depth_1() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let nodes = perft(&mut board, 1);
+        assert_eq!(nodes, 20);
+    }
+
+    #[test]
+    fn test_perft_depth_2() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let nodes = perft(&mut board, 2);
+        assert_eq!(nodes, 400);
+    }
+
+    #[test]
+    fn test_perft_depth_3() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let nodes = perft(&mut board, 3);
+        assert_eq!(nodes, 8902);
+    }
+
+    #[test]
+    fn test_perft_depth_4() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let nodes = perft(&mut board, 4);
+        assert_eq!(nodes, 197_281);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_perft_depth_5() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let nodes = perft(&mut board, 5);
+        assert_eq!(nodes, 4_865_609);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_perft_depth_6() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let nodes = perft(&mut board, 6);
+        assert_eq!(nodes, 119_060_324);
+    }
+
+    #[bench]
+    fn bench_perft_depth_1(bencher: &mut Bencher) {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        bencher.iter(|| perft(&mut board, 1));
+    }
+
+    #[bench]
+    fn bench_perft_depth_2(bencher: &mut Bencher) {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        bencher.iter(|| perft(&mut board, 2));
+    }
+
+    #[bench]
+    fn bench_perft_depth_3(bencher: &mut Bencher) {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        bencher.iter(|| perft(&mut board, 3));
+    }
+
+    #[test]
+    fn test_perft_from_position_1() {
+        let mut board =
+            Board::from_fen("rnbqkbnr/1ppppppp/p7/P7/8/8/1PPPPPPP/RNBQKBNR b KQkq - 0 2");
+        let nodes = perft(&mut board, 2);
+        assert_eq!(nodes, 380);
+    }
+
+    #[test]
+    fn test_perft_from_position_2() {
+        let mut board =
+            Board::from_fen("rnbqkbnr/2pppppp/p7/Pp6/8/8/1PPPPPPP/RNBQKBNR w KQkq b6 0 3");
+        let nodes = perft(&mut board, 1);
+        assert_eq!(nodes, 22);
+    }
+
+    #[test]
+    fn test_perft_from_position_3() {
+        let mut board =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/5P2/PPPPP1PP/RNBQKBNR b KQkq - 0 1");
+        let nodes = perft(&mut board, 4);
+        assert_eq!(nodes, 178_889);
+    }
+
+    #[test]
+    fn test_perft_from_position_4() {
+        let mut board =
+            Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/8/5P2/PPPPP1PP/RNBQKBNR w KQkq - 0 2");
+        let nodes = perft(&mut board, 3);
+        assert_eq!(nodes, 11_679);
+    }
+
+    #[test]
+    fn test_perft_from_position_5() {
+        let mut board =
+            Board::from_fen("rn1qkbnr/p1pppppp/bp6/8/8/N3PN2/PPPP1PPP/R1BQKB1R b KQkq - 0 3");
+        let nodes = perft(&mut board, 2);
+        assert_eq!(nodes, 636);
+    }
+
+    #[test]
+    fn test_perft_from_position_6() {
+        let mut board =
+            Board::from_fen("rn1qkbnr/p1pppppp/1p6/8/8/N3PN2/PPPP1PPP/R1BQKb1R w KQkq - 0 4");
+        let nodes = perft(&mut board, 1);
+        assert_eq!(nodes, 24);
+    }
+
+    #[test]
+    fn test_perft_from_position_7() {
+        let mut board =
+            Board::from_fen("rnb1kbnr/1p1p1ppp/8/2p5/p1QPP3/2N4q/PPP1NP2/R1B1K1R1 w Qkq - 1 13");
+        let nodes = perft(&mut board, 2);
+        assert_eq!(nodes, 1515);
+    }
+
+    #[test]
+    fn test_perft_from_position_8() {
+        let mut board = Board::from_fen("rnb1kqRQ/1p1p3p/8/2p5/p3P3/8/PPP1NP2/R3K3 b Q - 2 24");
+        let nodes = perft(&mut board, 2);
+        assert_eq!(nodes, 591);
+    }
+
+    #[test]
+    fn test_perft_from_position_9() {
+        let mut board = Board::from_fen("rnb1k1qQ/1p1p3p/8/2p5/p3P3/8/PPP1NP2/R3K3 w Q - 0 25");
+        let nodes = perft(&mut board, 1);
+        assert_eq!(nodes, 28);
+    }
+
+    #[test]
+    fn test_perft_chess960_position_1() {
+        let mut board =
+            Board::from_fen("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9");
+        assert_eq!(perft(&mut board, 1), 21);
+        assert_eq!(perft(&mut board, 2), 528);
+        assert_eq!(perft(&mut board, 3), 12_189);
+    }
+
+    #[test]
+    fn test_perft_chess960_position_2() {
+        let mut board =
+            Board::from_fen("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9");
+        assert_eq!(perft(&mut board, 1), 21);
+        assert_eq!(perft(&mut board, 2), 807);
+        assert_eq!(perft(&mut board, 3), 18_002);
+    }
+
+    #[test]
+    #[ignore]
+    // Kiwipete position
+    fn test_perft_from_position_10() {
+        let mut board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -");
+        let nodes = perft(&mut board, 4);
+        assert_eq!(nodes, 4085603);
+    }
+
+    #[test]
+    fn test_perft_from_position_11() {
+        let mut board =
+            Board::from_fen("r3k2r/p1ppqNb1/bn2pnp1/3P4/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1");
+        let nodes = perft(&mut board, 3);
+        assert_eq!(nodes, 88799);
+    }
+
+    #[test]
+    fn test_perft_from_position_12() {
+        let mut board =
+            Board::from_fen("r3k2r/pbppqNb1/1n2pnp1/3P4/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 1 2");
+        let nodes = perft(&mut board, 2);
+        assert_eq!(nodes, 2050);
+    }
+
+    #[test]
+    fn test_perft_from_position_13() {
+        let mut board = Board::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - -");
+        let nodes = perft(&mut board, 5);
+        assert_eq!(nodes, 674624);
+    }
+
+    #[test]
+    fn test_perft_from_position_14() {
+        let mut board =
+            Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1");
+        let nodes = perft(&mut board, 4);
+        assert_eq!(nodes, 422333);
+    }
+
+    #[test]
+    fn test_perft_from_position_15() {
+        let mut board =
+            Board::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8");
+        let nodes = perft(&mut board, 3);
+        assert_eq!(nodes, 62379);
+    }
+
+    #[test]
+    fn test_perft_from_position_16() {
+        let mut board = Board::from_fen(
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        );
+        let nodes = perft(&mut board, 3);
+        assert_eq!(nodes, 89890);
+    }
+}
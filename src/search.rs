@@ -1,39 +1,112 @@
-use super::board::{Board, Ply};
-use super::evaluate::Evaluator;
+use super::board::piece::Kind;
+use super::board::see::see_value;
+use super::board::{Board, GameState, Ply};
+use super::evaluate::{Evaluator, MAX_EVAL};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
 const DEFAULT_DEPTH: usize = 6;
+/// The deepest an infinite search iterates to
+pub const MAX_PLY: usize = 64;
 
+/// The number of moves searched at full depth before late move reductions apply
+const LMR_FULL_DEPTH_MOVES: usize = 3;
+/// The smallest remaining depth at which late moves are reduced
+const LMR_MIN_DEPTH: usize = 3;
+
+pub mod config;
+pub mod info;
 pub mod limits;
+pub mod logger;
+pub mod move_orderer;
+pub mod transposition_table;
 
+use config::Config;
+use info::{AspirationStats, Info, Line};
 use limits::SearchLimits;
+use logger::{Logger, StdoutLogger};
+use move_orderer::{MoveOrderer, MoveTag, StagedMoveOrderer};
+use transposition_table::{Bound, TTEntry, TranspositionTable};
+
+/// How far below alpha the static evaluation must be to prune quiet moves, indexed by the depth
+/// left. Deeper nodes are never pruned.
+const FUTILITY_MARGINS: [i64; 3] = [0, 200, 400];
+/// How much of the futility margin is taken off at a node whose static evaluation is improving,
+/// as a fraction of the margin
+const IMPROVING_FUTILITY_DIVISOR: i64 = 4;
+/// How far below alpha the static evaluation must be to drop into quiescence search, indexed by
+/// the depth left. Deeper nodes are never razored.
+const RAZOR_MARGINS: [i64; 3] = [0, 300, 600];
+
+/// The half-width of the first aspiration window around the previous iteration's score
+const ASPIRATION_DELTA: i64 = 50;
+/// The number of times a failed aspiration window is widened before searching with a full window
+const ASPIRATION_MAX_RESEARCHES: u32 = 3;
+
+/// The move time budget, in milliseconds, below which an obvious recapture is played at once
+const RECAPTURE_TIME_BUDGET: u64 = 100;
+/// How much more material an obvious recapture must win than the best alternative
+const RECAPTURE_SEE_MARGIN: i64 = 200;
 
-const NEGMAX: i64 = -i64::MAX;
+/// The shortest time, in milliseconds, between two `info` lines sent in the middle of an iteration
+const INFO_INTERVAL_MS: u128 = 250;
+/// How long, in milliseconds, an iteration runs before it starts reporting its progress
+const HEARTBEAT_DELAY_MS: u128 = 1_000;
+
+/// The score of checkmating the opponent at the root, which every other mate score counts down from
+const MATE: i64 = i64::MAX;
+/// The score of being checkmated at the root, the lowest score a position can have
+const NEGMAX: i64 = -MATE;
+/// Scores within this many plies of `MATE` or `NEGMAX` are forced mates, closer mates scoring
+/// further from zero
+const MATE_PLY_RANGE: i64 = 1_000;
+/// The score of a drawn position before any contempt is applied
+const DRAW: i64 = 0;
 #[allow(dead_code)]
 pub struct Search<T: Evaluator> {
     board: Board,
     evaluator: T,
     limits: SearchLimits,
+    config: Config,
     best_move: Option<Ply>,
     running: Arc<AtomicBool>,
+    pondering: Arc<AtomicBool>,
+    info: Info,
+    transposition_table: Arc<RwLock<TranspositionTable>>,
+    logger: Box<dyn Logger + Send>,
+    start_time: Instant,
+    searching_since: Instant,
+    last_info: Instant,
+    time_management_timer: Option<u64>,
+    hard_time_limit: Option<u64>,
+    game_result: Option<GameState>,
 
-    depth: u64,
     nodes: u64,
     movetime: u64,
 }
 
 impl<T: Evaluator> Search<T> {
     pub fn new(board: &Board, evaluator: &T, limits: Option<SearchLimits>) -> Self {
+        let limits = limits.unwrap_or_default();
         Self {
             board: board.clone(),
             evaluator: evaluator.clone(),
-            limits: limits.unwrap_or_default(),
+            time_management_timer: limits.time_budget(board.current_turn),
+            hard_time_limit: limits.hard_time_limit(board.current_turn),
+            pondering: Arc::new(AtomicBool::new(limits.ponder)),
+            limits,
+            config: Config::default(),
             best_move: None,
             running: Arc::new(AtomicBool::new(true)),
+            info: Info::new(),
+            transposition_table: Arc::new(RwLock::new(TranspositionTable::default())),
+            logger: Box::new(StdoutLogger),
+            start_time: Instant::now(),
+            searching_since: Instant::now(),
+            last_info: Instant::now(),
+            game_result: None,
 
-            depth: 0,
             nodes: 0,
             movetime: 0,
         }
@@ -58,6 +131,172 @@ impl<T: Evaluator> Search<T> {
         self.best_move
     }
 
+    /// Replaces the tunable parameters used by the search
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The parameters to use for future searches
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// search.set_config(Config::new().qsearch_see_threshold(-100));
+    /// ```
+    pub const fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
+    /// Replaces the transposition table used by the search, so that it can be shared with the
+    /// caller and kept between searches
+    ///
+    /// # Arguments
+    ///
+    /// * `transposition_table` - The table to probe and store results in
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// search.set_transposition_table(Arc::new(RwLock::new(TranspositionTable::default())));
+    /// ```
+    pub fn set_transposition_table(
+        &mut self,
+        transposition_table: Arc<RwLock<TranspositionTable>>,
+    ) {
+        self.transposition_table = transposition_table;
+    }
+
+    /// Replaces where the search writes its UCI output, which is standard output by default
+    ///
+    /// # Arguments
+    ///
+    /// * `logger` - The sink to write every output line to
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// search.set_logger(Box::new(StdoutLogger));
+    /// ```
+    #[allow(dead_code)]
+    pub fn set_logger(&mut self, logger: Box<dyn Logger + Send>) {
+        self.logger = logger;
+    }
+
+    /// Writes a line of UCI output to the logger
+    fn log(&self, line: &str) {
+        self.logger.log(line);
+    }
+
+    /// Writes a line of output unless another line was written less than `INFO_INTERVAL_MS` ago,
+    /// so that slow GUIs reading from a pipe are not flooded
+    fn log_throttled(&mut self, line: &str) {
+        if self.last_info.elapsed().as_millis() >= INFO_INTERVAL_MS {
+            self.log(line);
+            self.last_info = Instant::now();
+        }
+    }
+
+    /// Returns the principal variation found by the most recent search
+    ///
+    /// The line is collected from the search tree itself, so it always reaches the searched depth
+    /// unless the game ends first.
+    ///
+    /// # Returns
+    ///
+    /// * `&[Ply]` - The principal variation, starting with the best move
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// search.search(Some(3));
+    /// let pv = search.get_pv();
+    /// ```
+    pub fn get_pv(&self) -> &[Ply] {
+        self.info
+            .lines
+            .first()
+            .map_or(&[], |line| line.pv.as_slice())
+    }
+
+    /// Writes the `bestmove` line for the most recent search, suggesting the second move of the
+    /// principal variation to ponder on when there is one
+    ///
+    /// A finished game has no move to play, which UCI writes as `bestmove (none)` after an
+    /// `info string` saying whether it ended in checkmate or stalemate.
+    ///
+    /// # Arguments
+    ///
+    /// * `best_move` - The move the search settled on, if there was any
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// let best_move = search.search(Some(3));
+    /// search.report_best_move(best_move);
+    /// ```
+    pub fn report_best_move(&self, best_move: Option<Ply>) {
+        let Some(best_move) = best_move else {
+            match self.get_game_result() {
+                Some(GameState::CheckmateWhite | GameState::CheckmateBlack) => {
+                    self.log("info string checkmate");
+                }
+                Some(GameState::Stalemate) => self.log("info string stalemate"),
+                _ => (),
+            }
+            self.log("bestmove (none)");
+            return;
+        };
+
+        match self.get_pv().get(1) {
+            Some(ponder_move) => self.log(&format!("bestmove {best_move} ponder {ponder_move}")),
+            None => self.log(&format!("bestmove {best_move}")),
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Returns the best lines found by the most recent search, one for each of the `multipv`
+    /// best root moves
+    ///
+    /// # Returns
+    ///
+    /// * `&[Line]` - The score and principal variation of each line, best first
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// search.set_config(Config::new().multipv(3));
+    /// search.search(Some(3));
+    /// let lines = search.get_lines();
+    /// ```
+    pub fn get_lines(&self) -> &[Line] {
+        &self.info.lines
+    }
+
+    /// Returns the number of nodes visited so far, including those of quiescence search
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// search.search(Some(3));
+    /// println!("{} nodes", search.get_nodes());
+    /// ```
+    pub const fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
     /// Returns the `AtomicBool` that is used to determine if the search should continue
     ///
     /// # Returns
@@ -92,6 +331,84 @@ impl<T: Evaluator> Search<T> {
         self.running.load(Ordering::Relaxed)
     }
 
+    /// Returns the `AtomicBool` that is set while the search is pondering
+    ///
+    /// Clearing it signals a `ponderhit`: the time limits start counting from that moment.
+    ///
+    /// # Returns
+    ///
+    /// * `Arc<AtomicBool>` - The `AtomicBool` that is set while the search is pondering
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let search = Search::new(&board, &evaluator, Some(SearchLimits::new().ponder(true)));
+    /// let pondering = search.get_pondering();
+    /// ```
+    pub fn get_pondering(&self) -> Arc<AtomicBool> {
+        self.pondering.clone()
+    }
+
+    /// Records how long the search has been running for
+    ///
+    /// While pondering the clock is not running yet, so the time base keeps moving forward and
+    /// ends up at the moment the `ponderhit` arrived.
+    fn update_elapsed(&mut self) {
+        if self.pondering.load(Ordering::Relaxed) {
+            self.start_time = Instant::now();
+            self.movetime = 0;
+        } else {
+            self.movetime =
+                u64::try_from(self.start_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+        }
+    }
+
+    /// Returns whether the search should stop, either because it was told to or because it has
+    /// exceeded one of its limits
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - A boolean determining if the search should stop
+    fn limits_exceeded(&mut self) -> bool {
+        self.update_elapsed();
+        self.heartbeat();
+        !self.check_running() || self.check_limits()
+    }
+
+    /// Reports how many nodes have been searched and how fast, so that a GUI can tell a long
+    /// iteration is still making progress
+    fn heartbeat(&mut self) {
+        let time = self.searching_since.elapsed().as_millis();
+        if time < HEARTBEAT_DELAY_MS || self.last_info.elapsed().as_millis() < INFO_INTERVAL_MS {
+            return;
+        }
+
+        let nps = u128::from(self.nodes) * 1000 / time.max(1);
+        self.log_throttled(&format!("info nodes {} nps {nps} time {time}", self.nodes));
+    }
+
+    /// Reports which root move is about to be searched, once an iteration has run long enough that
+    /// a GUI would otherwise show no progress
+    ///
+    /// # Arguments
+    ///
+    /// * `mv` - The root move about to be searched
+    /// * `number` - The position of the move in the root move order, starting at 1
+    fn report_currmove(&mut self, mv: Ply, number: usize) {
+        if self.searching_since.elapsed().as_millis() < HEARTBEAT_DELAY_MS {
+            return;
+        }
+
+        self.log_throttled(&format!("info currmove {mv} currmovenumber {number}"));
+    }
+
+    /// Returns whether the search has been told to stop or has exceeded one of its limits, without
+    /// updating the elapsed time
+    fn is_stopped(&self) -> bool {
+        !self.check_running() || self.check_limits()
+    }
+
     /// Checks if the search has exceeded any of the limits
     ///
     /// # Returns
@@ -106,10 +423,8 @@ impl<T: Evaluator> Search<T> {
     /// let limits_exceeded = search.check_limits();
     /// ```
     const fn check_limits(&self) -> bool {
-        if let Some(depth) = self.limits.depth {
-            if self.depth >= depth {
-                return true;
-            }
+        if self.limits.infinite {
+            return false;
         }
         if let Some(nodes) = self.limits.nodes {
             if self.nodes >= nodes {
@@ -121,19 +436,36 @@ impl<T: Evaluator> Search<T> {
                 return true;
             }
         }
+        if let Some(timer) = self.time_management_timer {
+            if self.movetime >= timer {
+                return true;
+            }
+        }
+        // Whatever the budget, running out the clock loses the game
+        if let Some(limit) = self.hard_time_limit {
+            if self.movetime >= limit {
+                return true;
+            }
+        }
 
         false
     }
 
     /// Initializes the search and returns the best move found
     ///
+    /// When only one legal move is available it is played after a single ply of search, since
+    /// searching deeper cannot change the choice. The same goes for an obvious recapture when the
+    /// clock is nearly out. When there is no legal move at all the game is over, and its result
+    /// is recorded instead.
+    ///
     /// # Arguments
     ///
     /// * `depth` - An optional `usize` that determines the depth of the search
     ///
     /// # Returns
     ///
-    /// * `Ply` - The best move found by the search
+    /// * `Option<Ply>` - The best move found by the search, or `None` if the side to move is
+    ///   checkmated or stalemated
     ///
     /// # Example
     /// ```
@@ -142,146 +474,890 @@ impl<T: Evaluator> Search<T> {
     /// let mut search = Search::new(&board, &evaluator, None);
     /// let best_move = search.search(Some(3));
     /// ```
-    pub fn search(&mut self, depth: Option<usize>) -> Ply {
-        self.alpha_beta_start(depth.unwrap_or(DEFAULT_DEPTH))
+    pub fn search(&mut self, depth: Option<usize>) -> Option<Ply> {
+        self.game_result = None;
+        match self.board.legal_move_count() {
+            0 => {
+                self.record_game_result();
+                return None;
+            }
+            1 => return Some(self.iter_deep(1)),
+            _ => (),
+        }
+        if !self.limits.searchmoves.is_empty() && self.root_moves().is_empty() {
+            self.log("info string None of the searchmoves are legal, searching every move");
+            self.limits.searchmoves.clear();
+        }
+        if let Some(recapture) = self.obvious_recapture() {
+            self.limits.searchmoves = vec![recapture];
+            return Some(self.iter_deep(1));
+        }
+
+        Some(self.iter_deep(depth.unwrap_or_else(|| self.max_depth())))
+    }
+
+    /// Returns the deepest iteration the search limits allow
+    ///
+    /// An infinite search iterates up to `MAX_PLY`, and otherwise a `depth` limit caps the
+    /// iterations. Without one, a search bounded by nodes or time iterates until that bound is
+    /// hit, and an unbounded search stops after `DEFAULT_DEPTH`.
+    fn max_depth(&self) -> usize {
+        if self.limits.infinite {
+            return MAX_PLY;
+        }
+        if let Some(depth) = self.limits.depth {
+            return usize::try_from(depth).map_or(MAX_PLY, |depth| depth.clamp(1, MAX_PLY));
+        }
+        // Mating in n moves takes the side to move n moves and its opponent n - 1 replies
+        if let Some(moves) = self.limits.mate {
+            return usize::try_from(moves.saturating_mul(2).saturating_sub(1))
+                .map_or(MAX_PLY, |depth| depth.clamp(1, MAX_PLY));
+        }
+        let bounded = self.limits.nodes.is_some()
+            || self.limits.movetime.is_some()
+            || self.time_management_timer.is_some();
+
+        if bounded {
+            MAX_PLY
+        } else {
+            DEFAULT_DEPTH
+        }
+    }
+
+    /// Returns whether a `go mate` search has proven a mate within the requested number of moves
+    fn found_requested_mate(&self) -> bool {
+        self.limits.mate.is_some_and(|moves| {
+            self.info
+                .score
+                .and_then(mate_distance)
+                .and_then(|plies| u64::try_from(plies).ok())
+                .is_some_and(|plies| plies.div_ceil(2) <= moves)
+        })
+    }
+
+    /// Records how the game ended when the side to move has no legal move, and reports the final
+    /// score the way UCI engines do for a finished game
+    fn record_game_result(&mut self) {
+        let score = if self.board.in_check() {
+            "mate 0"
+        } else {
+            "cp 0"
+        };
+        self.board.is_game_over();
+        self.game_result = Some(self.board.game_state);
+        self.log(&format!("info depth 0 score {score}"));
+    }
+
+    /// Returns how the game ended if the most recent search found no legal move to play
+    ///
+    /// # Returns
+    ///
+    /// * `Option<GameState>` - Either `GameState::CheckmateWhite` or `GameState::CheckmateBlack`
+    ///   for the side that was mated, `GameState::Stalemate`, or `None` if there was a move
+    ///
+    /// # Example
+    /// ```
+    /// let board = Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1");
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// assert_eq!(search.search(Some(3)), None);
+    /// assert_eq!(search.get_game_result(), Some(GameState::CheckmateBlack));
+    /// ```
+    pub const fn get_game_result(&self) -> Option<GameState> {
+        self.game_result
     }
 
-    /// Initializes the alpha-beta search and returns the best move found
+    /// Searches the position to increasing depths until `max_depth` is reached or the search is
+    /// stopped, reporting the result of every completed iteration
+    ///
+    /// Each iteration finds the `multipv` best root moves one at a time, by searching again with
+    /// the moves already found left out. An iteration that is interrupted is discarded unless no
+    /// iteration has completed yet.
     ///
     /// # Arguments
     ///
-    /// * `depth` - A `usize` that determines the depth of the search
+    /// * `max_depth` - The depth of the final iteration
     ///
     /// # Returns
     ///
-    /// * `Ply` - The best move found by the search
+    /// * `Ply` - The best move found by the deepest completed iteration
     ///
     /// # Example
     /// ```
     /// let board = BoardBuilder::construct_starting_board().build();
     /// let evaluator = SimpleEvaluator::new();
     /// let mut search = Search::new(&board, &evaluator, None);
-    /// let best_move = search.alpha_beta_start(3);
+    /// let best_move = search.iter_deep(3);
     /// ```
-    fn alpha_beta_start(&mut self, depth: usize) -> Ply {
+    fn iter_deep(&mut self, max_depth: usize) -> Ply {
         let start = Instant::now();
-        let mut best_value = i64::MIN;
-        let moves = self.board.get_legal_moves();
+        self.searching_since = start;
+        self.last_info = start;
+        self.info.reset(max_depth);
+        self.best_move = None;
+        self.transposition_table
+            .write()
+            .expect("The transposition table lock should never be poisoned")
+            .new_generation();
+        let line_count = self.config.multipv.min(self.root_moves().len()).max(1);
 
-        let mut best_ply = moves[0];
+        for depth in 1..=max_depth {
+            let mut lines = Vec::with_capacity(line_count);
+            let mut excluded = Vec::with_capacity(line_count);
+            while lines.len() < line_count {
+                let (best_ply, score) = if excluded.is_empty() {
+                    self.aspiration_search(depth, start)
+                } else {
+                    self.alpha_beta_start(depth, i64::MIN, i64::MAX, &excluded)
+                };
 
-        for mv in moves {
-            self.board.make_move(mv);
+                let pv = match self.info.pv_table[0].first() {
+                    Some(&first) if first == best_ply => self.info.pv_table[0].clone(),
+                    _ => vec![best_ply],
+                };
+                excluded.push(best_ply);
+                lines.push(Line { score, pv });
+                if self.is_stopped() {
+                    break;
+                }
+            }
+            if self.is_stopped() && self.best_move.is_some() {
+                break;
+            }
 
-            let value = self
-                .alpha_beta(i64::MIN, i64::MAX, depth - 1)
-                .saturating_neg();
-            if value > best_value {
-                best_value = value;
-                best_ply = mv;
+            self.best_move = Some(excluded[0]);
+            self.info.score = Some(lines[0].score);
+            self.info.lines = lines;
+            self.report(depth, start.elapsed().as_millis());
+            self.last_info = Instant::now();
+            if self.found_requested_mate() {
+                break;
             }
-            self.board.unmake_move();
         }
 
-        let duration = start.elapsed();
-        let time_elapsed_in_ms = duration.as_millis();
-        match best_value {
-            i64::MIN | NEGMAX => {
-                println!(
-                    "info depth {depth} time {time_elapsed_in_ms} score mate -1 pv {best_ply}"
-                );
+        if let Some(moves) = self.limits.mate.filter(|_| !self.found_requested_mate()) {
+            self.log(&format!("info string no mate in {moves} found"));
+        }
+
+        self.best_move
+            .expect("The search always completes or keeps its first iteration")
+    }
+
+    /// Searches the root to `depth` inside a window centred on the score of the previous
+    /// iteration, widening the window and searching again whenever the score falls outside of it
+    ///
+    /// Mate scores, the lack of a previous score, or failing `ASPIRATION_MAX_RESEARCHES` times
+    /// fall back to a full-width search. Every failure is counted in `Info::aspiration_stats` and
+    /// reported as a `lowerbound` or `upperbound` score, unless another line was reported less
+    /// than `INFO_INTERVAL_MS` ago.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The depth to search the root to
+    /// * `start` - When the first iteration of the search started
+    ///
+    /// # Returns
+    ///
+    /// * `(Ply, i64)` - The best move and its score
+    fn aspiration_search(&mut self, depth: usize, start: Instant) -> (Ply, i64) {
+        let previous = self.info.score.filter(|&score| !is_mate_score(score));
+        let mut delta = ASPIRATION_DELTA;
+        let (mut alpha, mut beta) = previous.map_or((i64::MIN, i64::MAX), |previous| {
+            (previous - delta, previous + delta)
+        });
+
+        loop {
+            let (best_ply, score) = self.alpha_beta_start(depth, alpha, beta, &[]);
+            let failed_low = score <= alpha && alpha != i64::MIN;
+            let failed_high = score >= beta && beta != i64::MAX;
+            if !(failed_low || failed_high) || self.is_stopped() {
+                return (best_ply, score);
             }
-            i64::MAX => {
-                println!("info depth {depth} time {time_elapsed_in_ms} score mate 1 pv {best_ply}");
+
+            if depth >= self.info.aspiration_stats.len() {
+                self.info
+                    .aspiration_stats
+                    .resize(depth + 1, AspirationStats::default());
             }
-            _ => {
-                println!(
-                    "info depth {depth} time {time_elapsed_in_ms} score cp {best_value} pv {best_ply}",
-                );
+            let stats = &mut self.info.aspiration_stats[depth];
+            if failed_low {
+                stats.fail_lows += 1;
+            } else {
+                stats.fail_highs += 1;
             }
-        }
-
-        self.best_move = Some(best_ply);
+            let researches = stats.fail_lows + stats.fail_highs;
+            let bound = if failed_low {
+                "upperbound"
+            } else {
+                "lowerbound"
+            };
+            self.log_throttled(&format!(
+                "info depth {depth} time {} score {} {bound}",
+                start.elapsed().as_millis(),
+                format_score(score)
+            ));
 
-        best_ply
+            delta *= 2;
+            match previous {
+                Some(previous) if delta < MAX_EVAL && researches < ASPIRATION_MAX_RESEARCHES => {
+                    if failed_low {
+                        alpha = previous - delta;
+                    } else {
+                        beta = previous + delta;
+                    }
+                }
+                _ => (alpha, beta) = (i64::MIN, i64::MAX),
+            }
+        }
     }
 
-    /// The alpha-beta search algorithm
+    /// Searches every move at the root to `depth` and returns the best move found
+    ///
+    /// When the root is in check every evasion is searched a ply deeper, so that a defense that
+    /// walks into a forced mate is not mistaken for a safe one.
     ///
     /// # Arguments
     ///
-    /// * `alpha` - The best value for the maximizing player found so far
-    /// * `beta` - The best value for the minimizing player found so far
-    /// * `depthleft` - The depth left to search
+    /// * `depth` - A `usize` that determines the depth of the search
+    /// * `alpha` - The lower bound of the window to search in
+    /// * `beta` - The upper bound of the window to search in
+    /// * `excluded` - Root moves to leave out of the search
     ///
     /// # Returns
     ///
-    /// * `i64` - The score of the "best" position
+    /// * `(Ply, i64)` - The best move found by the search and its score
     ///
     /// # Example
     /// ```
     /// let board = BoardBuilder::construct_starting_board().build();
     /// let evaluator = SimpleEvaluator::new();
     /// let mut search = Search::new(&board, &evaluator, None);
-    /// let score = search.alpha_beta(i64::MIN, i64::MAX, 3);
+    /// let (best_move, score) = search.alpha_beta_start(3, i64::MIN, i64::MAX, &[]);
     /// ```
-    fn alpha_beta(&mut self, mut alpha: i64, beta: i64, depthleft: usize) -> i64 {
-        if depthleft == 0 || !self.check_running() || self.check_limits() {
-            return self.evaluator.evaluate(&mut self.board);
-        }
+    fn alpha_beta_start(
+        &mut self,
+        depth: usize,
+        mut alpha: i64,
+        beta: i64,
+        excluded: &[Ply],
+    ) -> (Ply, i64) {
+        self.info.enter_node(0);
+        let mut moves = self.root_moves();
+        moves.retain(|mv| !excluded.contains(mv));
+        let orderer = MoveOrderer::new(&self.board, moves, self.best_move, [None, None], None);
+        self.info.extension_budget = depth;
+        let in_check = self.board.in_check();
+        let child_depth = depth.saturating_add(self.info.check_extension(0, in_check)) - 1;
 
-        let moves = self.board.get_legal_moves();
-        if moves.is_empty() {
-            if self.board.is_in_check(self.board.current_turn) {
-                return i64::MIN; // Checkmate
-            }
-            return 0; // Stalemate
-        }
+        let mut best_value = i64::MIN;
+        let mut best_ply = None;
 
-        for mv in moves {
+        for (idx, (mv, _)) in orderer.enumerate() {
+            self.report_currmove(mv, idx + 1);
             self.board.make_move(mv);
-            let score = self
-                .alpha_beta(beta.saturating_neg(), alpha.saturating_neg(), depthleft - 1)
+            let value = self
+                .alpha_beta(
+                    beta.saturating_neg(),
+                    alpha.saturating_neg(),
+                    child_depth,
+                    1,
+                )
                 .saturating_neg();
             self.board.unmake_move();
 
-            if score >= beta {
-                return beta;
+            if best_ply.is_none() || value > best_value {
+                best_value = value;
+                best_ply = Some(mv);
             }
-            if score > alpha {
-                alpha = score;
+            if value > alpha {
+                alpha = value;
+                self.info.update_pv(0, mv);
+            }
+            if value >= beta {
+                break;
             }
         }
 
-        alpha
+        (
+            best_ply.expect("The root position has no legal moves"),
+            best_value,
+        )
     }
-}
 
-////////////////////////////////////////////////////////////////////////////////
+    /// Returns a recapture worth playing without searching when the side to move is short of time
+    ///
+    /// The last move must have been a capture, and taking back on the same square must win
+    /// material by a wide margin over every other root move according to static exchange
+    /// evaluation.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Ply>` - The recapture, or `None` if there is time to search or no recapture
+    ///   stands out
+    fn obvious_recapture(&mut self) -> Option<Ply> {
+        if self
+            .time_management_timer
+            .is_none_or(|budget| budget >= RECAPTURE_TIME_BUDGET)
+        {
+            return None;
+        }
+        let last_move = self
+            .board
+            .last_move()
+            .filter(|mv| mv.captured_piece.is_some())?;
 
-#[cfg(test)]
-mod tests {
-    extern crate test;
+        let (recaptures, alternatives): (Vec<Ply>, Vec<Ply>) = self
+            .root_moves()
+            .into_iter()
+            .partition(|mv| mv.dest == last_move.dest);
+        let best_alternative = alternatives
+            .iter()
+            .map(|&mv| self.board.see(mv))
+            .max()
+            .unwrap_or(i64::MIN);
 
-    use super::*;
-    use crate::board::BoardBuilder;
-    use crate::evaluate::simple_evaluator::SimpleEvaluator;
-    use test::Bencher;
+        recaptures
+            .into_iter()
+            .map(|mv| (mv, self.board.see(mv)))
+            .max_by_key(|&(_, gain)| gain)
+            .filter(|&(_, gain)| {
+                gain > 0 && gain >= best_alternative.saturating_add(RECAPTURE_SEE_MARGIN)
+            })
+            .map(|(mv, _)| mv)
+    }
 
-    #[test]
-    fn test_get_best_move() {
-        let board = BoardBuilder::construct_starting_board().build();
-        let evaluator = SimpleEvaluator::new();
-        let mut search = Search::new(&board, &evaluator, None);
-        assert!(search.get_best_move().is_none());
-        search.search(Some(3));
-        let best_move = search.get_best_move();
-        assert!(best_move.is_some());
+    /// Returns the legal moves at the root, restricted to `searchmoves` if any were given
+    fn root_moves(&mut self) -> Vec<Ply> {
+        let mut moves = self.board.get_legal_moves();
+        if !self.limits.searchmoves.is_empty() {
+            moves.retain(|mv| self.limits.searchmoves.contains(mv));
+        }
+
+        moves
     }
 
-    #[test]
-    fn test_get_running() {
-        let board = BoardBuilder::construct_starting_board().build();
+    /// Reports the lines of a completed iteration as UCI `info` lines, best first
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The depth of the iteration
+    /// * `time_elapsed_in_ms` - The time spent searching since the first iteration started
+    fn report(&self, depth: usize, time_elapsed_in_ms: u128) {
+        for (idx, line) in self.info.lines.iter().enumerate() {
+            let multipv = idx + 1;
+            let pv = line
+                .pv
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(" ");
+            self.log(&format!(
+                "info depth {depth} multipv {multipv} time {time_elapsed_in_ms} score {} pv {pv}",
+                format_score(line.score)
+            ));
+        }
+    }
+
+    /// The alpha-beta search algorithm
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The best value for the maximizing player found so far
+    /// * `beta` - The best value for the minimizing player found so far
+    /// * `depthleft` - The depth left to search
+    /// * `ply` - The distance from the root of the search
+    ///
+    /// # Returns
+    ///
+    /// * `i64` - The score of the "best" position
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// let score = search.alpha_beta(i64::MIN, i64::MAX, 3, 0);
+    /// ```
+    #[allow(clippy::too_many_lines)]
+    fn alpha_beta(&mut self, mut alpha: i64, mut beta: i64, depthleft: usize, ply: usize) -> i64 {
+        self.info.enter_node(ply);
+
+        // No line through this node can mate sooner than the next ply or be mated sooner than now
+        alpha = alpha.max(mated_in(ply));
+        beta = beta.min(mate_in(ply + 1));
+        if alpha >= beta {
+            return alpha;
+        }
+
+        self.nodes += 1;
+        if self.limits_exceeded() {
+            return self.evaluator.evaluate(&mut self.board);
+        }
+        if ply > 0
+            && (self.board.get_halfmove_clock() >= 100
+                || self.board.repetition_count() >= self.config.repetition_draw_count
+                || self.board.is_insufficient_material())
+        {
+            return self.draw_score(ply); // Draw
+        }
+        if depthleft == 0 {
+            return self.quiescence(alpha, beta);
+        }
+
+        let key = self.board.zobrist_key();
+        let entry = self.probe_entry(key);
+        // Principal variation nodes are searched in full so that their line stays intact
+        let is_pv_node = beta.saturating_sub(alpha) > 1;
+        if let Some(entry) = entry.filter(|entry| !is_pv_node && entry.depth >= depthleft) {
+            match entry.bound {
+                Bound::Exact => return score_from_tt(entry.score, ply),
+                Bound::Lower if score_from_tt(entry.score, ply) >= beta => return beta,
+                Bound::Upper if score_from_tt(entry.score, ply) <= alpha => return alpha,
+                _ => (),
+            }
+        }
+
+        let in_check = self.board.in_check();
+        if !in_check {
+            self.info.static_evals[ply] = Some(self.evaluator.evaluate(&mut self.board));
+        }
+        // Positions hopelessly below alpha only get a full search if their captures look promising
+        if !is_pv_node && self.can_razor(alpha, depthleft, ply) {
+            let score = self.quiescence(alpha, beta);
+            if score <= alpha {
+                return score;
+            }
+        }
+        let improving = self.info.is_improving(ply);
+        // Children of a node in check keep its depth, within the line's extension budget
+        let child_depth = depthleft.saturating_add(self.info.check_extension(ply, in_check)) - 1;
+        let futile = !is_pv_node && self.is_futile(alpha, depthleft, ply, improving);
+
+        let countermove = self.info.countermove(self.board.last_move());
+        let hash_move = self.hash_move(entry);
+        let mut orderer = StagedMoveOrderer::new(hash_move, self.killers(ply), countermove);
+        let mut best_ply = None;
+        let mut move_count = 0;
+
+        while let Some((mv, tag)) = orderer.next_move(&mut self.board) {
+            let move_idx = move_count;
+            move_count += 1;
+            self.board.make_move(mv);
+            let gives_check = self.board.in_check();
+            if futile && tag.is_quiet() && !gives_check {
+                self.board.unmake_move();
+                continue;
+            }
+            let reduction = if in_check || gives_check {
+                0
+            } else {
+                late_move_reduction(depthleft, move_idx, tag, improving)
+            };
+
+            // A null window is enough to prove that a reduced move is no better than alpha
+            let reduced_score = (reduction > 0).then(|| {
+                self.alpha_beta(
+                    alpha.saturating_add(1).saturating_neg(),
+                    alpha.saturating_neg(),
+                    child_depth - reduction,
+                    ply + 1,
+                )
+                .saturating_neg()
+            });
+            let score = match reduced_score {
+                Some(score) if score <= alpha => score,
+                _ => self
+                    .alpha_beta(
+                        beta.saturating_neg(),
+                        alpha.saturating_neg(),
+                        child_depth,
+                        ply + 1,
+                    )
+                    .saturating_neg(),
+            };
+            self.board.unmake_move();
+
+            if score >= beta {
+                if mv.is_quiet() {
+                    self.info
+                        .store_quiet_cutoff(ply, mv, self.board.last_move());
+                }
+                self.store_entry(
+                    key,
+                    depthleft,
+                    score_to_tt(beta, ply),
+                    Bound::Lower,
+                    Some(mv),
+                );
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+                best_ply = Some(mv);
+                self.info.update_pv(ply, mv);
+            }
+        }
+
+        if move_count == 0 {
+            if in_check {
+                return mated_in(ply); // Checkmate
+            }
+            return self.draw_score(ply); // Stalemate
+        }
+
+        let bound = if best_ply.is_some() {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        self.store_entry(key, depthleft, score_to_tt(alpha, ply), bound, best_ply);
+
+        alpha
+    }
+
+    /// Returns whether quiet moves at this node can be skipped because the static evaluation is so
+    /// far below alpha that a quiet move near the leaves is unlikely to make up the difference
+    ///
+    /// Like the late move reductions, the margin tightens at a node whose static evaluation is
+    /// improving, so more of its quiet moves are pruned, and stays wide when it is not, since the
+    /// side to move may be in trouble. Nodes in check have no static evaluation and are never
+    /// futile.
+    fn is_futile(&self, alpha: i64, depthleft: usize, ply: usize, improving: bool) -> bool {
+        let Some(&margin) = FUTILITY_MARGINS.get(depthleft) else {
+            return false;
+        };
+        let margin = if improving {
+            margin - margin / IMPROVING_FUTILITY_DIVISOR
+        } else {
+            margin
+        };
+        self.info.static_evals[ply]
+            .is_some_and(|static_eval| static_eval.saturating_add(margin) <= alpha)
+    }
+
+    /// Returns whether this node is so far below alpha near the leaves that a quiescence search
+    /// is enough to confirm that it fails low
+    ///
+    /// Nodes in check have no static evaluation and are never razored, and neither are nodes
+    /// whose alpha is a mate score, since no static evaluation can be compared to one.
+    fn can_razor(&self, alpha: i64, depthleft: usize, ply: usize) -> bool {
+        let Some(margin) = RAZOR_MARGINS.get(depthleft) else {
+            return false;
+        };
+        !is_mate_score(alpha)
+            && self.info.static_evals[ply]
+                .is_some_and(|static_eval| static_eval.saturating_add(*margin) < alpha)
+    }
+
+    /// Returns the killer moves stored for `ply` that can still be played in the current position
+    ///
+    /// Killers are shared by every node at the same distance from the root, so they may come from
+    /// a different subtree or an earlier iteration where they were possible.
+    fn killers(&self, ply: usize) -> [Option<Ply>; 2] {
+        self.info.killers[ply]
+            .map(|killer| killer.filter(|&killer| self.board.is_pseudo_legal(killer)))
+    }
+
+    /// Returns the best move stored in a transposition table entry if it can be played in the
+    /// current position
+    ///
+    /// An entry whose key collides with another position may carry a move that is nonsense here,
+    /// so it is treated as if there were no hash move at all.
+    fn hash_move(&self, entry: Option<TTEntry>) -> Option<Ply> {
+        entry
+            .and_then(|entry| entry.best_ply)
+            .filter(|&hash_move| self.board.is_pseudo_legal(hash_move))
+    }
+
+    /// Returns the transposition table entry stored for the position with `key`, if there is one
+    fn probe_entry(&self, key: u64) -> Option<TTEntry> {
+        self.transposition_table
+            .read()
+            .expect("The transposition table lock should never be poisoned")
+            .get(key)
+    }
+
+    /// Records the result of searching a position in the transposition table
+    ///
+    /// Nothing is stored once the search has been stopped, since the scores returned while
+    /// unwinding are not trustworthy.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The Zobrist key of the searched position
+    /// * `depth` - The remaining depth the position was searched to
+    /// * `score` - The score returned for the position
+    /// * `bound` - How `score` relates to the true value of the position
+    /// * `best_ply` - The move that produced `score`, if one is known
+    fn store_entry(&self, key: u64, depth: usize, score: i64, bound: Bound, best_ply: Option<Ply>) {
+        if self.is_stopped() {
+            return;
+        }
+
+        self.transposition_table
+            .write()
+            .expect("The transposition table lock should never be poisoned")
+            .insert(TTEntry {
+                key,
+                depth,
+                score,
+                bound,
+                best_ply,
+            });
+    }
+
+    /// Returns the score of a drawn position from the perspective of the side to move
+    ///
+    /// The root side scores draws at minus the contempt, so its opponent, moving on odd plies,
+    /// scores them at plus the contempt for negamax to carry the same value back to the root.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The number of plies from the root of the search
+    ///
+    /// # Returns
+    ///
+    /// * `i64` - The score of the draw
+    ///
+    /// # Example
+    /// ```
+    /// search.set_config(Config::new().contempt(50));
+    /// assert_eq!(search.draw_score(0), -50);
+    /// assert_eq!(search.draw_score(1), 50);
+    /// ```
+    const fn draw_score(&self, ply: usize) -> i64 {
+        if ply.is_multiple_of(2) {
+            DRAW - self.config.contempt
+        } else {
+            DRAW + self.config.contempt
+        }
+    }
+
+    /// Searches captures until the position is quiet, so that the static evaluation is never
+    /// taken in the middle of an exchange
+    ///
+    /// Captures that lose more material than `Config::qsearch_see_threshold` allows according
+    /// to the static exchange evaluation are skipped, as are captures that cannot bring the score
+    /// back up to alpha even with `Config::delta_margin` to spare.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The best value for the maximizing player found so far
+    /// * `beta` - The best value for the minimizing player found so far
+    ///
+    /// # Returns
+    ///
+    /// * `i64` - The score of the quiet position reached
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let evaluator = SimpleEvaluator::new();
+    /// let mut search = Search::new(&board, &evaluator, None);
+    /// let score = search.quiescence(i64::MIN, i64::MAX);
+    /// ```
+    fn quiescence(&mut self, mut alpha: i64, beta: i64) -> i64 {
+        self.nodes += 1;
+        let stand_pat = self.evaluator.evaluate(&mut self.board);
+        if stand_pat >= beta {
+            return beta;
+        }
+        if stand_pat > alpha {
+            alpha = stand_pat;
+        }
+        if self.limits_exceeded() {
+            return alpha;
+        }
+
+        let mut captures = self.board.get_capture_moves();
+        captures.retain(|mv| self.board.see(*mv) >= self.config.qsearch_see_threshold);
+
+        for mv in captures {
+            if let Some(margin) = self.config.delta_margin {
+                if stand_pat.saturating_add(capture_gain(mv) + margin) < alpha {
+                    continue;
+                }
+            }
+            if !self.board.is_legal(mv) {
+                continue;
+            }
+
+            self.board.make_move(mv);
+            let score = self
+                .quiescence(beta.saturating_neg(), alpha.saturating_neg())
+                .saturating_neg();
+            self.board.unmake_move();
+
+            if score >= beta {
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+            }
+        }
+
+        alpha
+    }
+}
+
+/// Returns the most material a capture can win outright, counting the promotion of a pawn
+fn capture_gain(mv: Ply) -> i64 {
+    let captured = mv.captured_piece.map_or(0, see_value);
+    let promotion = mv.promoted_to.map_or(0, |promoted_to| {
+        see_value(promoted_to) - see_value(Kind::Pawn(promoted_to.get_color()))
+    });
+
+    captured + promotion
+}
+
+/// Returns the score of checkmating the opponent `ply` plies from the root
+///
+/// # Example
+/// ```
+/// assert_eq!(mate_in(1), i64::MAX - 1);
+/// assert!(mate_in(1) > mate_in(3));
+/// ```
+const fn mate_in(ply: usize) -> i64 {
+    MATE.saturating_sub_unsigned(ply as u64)
+}
+
+/// Returns the score of being checkmated `ply` plies from the root
+///
+/// # Example
+/// ```
+/// assert_eq!(mated_in(2), -i64::MAX + 2);
+/// assert!(mated_in(2) < mated_in(4));
+/// ```
+const fn mated_in(ply: usize) -> i64 {
+    NEGMAX.saturating_add_unsigned(ply as u64)
+}
+
+/// Returns whether `score` is a forced mate for either side rather than an evaluation
+///
+/// # Example
+/// ```
+/// assert!(is_mate_score(mate_in(3)));
+/// assert!(is_mate_score(mated_in(4)));
+/// assert!(!is_mate_score(MAX_EVAL));
+/// ```
+const fn is_mate_score(score: i64) -> bool {
+    score >= MATE - MATE_PLY_RANGE || score <= NEGMAX + MATE_PLY_RANGE
+}
+
+/// Returns the number of plies until the side to move mates, or is mated if negative, when
+/// `score` is a mate score
+fn mate_distance(score: i64) -> Option<i64> {
+    if !is_mate_score(score) {
+        None
+    } else if score > DRAW {
+        Some(MATE - score)
+    } else {
+        Some(-score.saturating_sub(NEGMAX).max(1))
+    }
+}
+
+/// Converts a mate score measured from the root into one measured from the node at `ply`, so
+/// that the transposition table can hand it to the same position at a different ply
+fn score_to_tt(score: i64, ply: usize) -> i64 {
+    let ply = i64::try_from(ply).unwrap_or(MATE_PLY_RANGE);
+    match mate_distance(score) {
+        Some(distance) if distance >= 0 => score.saturating_add(ply),
+        Some(_) => score.saturating_sub(ply),
+        None => score,
+    }
+}
+
+/// Converts a mate score stored in the transposition table back into one measured from the root
+fn score_from_tt(score: i64, ply: usize) -> i64 {
+    let ply = i64::try_from(ply).unwrap_or(MATE_PLY_RANGE);
+    match mate_distance(score) {
+        Some(distance) if distance >= 0 => score.saturating_sub(ply),
+        Some(_) => score.saturating_add(ply),
+        None => score,
+    }
+}
+
+/// Formats a score the way the UCI `score` field expects it, such as "cp 25" or "mate -1"
+///
+/// Mates are reported in moves rather than plies, rounding up for the side delivering mate.
+fn format_score(score: i64) -> String {
+    match mate_distance(score) {
+        Some(plies) if plies >= 0 => format!("mate {}", ((plies + 1) / 2).max(1)),
+        Some(plies) => format!("mate -{}", (-plies / 2).max(1)),
+        None => format!("cp {score}"),
+    }
+}
+
+/// Returns how many plies less than the full depth a move should be searched to
+///
+/// Moves past the first few are unlikely to be best in a well-ordered list, so they are searched
+/// shallower. Killers and countermoves have refuted similar positions before and are reduced
+/// less than other quiet moves, while captures and promotions are never reduced. When the side
+/// to move is not improving it may be in trouble, so its moves are reduced less than when it is.
+///
+/// # Arguments
+///
+/// * `depthleft` - The depth left to search at the node
+/// * `move_idx` - The position of the move in the node's move order
+/// * `tag` - Why the move was ordered where it is
+/// * `improving` - Whether the static evaluation improved since the side's previous turn
+///
+/// # Returns
+///
+/// * `usize` - The number of plies to reduce the search of the move by
+///
+/// # Example
+/// ```
+/// assert_eq!(late_move_reduction(6, 10, MoveTag::Quiet, false), 2);
+/// assert_eq!(late_move_reduction(6, 10, MoveTag::Killer, false), 1);
+/// ```
+fn late_move_reduction(depthleft: usize, move_idx: usize, tag: MoveTag, improving: bool) -> usize {
+    if depthleft < LMR_MIN_DEPTH || move_idx < LMR_FULL_DEPTH_MOVES || !tag.is_quiet() {
+        return 0;
+    }
+
+    // Always leave at least one ply to search
+    let reduction =
+        (1 + usize::from(depthleft >= 6) + usize::from(move_idx >= 12) + usize::from(improving))
+            .min(depthleft - 2);
+
+    match tag {
+        MoveTag::Killer | MoveTag::Countermove => reduction - 1,
+        MoveTag::Quiet | MoveTag::HashMove | MoveTag::Tactical | MoveTag::LosingCapture => {
+            reduction
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use super::*;
+    use crate::board::square::Square;
+    use crate::board::BoardBuilder;
+    use crate::evaluate::simple_evaluator::SimpleEvaluator;
+    use crate::search::logger::{BufferLogger, NullLogger};
+    use std::thread;
+    use std::time::Duration;
+    use test::Bencher;
+
+    #[test]
+    fn test_get_best_move() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        assert!(search.get_best_move().is_none());
+        search.search(Some(3));
+        let best_move = search.get_best_move();
+        assert!(best_move.is_some());
+    }
+
+    #[test]
+    fn test_get_running() {
+        let board = BoardBuilder::construct_starting_board().build();
         let evaluator = SimpleEvaluator::new();
         let search = Search::new(&board, &evaluator, None);
         assert!(search.check_running());
@@ -295,11 +1371,6 @@ mod tests {
         let evaluator = SimpleEvaluator::new();
         let mut search = Search::new(&board, &evaluator, None);
         assert!(!search.check_limits());
-        search.limits.depth = Some(3);
-        assert!(!search.check_limits());
-        search.depth = 3;
-        assert!(search.check_limits());
-        search.limits.depth = None;
         search.limits.nodes = Some(100);
         assert!(!search.check_limits());
         search.nodes = 100;
@@ -312,27 +1383,906 @@ mod tests {
     }
 
     #[test]
-    fn test_alpha_beta() {
+    fn test_max_depth() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let max_depth =
+            |limits: SearchLimits| Search::new(&board, &evaluator, Some(limits)).max_depth();
+
+        assert_eq!(max_depth(SearchLimits::new()), DEFAULT_DEPTH);
+        assert_eq!(max_depth(SearchLimits::new().depth(Some(9))), 9);
+        assert_eq!(max_depth(SearchLimits::new().depth(Some(0))), 1);
+        assert_eq!(max_depth(SearchLimits::new().depth(Some(1_000))), MAX_PLY);
+        assert_eq!(max_depth(SearchLimits::new().nodes(Some(100))), MAX_PLY);
+        assert_eq!(max_depth(SearchLimits::new().movetime(Some(100))), MAX_PLY);
+        assert_eq!(
+            max_depth(SearchLimits::new().white_time(Some(100))),
+            MAX_PLY
+        );
+        assert_eq!(
+            max_depth(SearchLimits::new().black_time(Some(100))),
+            DEFAULT_DEPTH
+        );
+        assert_eq!(
+            max_depth(SearchLimits::new().depth(Some(4)).movetime(Some(50))),
+            4
+        );
+        assert_eq!(
+            max_depth(SearchLimits::new().depth(Some(4)).infinite(true)),
+            MAX_PLY
+        );
+    }
+
+    #[test]
+    fn test_time_management_timer() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new().white_time(Some(10_000));
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+        assert_eq!(search.time_management_timer, Some(500));
+        assert!(!search.check_limits());
+        search.movetime = 500;
+        assert!(search.check_limits());
+    }
+
+    #[test]
+    fn test_search_stays_within_a_small_black_clock() {
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new()
+            .white_time(Some(60_000))
+            .black_time(Some(100));
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+        search.logger = Box::new(NullLogger);
+        assert_eq!(search.hard_time_limit, Some(90));
+
+        let started = Instant::now();
+        let best_move = search.search(Some(64));
+
+        // The limit only bounds the search itself, so leave room for a loaded machine
+        assert!(best_move.is_some());
+        assert!(started.elapsed() < Duration::from_millis(2 * 90));
+    }
+
+    #[test]
+    fn test_ponderhit_starts_the_clock() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new().white_time(Some(4_000)).ponder(true);
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+        let pondering = search.get_pondering();
+
+        let handle = thread::spawn(move || search.search(Some(64)));
+
+        // Ponder for longer than the 200ms the move is allocated
+        thread::sleep(Duration::from_millis(300));
+        assert!(!handle.is_finished());
+
+        let ponderhit = Instant::now();
+        pondering.store(false, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        let elapsed = ponderhit.elapsed().as_millis();
+        assert!(elapsed >= 150, "search stopped {elapsed}ms after ponderhit");
+        assert!(
+            elapsed < 2_000,
+            "search stopped {elapsed}ms after ponderhit"
+        );
+    }
+
+    #[test]
+    fn test_ponderhit_continues_the_search() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new().white_time(Some(20_000)).ponder(true);
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+        search.set_logger(Box::new(logger.clone()));
+        let pondering = search.get_pondering();
+
+        let handle = thread::spawn(move || search.search(Some(64)));
+
+        thread::sleep(Duration::from_millis(300));
+        let pondered = logger
+            .lines()
+            .iter()
+            .filter(|line| line.starts_with("info depth "))
+            .count();
+        pondering.store(false, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        let depths: Vec<usize> = logger
+            .lines()
+            .iter()
+            .filter_map(|line| {
+                line.strip_prefix("info depth ")?
+                    .split(' ')
+                    .next()?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        assert!(pondered > 0);
+        assert!(depths.len() > pondered, "no iterations after ponderhit");
+        assert!(
+            depths.windows(2).all(|pair| pair[0] <= pair[1]),
+            "search restarted after ponderhit: {depths:?}"
+        );
+    }
+
+    #[test]
+    fn test_illegal_hash_move_is_ignored() {
         let board = BoardBuilder::construct_starting_board().build();
         let evaluator = SimpleEvaluator::new();
         let mut search = Search::new(&board, &evaluator, None);
-        let score = search.alpha_beta(i64::MIN, i64::MAX, 4);
-        assert_eq!(score, 0)
+        let poisoned = TTEntry {
+            key: board.zobrist_key(),
+            depth: 0,
+            score: NEGMAX,
+            bound: Bound::Lower,
+            best_ply: Some(Ply::new(Square::from("e1"), Square::from("e5"))),
+        };
+        search.transposition_table.write().unwrap().insert(poisoned);
+
+        assert_eq!(search.hash_move(Some(poisoned)), None);
+
+        let best_move = search.search(Some(2)).unwrap();
+        assert!(search.board.get_legal_moves().contains(&best_move));
     }
 
-    #[bench]
-    fn bench_search_depth_3(bencher: &mut Bencher) {
+    #[test]
+    fn test_quiet_moves_are_futile_far_below_alpha() {
         let board = BoardBuilder::construct_starting_board().build();
         let evaluator = SimpleEvaluator::new();
         let mut search = Search::new(&board, &evaluator, None);
-        bencher.iter(|| search.search(Some(3)));
+        search.info.reset(3);
+        search.info.static_evals[1] = Some(-500);
+
+        assert!(search.is_futile(0, 1, 1, false));
+        assert!(search.is_futile(0, 2, 1, false));
+        assert!(!search.is_futile(-350, 2, 1, false));
+        assert!(!search.is_futile(0, 3, 1, false));
+        assert!(!search.is_futile(0, 1, 2, false));
     }
 
-    #[bench]
-    fn bench_search_depth_4(bencher: &mut Bencher) {
+    #[test]
+    fn test_improving_nodes_have_tighter_futility_margins() {
         let board = BoardBuilder::construct_starting_board().build();
         let evaluator = SimpleEvaluator::new();
         let mut search = Search::new(&board, &evaluator, None);
-        bencher.iter(|| search.search(Some(4)));
+        search.info.reset(3);
+        search.info.static_evals[1] = Some(-500);
+
+        // Within the full margin of alpha, but outside the tightened one
+        assert!(!search.is_futile(-150, 2, 1, false));
+        assert!(search.is_futile(-150, 2, 1, true));
+        assert!(!search.is_futile(-350, 1, 1, false));
+        assert!(search.is_futile(-350, 1, 1, true));
+    }
+
+    #[test]
+    fn test_razoring_far_below_alpha() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.info.reset(3);
+        search.info.static_evals[1] = Some(-700);
+
+        assert!(search.can_razor(0, 1, 1));
+        assert!(search.can_razor(0, 2, 1));
+        assert!(!search.can_razor(-100, 2, 1));
+        assert!(!search.can_razor(0, 3, 1));
+        assert!(!search.can_razor(mate_in(5), 1, 1));
+        assert!(!search.can_razor(0, 1, 2));
+    }
+
+    #[test]
+    fn test_delta_pruning_keeps_the_quiescence_score() {
+        // Black is a queen and a rook down, so none of its pawn captures can get back to alpha
+        let board = Board::from_fen("4k3/8/8/3p1p2/2P1P1P1/8/8/QR2K3 b - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut pruned = Search::new(&board, &evaluator, None);
+        let mut unpruned = Search::new(&board, &evaluator, None);
+        unpruned.set_config(Config::new().delta_margin(None));
+
+        let pruned_score = pruned.quiescence(-100, 100);
+        let unpruned_score = unpruned.quiescence(-100, 100);
+
+        assert_eq!(pruned_score, unpruned_score);
+        assert!(pruned.nodes < unpruned.nodes);
+    }
+
+    #[test]
+    fn test_capture_gain_counts_promotions() {
+        let mut board = Board::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+        let promotion = board.find_move("a7b8q").unwrap();
+
+        assert_eq!(capture_gain(promotion), 500 + 900 - 100);
+    }
+
+    #[test]
+    fn test_killers_are_reduced_less_than_quiet_moves() {
+        for depthleft in LMR_MIN_DEPTH..10 {
+            for move_idx in LMR_FULL_DEPTH_MOVES..20 {
+                let quiet = late_move_reduction(depthleft, move_idx, MoveTag::Quiet, false);
+                let killer = late_move_reduction(depthleft, move_idx, MoveTag::Killer, false);
+                let countermove =
+                    late_move_reduction(depthleft, move_idx, MoveTag::Countermove, false);
+
+                assert!(quiet >= 1);
+                assert!(depthleft - 1 - killer > depthleft - 1 - quiet);
+                assert_eq!(killer, countermove);
+            }
+        }
+        assert_eq!(late_move_reduction(6, 10, MoveTag::Tactical, false), 0);
+        assert_eq!(late_move_reduction(6, 10, MoveTag::LosingCapture, false), 0);
+        assert_eq!(late_move_reduction(6, 1, MoveTag::Quiet, false), 0);
+        assert_eq!(late_move_reduction(2, 10, MoveTag::Quiet, false), 0);
+    }
+
+    #[test]
+    fn test_improving_nodes_are_reduced_more() {
+        let improving = late_move_reduction(6, 10, MoveTag::Quiet, true);
+        let not_improving = late_move_reduction(6, 10, MoveTag::Quiet, false);
+        assert!(improving > not_improving);
+    }
+
+    #[test]
+    fn test_static_evals_are_stored_along_the_line() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.info.reset(4);
+        search.alpha_beta(i64::MIN, i64::MAX, 3, 0);
+
+        assert_eq!(search.info.static_evals[0], Some(0));
+    }
+
+    #[test]
+    fn test_quiet_cutoffs_are_stored() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.search(Some(4));
+
+        assert!(search.info.killers.iter().flatten().any(Option::is_some));
+        assert!(search.info.countermoves.iter().any(Option::is_some));
+    }
+
+    #[test]
+    fn test_insufficient_material_is_a_draw() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        assert_eq!(search.alpha_beta(i64::MIN, i64::MAX, 3, 1), 0);
+    }
+
+    #[test]
+    fn test_invalid_killers_are_skipped() {
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let evaluator = SimpleEvaluator::new();
+
+        let mut clean = Search::new(&board, &evaluator, None);
+        clean.info.reset(3);
+        let expected = clean.alpha_beta(i64::MIN, i64::MAX, 3, 0);
+
+        // Neither move can be played here: one square is empty and the other holds a black piece
+        let stale = [
+            Some(Ply::new(Square::from("d4"), Square::from("d5"))),
+            Some(Ply::new(Square::from("c6"), Square::from("d4"))),
+        ];
+        let mut search = Search::new(&board, &evaluator, None);
+        search.info.reset(3);
+        search.info.killers[0] = stale;
+
+        assert_eq!(search.killers(0), [None, None]);
+        assert_eq!(search.alpha_beta(i64::MIN, i64::MAX, 3, 0), expected);
+    }
+
+    #[test]
+    fn test_repetition_draw_count() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K1N1 w - - 0 1");
+        for notation in ["g1f3", "e8d8", "f3g1", "d8e8"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+        }
+        assert_eq!(board.repetition_count(), 1);
+        let evaluator = SimpleEvaluator::new();
+
+        let mut search = Search::new(&board, &evaluator, None);
+        assert_ne!(search.alpha_beta(i64::MIN, i64::MAX, 1, 1), 0);
+
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_config(Config::new().repetition_draw_count(1));
+        assert_eq!(search.alpha_beta(i64::MIN, i64::MAX, 1, 1), 0);
+    }
+
+    #[test]
+    fn test_contempt_avoids_repetition() {
+        // Black is a rook for a knight ahead, and only Nf3 repeats the position
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/r7/4K1N1 w - - 0 1");
+        for notation in ["g1f3", "e8d8", "f3g1", "d8e8"] {
+            let mv = board.find_move(notation).unwrap();
+            board.make_move(mv);
+        }
+        let repetition = board.find_move("g1f3").unwrap();
+        let evaluator = SimpleEvaluator::new();
+
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_config(Config::new().repetition_draw_count(1));
+        assert_eq!(search.search(Some(3)), Some(repetition));
+
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_config(Config::new().repetition_draw_count(1).contempt(1_000));
+        assert_ne!(search.search(Some(3)), Some(repetition));
+    }
+
+    #[test]
+    fn test_draw_score_flips_with_ply() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_config(Config::new().contempt(50));
+        assert_eq!(search.draw_score(0), -50);
+        assert_eq!(search.draw_score(1), 50);
+        assert_eq!(search.draw_score(4), -50);
+    }
+
+    #[test]
+    fn test_alpha_beta_scores_mirrored_positions_alike() {
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let mirrored =
+            Board::from_fen("rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3");
+        let evaluator = SimpleEvaluator::new();
+
+        let mut search = Search::new(&board, &evaluator, None);
+        let score = search.alpha_beta(i64::MIN, i64::MAX, 2, 0);
+        let mut search = Search::new(&mirrored, &evaluator, None);
+
+        assert_eq!(search.alpha_beta(i64::MIN, i64::MAX, 2, 0), score);
+    }
+
+    #[test]
+    fn test_alpha_beta_matches_root_search() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+
+        let mut search = Search::new(&board, &evaluator, None);
+        let score = search.alpha_beta(i64::MIN, i64::MAX, 4, 0);
+        let mut search = Search::new(&board, &evaluator, None);
+        let (_, root_score) = search.alpha_beta_start(4, i64::MIN, i64::MAX, &[]);
+
+        assert_eq!(score, root_score);
+    }
+
+    #[test]
+    fn test_pv_reaches_search_depth() {
+        let board = Board::from_fen("7k/8/5Q2/8/8/8/8/K7 b - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        let best_move = search.search(Some(3)).unwrap();
+
+        // The root is in check, so its evasions are searched a ply deeper
+        let pv = search.get_pv().to_vec();
+        assert_eq!(pv.len(), 4);
+        assert_eq!(pv[0], best_move);
+
+        let mut board = board;
+        for mv in pv {
+            assert!(board.get_legal_moves().contains(&mv));
+            board.make_move(mv);
+        }
+    }
+
+    #[test]
+    fn test_aspiration_window_matches_full_width_search() {
+        // Shallow enough that no move is reduced, so the score does not depend on move ordering
+        let board =
+            Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3");
+        let evaluator = SimpleEvaluator::new();
+
+        let mut full_width = Search::new(&board, &evaluator, None);
+        full_width.info.reset(3);
+        let (_, full_width_score) = full_width.alpha_beta_start(3, i64::MIN, i64::MAX, &[]);
+
+        // Previous scores far from the truth force the window to fail low and high
+        for previous in [-1_000, 0, 1_000] {
+            let mut aspiration = Search::new(&board, &evaluator, None);
+            aspiration.info.reset(3);
+            aspiration.info.score = Some(previous);
+            let (_, score) = aspiration.aspiration_search(3, Instant::now());
+
+            assert_eq!(score, full_width_score, "previous score {previous}");
+        }
+    }
+
+    #[test]
+    fn test_aspiration_falls_back_to_full_width_after_capped_researches() {
+        // White can win the queen, so a previous score that expected equality keeps failing high
+        let board = Board::from_fen("3qk3/8/8/8/8/8/3R4/3RK3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+
+        let mut full_width = Search::new(&board, &evaluator, None);
+        full_width.info.reset(3);
+        let (_, full_width_score) = full_width.alpha_beta_start(3, i64::MIN, i64::MAX, &[]);
+
+        let mut search = Search::new(&board, &evaluator, None);
+        search.info.reset(3);
+        search.info.score = Some(-2_000);
+        let (_, score) = search.aspiration_search(3, Instant::now());
+
+        assert_eq!(score, full_width_score);
+        assert_eq!(
+            search.info.aspiration_stats[3],
+            AspirationStats {
+                fail_highs: ASPIRATION_MAX_RESEARCHES,
+                fail_lows: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_aspiration_fail_high_reports_lowerbound() {
+        let board = Board::from_fen("3qk3/8/8/8/8/8/3R4/3RK3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+        search.info.reset(3);
+        search.info.score = Some(-2_000);
+        search.last_info = Instant::now().checked_sub(Duration::from_secs(1)).unwrap();
+
+        search.aspiration_search(3, Instant::now());
+
+        // Every failure is counted, but at most one is reported this soon after the last line
+        let stats = search.info.aspiration_stats[3];
+        assert_eq!(stats.fail_highs, ASPIRATION_MAX_RESEARCHES);
+        let lines: Vec<String> = logger
+            .lines()
+            .iter()
+            .filter(|line| line.starts_with("info depth"))
+            .cloned()
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("info depth 3 time "), "{}", lines[0]);
+        assert!(lines[0].ends_with(" lowerbound"), "{}", lines[0]);
+        assert!(lines[0].contains(" score cp "), "{}", lines[0]);
+    }
+
+    #[test]
+    fn test_search_records_checkmate() {
+        let board = Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+
+        let best_move = search.search(Some(3));
+        search.report_best_move(best_move);
+
+        assert_eq!(best_move, None);
+        assert_eq!(search.get_game_result(), Some(GameState::CheckmateBlack));
+        assert_eq!(
+            logger.lines(),
+            vec![
+                "info depth 0 score mate 0".to_string(),
+                "info string checkmate".to_string(),
+                "bestmove (none)".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_records_stalemate() {
+        let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+
+        let best_move = search.search(Some(3));
+        search.report_best_move(best_move);
+
+        assert_eq!(best_move, None);
+        assert_eq!(search.get_game_result(), Some(GameState::Stalemate));
+        assert_eq!(
+            logger.lines(),
+            vec![
+                "info depth 0 score cp 0".to_string(),
+                "info string stalemate".to_string(),
+                "bestmove (none)".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_currmove_is_reported_during_long_iterations() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+        search.info.reset(2);
+        search.searching_since = Instant::now().checked_sub(Duration::from_secs(2)).unwrap();
+        search.last_info = Instant::now().checked_sub(Duration::from_secs(1)).unwrap();
+
+        search.alpha_beta_start(2, NEGMAX, i64::MAX, &[]);
+
+        let lines = logger.lines();
+        let mut board = board;
+        let legal_moves: Vec<String> = board
+            .get_legal_moves()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let currmove_lines: Vec<&String> = lines
+            .iter()
+            .filter(|line| line.starts_with("info currmove "))
+            .collect();
+        assert_eq!(currmove_lines.len(), 1);
+
+        let tokens: Vec<&str> = currmove_lines[0].split_whitespace().collect();
+        assert_eq!(tokens.len(), 5);
+        assert_eq!(tokens[..2], ["info", "currmove"]);
+        assert!(
+            legal_moves.iter().any(|mv| mv == tokens[2]),
+            "{}",
+            tokens[2]
+        );
+        assert_eq!(tokens[3], "currmovenumber");
+        assert_eq!(tokens[4], "1");
+    }
+
+    #[test]
+    fn test_currmove_is_silent_early_in_an_iteration() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+        search.info.reset(2);
+        search.last_info = Instant::now().checked_sub(Duration::from_secs(1)).unwrap();
+
+        search.alpha_beta_start(2, NEGMAX, i64::MAX, &[]);
+
+        assert!(logger.lines().is_empty());
+    }
+
+    #[test]
+    fn test_check_extensions_stay_within_node_budget() {
+        // Both queens can check the exposed kings over and over
+        let board = Board::from_fen("6k1/6p1/8/8/8/8/1q6/1R1Q2K1 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(NullLogger));
+
+        search.search(Some(6));
+
+        assert!(search.get_nodes() < 1_000_000, "{}", search.get_nodes());
+    }
+
+    #[test]
+    fn test_info_output_is_rate_limited() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+
+        let start = Instant::now();
+        search.search(Some(6));
+        let elapsed = start.elapsed().as_millis();
+
+        let lines = logger.lines();
+        let depth_lines = lines.iter().filter(|line| line.contains(" pv ")).count();
+        let other_lines = lines.len() - depth_lines;
+        assert_eq!(depth_lines, 6);
+        assert!(
+            other_lines as u128 <= elapsed / INFO_INTERVAL_MS,
+            "{other_lines} lines in {elapsed}ms"
+        );
+    }
+
+    #[test]
+    fn test_aspiration_falls_back_to_full_width_near_mate() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+
+        let best_move = search.search(Some(3)).unwrap();
+
+        assert_eq!(best_move.to_string(), "a1a8");
+        assert_eq!(search.info.score, Some(mate_in(1)));
+    }
+
+    #[test]
+    fn test_reports_shortest_mate_distance() {
+        // The rooks box the king in on the h-file, such as 1. Rg2 Kh4 2. Rb7 Kh3 3. Rh7#
+        let board = Board::from_fen("8/8/8/7k/8/8/R7/1R4K1 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_logger(Box::new(logger.clone()));
+
+        search.search(Some(7));
+
+        let lines = logger.lines();
+        let last = lines.last().unwrap();
+        assert!(last.starts_with("info depth 7 "), "{last}");
+        assert!(last.contains(" score mate 3 "), "{last}");
+    }
+
+    #[test]
+    fn test_is_mate_score_boundaries() {
+        let range = usize::try_from(MATE_PLY_RANGE).unwrap();
+
+        assert!(is_mate_score(mate_in(0)));
+        assert!(is_mate_score(mate_in(range)));
+        assert!(!is_mate_score(mate_in(range + 1)));
+        assert!(is_mate_score(mated_in(0)));
+        assert!(is_mate_score(mated_in(range)));
+        assert!(!is_mate_score(mated_in(range + 1)));
+
+        assert!(!is_mate_score(DRAW));
+        assert!(!is_mate_score(MAX_EVAL));
+        assert!(!is_mate_score(-MAX_EVAL));
+    }
+
+    #[test]
+    fn test_mate_distance_boundaries() {
+        assert_eq!(mate_distance(mate_in(1)), Some(1));
+        assert_eq!(mate_distance(mate_in(2)), Some(2));
+        assert_eq!(mate_distance(mated_in(1)), Some(-1));
+        assert_eq!(mate_distance(mated_in(2)), Some(-2));
+        assert_eq!(mate_distance(DRAW), None);
+        assert_eq!(mate_in(1), mated_in(1).saturating_neg());
+
+        assert_eq!(format_score(mate_in(1)), "mate 1");
+        assert_eq!(format_score(mate_in(2)), "mate 1");
+        assert_eq!(format_score(mate_in(3)), "mate 2");
+        assert_eq!(format_score(mated_in(2)), "mate -1");
+        assert_eq!(format_score(mated_in(3)), "mate -1");
+    }
+
+    #[test]
+    fn test_mate_scores_survive_the_transposition_table() {
+        let mate_in_two = mate_in(3);
+        let stored = score_to_tt(mate_in_two, 2);
+
+        assert_eq!(score_from_tt(stored, 2), mate_in_two);
+        assert_eq!(format_score(score_from_tt(stored, 0)), "mate 1");
+        assert_eq!(format_score(mated_in(4)), "mate -2");
+        assert_eq!(score_to_tt(25, 7), 25);
+    }
+
+    #[test]
+    fn test_searchmoves_restricts_root_moves() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let searchmoves = vec![
+            board.find_move("a2a3").unwrap(),
+            board.find_move("h2h3").unwrap(),
+        ];
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new().searchmoves(searchmoves.clone());
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+
+        let best_move = search.search(Some(2)).unwrap();
+
+        assert!(searchmoves.contains(&best_move));
+    }
+
+    #[test]
+    fn test_illegal_searchmoves_fall_back_to_every_move() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let illegal = Ply::new(Square::from("e2"), Square::from("e5"));
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new().searchmoves(vec![illegal]);
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+
+        let best_move = search.search(Some(2)).unwrap();
+
+        assert_ne!(best_move, illegal);
+        assert!(search.limits.searchmoves.is_empty());
+    }
+
+    #[test]
+    fn test_multipv_reports_distinct_root_moves() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_config(Config::new().multipv(3));
+
+        let best_move = search.search(Some(3)).unwrap();
+        let lines = search.get_lines();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].pv[0], best_move);
+        assert_ne!(lines[0].pv[0], lines[1].pv[0]);
+        assert_ne!(lines[0].pv[0], lines[2].pv[0]);
+        assert_ne!(lines[1].pv[0], lines[2].pv[0]);
+        assert!(lines.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn test_multipv_is_limited_by_legal_moves() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        search.set_config(Config::new().multipv(5));
+
+        search.search(Some(2));
+
+        assert_eq!(search.get_lines().len(), 3);
+    }
+
+    #[test]
+    fn test_single_legal_move_is_played_without_a_deep_search() {
+        // The king is in check and capturing the rook on b2 is the only way out
+        let board = Board::from_fen("k7/8/8/8/8/8/1r6/K6r w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+
+        let best_move = search.search(None).unwrap();
+
+        assert_eq!(best_move.to_string(), "a1b2");
+        assert!(search.nodes < 100, "searched {} nodes", search.nodes);
+    }
+
+    /// Returns the position after black's bishop takes the knight on d5, which the rook on d1
+    /// can take back
+    fn bishop_takes_defended_knight() -> Board {
+        let mut board = Board::from_fen("b3k3/8/8/3N4/8/8/8/3RK3 b - - 0 1");
+        let capture = board.find_move("a8d5").unwrap();
+        board.make_move(capture);
+
+        board
+    }
+
+    #[test]
+    fn test_obvious_recapture_is_played_in_time_trouble() {
+        let board = bishop_takes_defended_knight();
+        let evaluator = SimpleEvaluator::new();
+        let limits = SearchLimits::new().white_time(Some(50));
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+
+        let best_move = search.search(None).unwrap();
+
+        assert_eq!(best_move.to_string(), "d1d5");
+        assert!(search.nodes < 100, "searched {} nodes", search.nodes);
+    }
+
+    #[test]
+    fn test_obvious_recapture_needs_time_trouble() {
+        let board = bishop_takes_defended_knight();
+        let evaluator = SimpleEvaluator::new();
+        let mut relaxed = Search::new(&board, &evaluator, None);
+        let limits = SearchLimits::new().white_time(Some(60_000));
+        let mut plenty = Search::new(&board, &evaluator, Some(limits));
+
+        assert_eq!(relaxed.obvious_recapture(), None);
+        assert_eq!(plenty.obvious_recapture(), None);
+    }
+
+    #[test]
+    fn test_root_in_check_finds_the_only_safe_evasion() {
+        // The king must step to d1, since Kf1 walks into Qh1#
+        let board = Board::from_fen("4r1k1/8/8/8/7q/8/3P1P2/4K3 w - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+
+        for depth in 2..=3 {
+            let mut search = Search::new(&board, &evaluator, None);
+            assert_eq!(
+                search.search(Some(depth)).unwrap().to_string(),
+                "e1d1",
+                "depth {depth}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mate_limit_stops_once_mate_is_found() {
+        // 1. Nf6+ gxf6 2. Bxf7#
+        let board =
+            Board::from_fen("r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 1");
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let limits = SearchLimits::new().mate(Some(5));
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+        search.set_logger(Box::new(logger.clone()));
+
+        assert_eq!(search.max_depth(), 9);
+        assert_eq!(search.search(None).unwrap().to_string(), "d5f6");
+        assert!(search.found_requested_mate());
+        assert!(logger
+            .lines()
+            .iter()
+            .all(|line| !line.starts_with("info depth 9 ")));
+    }
+
+    #[test]
+    fn test_mate_limit_reports_no_mate() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let logger = BufferLogger::default();
+        let limits = SearchLimits::new().mate(Some(1));
+        let mut search = Search::new(&board, &evaluator, Some(limits));
+        search.set_logger(Box::new(logger.clone()));
+
+        assert_eq!(search.max_depth(), 1);
+        assert!(search.search(None).is_some());
+        assert!(!search.found_requested_mate());
+        assert_eq!(
+            logger.lines().last().map(String::as_str),
+            Some("info string no mate in 1 found")
+        );
+    }
+
+    #[test]
+    fn test_mate_in_2() {
+        // 1. Nf6+ gxf6 2. Bxf7#
+        let board =
+            Board::from_fen("r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        let best_move = search.search(Some(4)).unwrap();
+
+        // Razoring the hopeless replies near the leaves must not hide the mate
+        assert_eq!(best_move.to_string(), "d5f6");
+        assert_eq!(search.info.score, Some(mate_in(3)));
+    }
+
+    #[test]
+    fn test_quiescence_resolves_hanging_capture() {
+        // Taking the rook loses the queen to the king, which a depth one search only sees
+        // through quiescence
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 b - - 0 1");
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        let best_move = search.search(Some(1)).unwrap();
+
+        assert_ne!(best_move.dest, Square::from("d2"));
+    }
+
+    #[test]
+    fn test_qsearch_see_threshold_examines_more_captures() {
+        let board = Board::from_fen(
+            "r1bqkb1r/pp1n1ppp/2n1p3/2ppP3/3P1P2/2N1BN2/PPP3PP/R2QKB1R b KQkq - 0 1",
+        );
+        let evaluator = SimpleEvaluator::new();
+
+        let mut default_search = Search::new(&board, &evaluator, None);
+        default_search.search(Some(2));
+
+        let mut permissive_search = Search::new(&board, &evaluator, None);
+        permissive_search.set_config(Config::new().qsearch_see_threshold(-10_000));
+        permissive_search.search(Some(2));
+
+        assert!(permissive_search.nodes > default_search.nodes);
+    }
+
+    #[bench]
+    fn bench_search_depth_3(bencher: &mut Bencher) {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        bencher.iter(|| search.search(Some(3)));
+    }
+
+    #[bench]
+    fn bench_search_depth_4(bencher: &mut Bencher) {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        bencher.iter(|| search.search(Some(4)));
+    }
+
+    #[bench]
+    fn bench_search_depth_5(bencher: &mut Bencher) {
+        let board = BoardBuilder::construct_starting_board().build();
+        let evaluator = SimpleEvaluator::new();
+        let mut search = Search::new(&board, &evaluator, None);
+        bencher.iter(|| search.search(Some(5)));
     }
 }
@@ -0,0 +1,59 @@
+/// Tunable parameters of the search
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Captures in quiescence search with a static exchange evaluation below this are skipped
+    pub qsearch_see_threshold: i64,
+    /// The number of best root moves to report a principal variation for
+    pub multipv: usize,
+    /// The number of earlier occurrences of a position after which the search scores it as a draw
+    pub repetition_draw_count: u8,
+    /// Captures in quiescence search are skipped if winning the captured piece plus this margin
+    /// still leaves the side to move below alpha, or none to search every capture
+    pub delta_margin: Option<i64>,
+    /// How many centipawns the side to move at the root considers a draw worse than an even
+    /// position, so that a positive value makes it avoid draws and a negative value seek them
+    pub contempt: i64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            qsearch_see_threshold: 0,
+            multipv: 1,
+            repetition_draw_count: 2,
+            delta_margin: Some(200),
+            contempt: 0,
+        }
+    }
+
+    pub const fn qsearch_see_threshold(mut self, qsearch_see_threshold: i64) -> Self {
+        self.qsearch_see_threshold = qsearch_see_threshold;
+        self
+    }
+
+    pub const fn multipv(mut self, multipv: usize) -> Self {
+        self.multipv = multipv;
+        self
+    }
+
+    pub const fn repetition_draw_count(mut self, repetition_draw_count: u8) -> Self {
+        self.repetition_draw_count = repetition_draw_count;
+        self
+    }
+
+    pub const fn delta_margin(mut self, delta_margin: Option<i64>) -> Self {
+        self.delta_margin = delta_margin;
+        self
+    }
+
+    pub const fn contempt(mut self, contempt: i64) -> Self {
+        self.contempt = contempt;
+        self
+    }
+}
@@ -0,0 +1,265 @@
+use crate::board::Ply;
+
+/// The principal variation found for one of the best moves at the root
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub score: i64,
+    pub pv: Vec<Ply>,
+}
+
+/// How often the aspiration window failed while searching the root to one depth
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AspirationStats {
+    /// The number of times the score was at or above the window
+    pub fail_highs: u32,
+    /// The number of times the score was at or below the window
+    pub fail_lows: u32,
+}
+
+/// What the search has learned about the tree it is exploring
+///
+/// Tables indexed by `ply` hold one entry per distance from the root and grow as the search
+/// reaches deeper nodes.
+pub struct Info {
+    /// The principal variation found below each node of the current line
+    pub pv_table: Vec<Vec<Ply>>,
+    /// The last two quiet moves that caused a beta cutoff at each distance from the root
+    pub killers: Vec<[Option<Ply>; 2]>,
+    /// The last quiet move that refuted each move, indexed by `countermove_index`
+    pub countermoves: Vec<Option<Ply>>,
+    /// The static evaluation of each node of the current line, or `None` while in check
+    pub static_evals: Vec<Option<i64>>,
+    /// The score of the last completed iteration of iterative deepening
+    pub score: Option<i64>,
+    /// The best lines of the last completed iteration, best first
+    pub lines: Vec<Line>,
+    /// The aspiration window failures of each iteration, indexed by depth
+    pub aspiration_stats: Vec<AspirationStats>,
+    /// The number of plies the current line has been extended by, up to and including each node
+    pub extensions: Vec<usize>,
+    /// The most plies a single line may be extended by in the current iteration
+    pub extension_budget: usize,
+}
+
+impl Default for Info {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Info {
+    pub fn new() -> Self {
+        Self {
+            pv_table: Vec::new(),
+            killers: Vec::new(),
+            countermoves: vec![None; 64 * 64],
+            static_evals: Vec::new(),
+            score: None,
+            lines: Vec::new(),
+            aspiration_stats: Vec::new(),
+            extensions: Vec::new(),
+            extension_budget: 0,
+        }
+    }
+
+    /// Forgets everything learned by a previous search
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The depth of the upcoming search
+    pub fn reset(&mut self, depth: usize) {
+        self.pv_table = vec![Vec::new(); depth + 1];
+        self.killers = vec![[None; 2]; depth + 1];
+        self.countermoves.fill(None);
+        self.static_evals = vec![None; depth + 1];
+        self.score = None;
+        self.lines.clear();
+        self.aspiration_stats = vec![AspirationStats::default(); depth + 1];
+        self.extensions = vec![0; depth + 1];
+        self.extension_budget = depth;
+    }
+
+    /// Prepares the tables for a node at `ply`, growing them if the search has gone deeper than
+    /// expected
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The distance from the root of the node being entered
+    pub fn enter_node(&mut self, ply: usize) {
+        if ply >= self.pv_table.len() {
+            self.pv_table.resize(ply + 1, Vec::new());
+            self.killers.resize(ply + 1, [None; 2]);
+            self.static_evals.resize(ply + 1, None);
+            self.extensions.resize(ply + 1, 0);
+        }
+        self.pv_table[ply].clear();
+        self.static_evals[ply] = None;
+    }
+
+    /// Stores `mv` followed by the principal variation of the child node as the line for `ply`
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The distance from the root of the node that found a new best move
+    /// * `mv` - The new best move at that node
+    pub fn update_pv(&mut self, ply: usize, mv: Ply) {
+        let (parents, children) = self.pv_table.split_at_mut(ply + 1);
+        let line = &mut parents[ply];
+        line.clear();
+        line.push(mv);
+        if let Some(child_line) = children.first() {
+            line.extend_from_slice(child_line);
+        }
+    }
+
+    /// Remembers a quiet move that caused a beta cutoff, both as a killer for its distance from
+    /// the root and as the countermove to the opponent's previous move
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The distance from the root of the node where the cutoff happened
+    /// * `mv` - The move that caused the cutoff
+    /// * `previous` - The move that led to the node, if there was one
+    pub fn store_quiet_cutoff(&mut self, ply: usize, mv: Ply, previous: Option<Ply>) {
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(mv) {
+            killers[1] = killers[0];
+            killers[0] = Some(mv);
+        }
+
+        if let Some(previous) = previous {
+            self.countermoves[countermove_index(previous)] = Some(mv);
+        }
+    }
+
+    /// Returns how many plies to extend the node at `ply` by, and records the extensions of the
+    /// line through it for its children
+    ///
+    /// A node in check is extended by one ply until the line has used up `extension_budget`, so
+    /// that long sequences of checks cannot grow the tree without bound.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The distance from the root of the node
+    /// * `in_check` - Whether the side to move at the node is in check
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of plies to add to the depth of the node's children
+    ///
+    /// # Example
+    /// ```
+    /// let mut info = Info::new();
+    /// info.reset(1);
+    /// assert_eq!(info.check_extension(0, true), 1);
+    /// assert_eq!(info.check_extension(1, true), 0);
+    /// ```
+    pub fn check_extension(&mut self, ply: usize, in_check: bool) -> usize {
+        let used = ply
+            .checked_sub(1)
+            .map_or(0, |parent| self.extensions[parent]);
+        let extension = usize::from(in_check && used < self.extension_budget);
+        self.extensions[ply] = used + extension;
+
+        extension
+    }
+
+    /// Returns the countermove stored as the reply to `previous`
+    pub fn countermove(&self, previous: Option<Ply>) -> Option<Ply> {
+        previous.and_then(|previous| self.countermoves[countermove_index(previous)])
+    }
+
+    /// Returns whether the side to move at `ply` is doing better than it was on its previous
+    /// turn, two plies earlier
+    ///
+    /// Positions where either side was in check have no static evaluation and never count as
+    /// improving.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The distance from the root of the node
+    ///
+    /// # Example
+    /// ```
+    /// let mut info = Info::new();
+    /// info.static_evals = vec![Some(0), Some(-20), Some(50)];
+    /// assert!(info.is_improving(2));
+    /// ```
+    pub fn is_improving(&self, ply: usize) -> bool {
+        if ply < 2 {
+            return false;
+        }
+
+        match (self.static_evals.get(ply), self.static_evals.get(ply - 2)) {
+            (Some(Some(current)), Some(Some(previous))) => current > previous,
+            _ => false,
+        }
+    }
+}
+
+/// Returns the index of the countermove table entry for replies to `previous`
+const fn countermove_index(previous: Ply) -> usize {
+    previous.start.index() as usize * 64 + previous.dest.index() as usize
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::square::Square;
+
+    #[test]
+    fn test_is_improving() {
+        let mut info = Info::new();
+        info.static_evals = vec![Some(10), Some(-40), Some(35), Some(-60), Some(20)];
+
+        assert!(!info.is_improving(0));
+        assert!(!info.is_improving(1));
+        assert!(info.is_improving(2));
+        assert!(!info.is_improving(3));
+        assert!(!info.is_improving(4));
+    }
+
+    #[test]
+    fn test_is_not_improving_after_check() {
+        let mut info = Info::new();
+        info.static_evals = vec![None, Some(0), Some(100), Some(50)];
+
+        assert!(!info.is_improving(2));
+        assert!(info.is_improving(3));
+    }
+
+    #[test]
+    fn test_check_extension_is_bounded_by_budget() {
+        let mut info = Info::new();
+        info.reset(3);
+        info.extension_budget = 2;
+
+        assert_eq!(info.check_extension(0, true), 1);
+        assert_eq!(info.check_extension(1, false), 0);
+        assert_eq!(info.check_extension(2, true), 1);
+        assert_eq!(info.check_extension(3, true), 0);
+        assert_eq!(info.extensions[..4], [1, 1, 2, 2]);
+
+        // A sibling line starts again from its parent's count
+        assert_eq!(info.check_extension(2, false), 0);
+        assert_eq!(info.extensions[2], 1);
+    }
+
+    #[test]
+    fn test_countermove_is_stored_and_reset() {
+        let previous = Ply::new(Square::from("e7"), Square::from("e5"));
+        let reply = Ply::new(Square::from("g1"), Square::from("f3"));
+        let mut info = Info::new();
+        info.reset(3);
+
+        info.store_quiet_cutoff(2, reply, Some(previous));
+        assert_eq!(info.countermove(Some(previous)), Some(reply));
+        assert_eq!(info.countermove(Some(reply)), None);
+        assert_eq!(info.countermove(None), None);
+
+        info.reset(3);
+        assert_eq!(info.countermove(Some(previous)), None);
+    }
+}
@@ -1,3 +1,19 @@
+use crate::board::piece::Color;
+use crate::board::Ply;
+
+/// The share of the remaining clock time allocated to a single move
+const TIME_DIVISOR: u64 = 20;
+/// The time, in milliseconds, kept back from the clock for the GUI and the connection to pass on
+/// a move
+pub const DEFAULT_MOVE_OVERHEAD: u64 = 10;
+
+/// The limits a `go` command puts on a search
+///
+/// The search stops as soon as any of them is reached. `depth` caps how deep the iterations go,
+/// while `nodes`, `movetime`, and the clock budget can also cut an iteration short, in which case
+/// the last completed iteration decides the move. `mate` ends the search as soon as a short
+/// enough mate is proven, and otherwise limits the depth to the plies such a mate takes unless
+/// `depth` is also given. `infinite` overrides every other limit.
 #[allow(clippy::module_name_repetitions)]
 pub struct SearchLimits {
     pub depth: Option<u64>,
@@ -7,6 +23,17 @@ pub struct SearchLimits {
     pub black_time: Option<u64>,
     pub white_increment: Option<u64>,
     pub black_increment: Option<u64>,
+    /// The number of moves until the next time control
+    pub movestogo: Option<u64>,
+    pub ponder: bool,
+    /// The only root moves to consider, or every legal move when empty
+    pub searchmoves: Vec<Ply>,
+    /// The time, in milliseconds, the search must leave on the clock
+    pub move_overhead: u64,
+    /// Whether to keep searching until stopped, ignoring every other limit
+    pub infinite: bool,
+    /// The number of moves to look for a mate in
+    pub mate: Option<u64>,
 }
 
 impl Default for SearchLimits {
@@ -25,6 +52,12 @@ impl SearchLimits {
             black_time: None,
             white_increment: None,
             black_increment: None,
+            movestogo: None,
+            ponder: false,
+            searchmoves: Vec::new(),
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+            infinite: false,
+            mate: None,
         }
     }
 
@@ -62,4 +95,158 @@ impl SearchLimits {
         self.black_increment = black_increment;
         self
     }
+
+    pub const fn ponder(mut self, ponder: bool) -> Self {
+        self.ponder = ponder;
+        self
+    }
+
+    pub fn searchmoves(mut self, searchmoves: Vec<Ply>) -> Self {
+        self.searchmoves = searchmoves;
+        self
+    }
+
+    pub const fn move_overhead(mut self, move_overhead: u64) -> Self {
+        self.move_overhead = move_overhead;
+        self
+    }
+
+    pub const fn movestogo(mut self, movestogo: Option<u64>) -> Self {
+        self.movestogo = movestogo;
+        self
+    }
+
+    pub const fn infinite(mut self, infinite: bool) -> Self {
+        self.infinite = infinite;
+        self
+    }
+
+    pub const fn mate(mut self, mate: Option<u64>) -> Self {
+        self.mate = mate;
+        self
+    }
+
+    /// Returns how long the side to move should spend on this move based on its clock
+    ///
+    /// The remaining time is shared evenly between the moves left until the next time control,
+    /// or `TIME_DIVISOR` moves if that is unknown, and half of the increment is added on top. The
+    /// budget never exceeds `hard_time_limit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The side to move
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>` - The time to spend in milliseconds, or `None` if no clock was given for
+    ///   `color` or the search is infinite
+    ///
+    /// # Example
+    /// ```
+    /// let limits = SearchLimits::new().white_time(Some(10_000));
+    /// assert_eq!(limits.time_budget(Color::White), Some(500));
+    /// ```
+    pub fn time_budget(&self, color: Color) -> Option<u64> {
+        let (time, increment) = self.clock(color);
+        let hard_limit = self.hard_time_limit(color)?;
+        let moves_left = self.movestogo.unwrap_or(TIME_DIVISOR).max(1);
+
+        time.map(|time| (time / moves_left + increment.unwrap_or(0) / 2).min(hard_limit))
+    }
+
+    /// Returns the longest the side to move may search before it would risk losing on time
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The side to move
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>` - The remaining clock time less the move overhead in milliseconds, or
+    ///   `None` if no clock was given for `color` or the search is infinite
+    ///
+    /// # Example
+    /// ```
+    /// let limits = SearchLimits::new().black_time(Some(1_000)).move_overhead(50);
+    /// assert_eq!(limits.hard_time_limit(Color::Black), Some(950));
+    /// ```
+    pub fn hard_time_limit(&self, color: Color) -> Option<u64> {
+        if self.infinite {
+            return None;
+        }
+        let (time, _) = self.clock(color);
+
+        time.map(|time| time.saturating_sub(self.move_overhead))
+    }
+
+    /// Returns the remaining time and increment of `color`
+    const fn clock(&self, color: Color) -> (Option<u64>, Option<u64>) {
+        match color {
+            Color::White => (self.white_time, self.white_increment),
+            Color::Black => (self.black_time, self.black_increment),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_time_budget() {
+        let limits = SearchLimits::new()
+            .white_time(Some(10_000))
+            .black_time(Some(2_000))
+            .black_increment(Some(1_000));
+
+        assert_eq!(limits.time_budget(Color::White), Some(500));
+        assert_eq!(limits.time_budget(Color::Black), Some(600));
+        assert_eq!(SearchLimits::new().time_budget(Color::White), None);
+    }
+
+    #[test]
+    fn test_hard_time_limit() {
+        let limits = SearchLimits::new()
+            .white_time(Some(10_000))
+            .black_time(Some(15))
+            .black_increment(Some(2_000))
+            .move_overhead(20);
+
+        assert_eq!(limits.hard_time_limit(Color::White), Some(9_980));
+        assert_eq!(limits.hard_time_limit(Color::Black), Some(0));
+        // The increment only arrives after the move, so it cannot stretch the budget past the clock
+        assert_eq!(limits.time_budget(Color::Black), Some(0));
+        assert_eq!(SearchLimits::new().hard_time_limit(Color::Black), None);
+    }
+
+    #[test]
+    fn test_time_budget_with_movestogo() {
+        let limits = SearchLimits::new()
+            .white_time(Some(10_000))
+            .white_increment(Some(1_000))
+            .movestogo(Some(10));
+        assert_eq!(limits.time_budget(Color::White), Some(1_500));
+
+        // The last move before the time control may use everything but the move overhead
+        let limits = SearchLimits::new()
+            .black_time(Some(3_000))
+            .movestogo(Some(1));
+        assert_eq!(limits.time_budget(Color::Black), Some(2_990));
+
+        let limits = SearchLimits::new()
+            .black_time(Some(3_000))
+            .movestogo(Some(0));
+        assert_eq!(limits.time_budget(Color::Black), Some(2_990));
+    }
+
+    #[test]
+    fn test_infinite_search_has_no_time_limits() {
+        let limits = SearchLimits::new().white_time(Some(10_000)).infinite(true);
+
+        assert_eq!(limits.time_budget(Color::White), None);
+        assert_eq!(limits.hard_time_limit(Color::White), None);
+    }
 }
@@ -0,0 +1,86 @@
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+/// Somewhere the search writes its UCI output to
+pub trait Logger {
+    /// Writes a single line of output
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to write, without a trailing newline
+    fn log(&self, line: &str);
+}
+
+/// Writes every line to standard output, where the GUI reads it
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdoutLogger;
+
+impl Logger for StdoutLogger {
+    fn log(&self, line: &str) {
+        println!("{line}");
+    }
+}
+
+/// Discards every line, for searches whose output nobody reads
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullLogger;
+
+impl Logger for NullLogger {
+    fn log(&self, _line: &str) {}
+}
+
+/// Collects every line in memory, so that tests can assert on the exact output
+///
+/// Clones share the same buffer, so one clone can be handed to the search while another is kept
+/// to read the lines back.
+#[cfg(test)]
+#[derive(Clone, Debug, Default)]
+pub struct BufferLogger(Arc<Mutex<Vec<String>>>);
+
+#[cfg(test)]
+impl BufferLogger {
+    /// Returns every line written so far, oldest first
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - A copy of the lines in the buffer
+    ///
+    /// # Example
+    /// ```
+    /// let logger = BufferLogger::default();
+    /// logger.log("readyok");
+    /// assert_eq!(logger.lines(), vec!["readyok".to_string()]);
+    /// ```
+    pub fn lines(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl Logger for BufferLogger {
+    fn log(&self, line: &str) {
+        self.0.lock().unwrap().push(line.to_string());
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_buffer_logger_clones_share_lines() {
+        let logger = BufferLogger::default();
+        let sink: Box<dyn Logger + Send> = Box::new(logger.clone());
+
+        sink.log("info depth 1");
+        sink.log("bestmove e2e4");
+
+        assert_eq!(
+            logger.lines(),
+            vec!["info depth 1".to_string(), "bestmove e2e4".to_string()]
+        );
+    }
+}
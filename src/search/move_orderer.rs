@@ -0,0 +1,514 @@
+use crate::board::bitboard::Bitboard;
+use crate::board::see::see_value;
+use crate::board::{Board, Ply};
+
+/// Why a move was placed where it is in the search order, from last to first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MoveTag {
+    /// A capture that loses material according to the static exchange evaluation
+    LosingCapture,
+    /// Any other quiet move
+    Quiet,
+    /// The quiet move that last refuted the opponent's previous move
+    Countermove,
+    /// A quiet move that caused a beta cutoff at the same distance from the root
+    Killer,
+    /// A promotion, or a capture that does not lose material
+    Tactical,
+    /// The best move stored in the transposition table for the position
+    HashMove,
+}
+
+impl MoveTag {
+    /// Returns whether the move was ordered among the moves that neither capture nor promote
+    pub const fn is_quiet(self) -> bool {
+        matches!(self, Self::Quiet | Self::Countermove | Self::Killer)
+    }
+}
+
+/// Orders the moves of a node so that the most promising ones are searched first
+///
+/// The best move from the transposition table comes first. Tactical moves follow with the most
+/// valuable victims leading, then the killer moves, the countermove, every other quiet move, and
+/// finally the captures that lose material. Among the other quiet moves, those that move a
+/// threatened piece out of danger come first.
+pub struct MoveOrderer {
+    moves: std::vec::IntoIter<(Ply, MoveTag)>,
+}
+
+/// Returns whether two plies describe the same move, ignoring the state they were played in
+fn is_same_move(a: Ply, b: Ply) -> bool {
+    a.start == b.start && a.dest == b.dest && a.promoted_to == b.promoted_to
+}
+
+/// Tags a capture or promotion by whether it loses material and scores it by the most valuable
+/// victim taken with the least valuable attacker
+fn score_tactical(board: &Board, mv: Ply) -> (MoveTag, i64) {
+    let attacker = board.get_piece(mv.start).map_or(0, see_value);
+    let victim = mv.captured_piece.map_or(0, see_value);
+    let promotion = mv.promoted_to.map_or(0, see_value);
+    let mvv_lva = 10 * (victim + promotion) - attacker;
+    if mv.promoted_to.is_none() && board.see(mv) < 0 {
+        (MoveTag::LosingCapture, mvv_lva)
+    } else {
+        (MoveTag::Tactical, mvv_lva)
+    }
+}
+
+/// Scores a quiet move by whether it moves a piece out of the squares in `threats`
+fn score_quiet(threats: Bitboard, mv: Ply) -> i64 {
+    i64::from(!(threats & Bitboard::from(mv.start)).is_empty())
+}
+
+/// Returns the moves in descending order of their score, keeping the generation order of ties
+fn sorted_by_score(mut scored: Vec<(Ply, i64)>) -> Vec<Ply> {
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(mv, _)| mv).collect()
+}
+
+impl MoveOrderer {
+    /// Tags and sorts the moves of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board the moves will be played on
+    /// * `moves` - The moves to order
+    /// * `hash_move` - The best move stored in the transposition table for the position
+    /// * `killers` - The killer moves stored for the node's distance from the root
+    /// * `countermove` - The move that last refuted the opponent's previous move
+    ///
+    /// # Example
+    /// ```
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let moves = board.get_legal_moves();
+    /// let orderer = MoveOrderer::new(&board, moves, None, [None, None], None);
+    /// ```
+    pub fn new(
+        board: &Board,
+        moves: Vec<Ply>,
+        hash_move: Option<Ply>,
+        killers: [Option<Ply>; 2],
+        countermove: Option<Ply>,
+    ) -> Self {
+        let is_killer = |mv: Ply| killers.iter().flatten().any(|&k| is_same_move(k, mv));
+        let is_countermove = |mv: Ply| countermove.is_some_and(|c| is_same_move(c, mv));
+        let threats = board.threats();
+
+        let mut scored: Vec<(Ply, MoveTag, i64)> = moves
+            .into_iter()
+            .map(|mv| {
+                if hash_move.is_some_and(|hash_move| is_same_move(hash_move, mv)) {
+                    (mv, MoveTag::HashMove, 0)
+                } else if !mv.is_quiet() {
+                    let (tag, score) = score_tactical(board, mv);
+                    (mv, tag, score)
+                } else if is_killer(mv) {
+                    (mv, MoveTag::Killer, 0)
+                } else if is_countermove(mv) {
+                    (mv, MoveTag::Countermove, 0)
+                } else {
+                    (mv, MoveTag::Quiet, score_quiet(threats, mv))
+                }
+            })
+            .collect();
+        scored.sort_by_key(|&(_, tag, score)| std::cmp::Reverse((tag, score)));
+
+        Self {
+            moves: scored
+                .into_iter()
+                .map(|(mv, tag, _)| (mv, tag))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        }
+    }
+}
+
+impl Iterator for MoveOrderer {
+    type Item = (Ply, MoveTag);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.moves.next()
+    }
+}
+
+/// The phases a `StagedMoveOrderer` hands out moves in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    HashMove,
+    GenerateTactical,
+    Tactical,
+    Refutations,
+    GenerateQuiet,
+    Quiet,
+    LosingCaptures,
+    Done,
+}
+
+/// Hands out the moves of a node in the same order as `MoveOrderer`, generating each group only
+/// once the moves before it have been searched
+///
+/// Most nodes that fail high do so on the hash move or a capture, so the quiet moves are often
+/// never generated at all, and each move is only checked for legality right before it is handed
+/// out. Since the generation needs the board while the search is also making moves on it, moves
+/// are taken with `next_move` instead of through `Iterator`.
+pub struct StagedMoveOrderer {
+    stage: Stage,
+    hash_move: Option<Ply>,
+    /// The killer moves followed by the countermove, tagged as they will be handed out
+    refutations: Vec<(Ply, MoveTag)>,
+    /// The refutations that were handed out, and so must be skipped among the quiet moves
+    played_refutations: Vec<Ply>,
+    moves: std::vec::IntoIter<Ply>,
+    losing_captures: Vec<(Ply, i64)>,
+}
+
+impl StagedMoveOrderer {
+    /// Prepares to hand out the moves of a node
+    ///
+    /// # Arguments
+    ///
+    /// * `hash_move` - The best move stored in the transposition table for the position
+    /// * `killers` - The killer moves stored for the node's distance from the root
+    /// * `countermove` - The move that last refuted the opponent's previous move
+    ///
+    /// # Example
+    /// ```
+    /// let mut board = BoardBuilder::construct_starting_board().build();
+    /// let mut orderer = StagedMoveOrderer::new(None, [None, None], None);
+    /// while let Some((mv, tag)) = orderer.next_move(&mut board) {}
+    /// ```
+    pub fn new(
+        hash_move: Option<Ply>,
+        killers: [Option<Ply>; 2],
+        countermove: Option<Ply>,
+    ) -> Self {
+        // Stored last to first so that they can be popped in order
+        let refutations = countermove
+            .map(|countermove| (countermove, MoveTag::Countermove))
+            .into_iter()
+            .chain(
+                killers
+                    .into_iter()
+                    .rev()
+                    .flatten()
+                    .map(|killer| (killer, MoveTag::Killer)),
+            )
+            .collect();
+
+        Self {
+            stage: Stage::HashMove,
+            hash_move,
+            refutations,
+            played_refutations: Vec::new(),
+            moves: Vec::new().into_iter(),
+            losing_captures: Vec::new(),
+        }
+    }
+
+    /// Returns the next legal move to search and why it was placed there
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board the moves will be played on, in the position the orderer was
+    ///   created for
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Ply, MoveTag)>` - The next move, or `None` once every legal move was handed out
+    pub fn next_move(&mut self, board: &mut Board) -> Option<(Ply, MoveTag)> {
+        loop {
+            match self.stage {
+                Stage::HashMove => {
+                    self.stage = Stage::GenerateTactical;
+                    self.hash_move = self
+                        .hash_move
+                        .and_then(|hash_move| board.complete_ply(hash_move).ok())
+                        .filter(|&hash_move| board.is_legal(hash_move));
+                    if let Some(hash_move) = self.hash_move {
+                        return Some((hash_move, MoveTag::HashMove));
+                    }
+                }
+                Stage::GenerateTactical => {
+                    self.stage = Stage::Tactical;
+                    let mut winning = Vec::new();
+                    for mv in board.get_filtered_moves(|mv| !mv.is_quiet()) {
+                        if self.is_hash_move(mv) {
+                            continue;
+                        }
+                        match score_tactical(board, mv) {
+                            (MoveTag::LosingCapture, score) => {
+                                self.losing_captures.push((mv, score));
+                            }
+                            (_, score) => winning.push((mv, score)),
+                        }
+                    }
+                    self.moves = sorted_by_score(winning).into_iter();
+                }
+                Stage::Tactical => match self.moves.next() {
+                    Some(mv) if board.is_legal(mv) => return Some((mv, MoveTag::Tactical)),
+                    Some(_) => (),
+                    None => self.stage = Stage::Refutations,
+                },
+                Stage::Refutations => {
+                    let Some((refutation, tag)) = self.refutations.pop() else {
+                        self.stage = Stage::GenerateQuiet;
+                        continue;
+                    };
+                    let Ok(mv) = board.complete_ply(refutation) else {
+                        continue;
+                    };
+                    // A refutation that captures here was already handed out with the captures
+                    if mv.is_quiet()
+                        && !self.is_hash_move(mv)
+                        && !self
+                            .played_refutations
+                            .iter()
+                            .any(|&played| is_same_move(played, mv))
+                        && board.is_legal(mv)
+                    {
+                        self.played_refutations.push(mv);
+                        return Some((mv, tag));
+                    }
+                }
+                Stage::GenerateQuiet => {
+                    self.stage = Stage::Quiet;
+                    let threats = board.threats();
+                    let quiet = board
+                        .get_filtered_moves(|mv| mv.is_quiet())
+                        .into_iter()
+                        .filter(|&mv| {
+                            !self.is_hash_move(mv)
+                                && !self
+                                    .played_refutations
+                                    .iter()
+                                    .any(|&played| is_same_move(played, mv))
+                        })
+                        .map(|mv| (mv, score_quiet(threats, mv)))
+                        .collect();
+                    self.moves = sorted_by_score(quiet).into_iter();
+                }
+                Stage::Quiet => match self.moves.next() {
+                    Some(mv) if board.is_legal(mv) => return Some((mv, MoveTag::Quiet)),
+                    Some(_) => (),
+                    None => {
+                        self.stage = Stage::LosingCaptures;
+                        self.moves =
+                            sorted_by_score(std::mem::take(&mut self.losing_captures)).into_iter();
+                    }
+                },
+                Stage::LosingCaptures => match self.moves.next() {
+                    Some(mv) if board.is_legal(mv) => return Some((mv, MoveTag::LosingCapture)),
+                    Some(_) => (),
+                    None => self.stage = Stage::Done,
+                },
+                Stage::Done => return None,
+            }
+        }
+    }
+
+    /// Returns whether `mv` is the hash move that was already handed out
+    fn is_hash_move(&self, mv: Ply) -> bool {
+        self.hash_move
+            .is_some_and(|hash_move| is_same_move(hash_move, mv))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::square::Square;
+    use crate::search::transposition_table::{Bound, TTEntry, TranspositionTable};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_captures_first_most_valuable_victim() {
+        let mut board = Board::from_fen("4k3/8/2q1r3/3P4/8/8/8/K7 w - - 0 1");
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, None, [None, None], None).collect();
+
+        assert_eq!(ordered[0].0.to_string(), "d5c6");
+        assert_eq!(ordered[0].1, MoveTag::Tactical);
+        assert_eq!(ordered[1].0.to_string(), "d5e6");
+        assert_eq!(ordered[1].1, MoveTag::Tactical);
+    }
+
+    #[test]
+    fn test_killers_and_countermove_are_tagged() {
+        let mut board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+        let killer = board.find_move("e1f1").unwrap();
+        let countermove = board.find_move("e1d1").unwrap();
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, None, [Some(killer), None], Some(countermove))
+                .collect();
+
+        assert_eq!(ordered[0].1, MoveTag::Tactical);
+        assert_eq!(ordered[1], (killer, MoveTag::Killer));
+        assert_eq!(ordered[2], (countermove, MoveTag::Countermove));
+        assert!(ordered[3..].iter().all(|(_, tag)| *tag == MoveTag::Quiet));
+    }
+
+    #[test]
+    fn test_losing_captures_are_ordered_last() {
+        // Taking the pawn on d5 with the queen loses her to the e6 pawn
+        let mut board = Board::from_fen("4k3/8/4p3/3p4/8/8/3Q4/4K2N w - - 0 1");
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, None, [None, None], None).collect();
+
+        let (last, tag) = *ordered.last().unwrap();
+        assert_eq!(last.to_string(), "d2d5");
+        assert_eq!(tag, MoveTag::LosingCapture);
+        assert!(!tag.is_quiet());
+    }
+
+    #[test]
+    fn test_winning_captures_before_quiet_moves() {
+        let mut board = Board::from_fen("4k3/8/8/3p4/8/8/3Q4/4K3 w - - 0 1");
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, None, [None, None], None).collect();
+
+        assert_eq!(ordered[0].0.to_string(), "d2d5");
+        assert_eq!(ordered[0].1, MoveTag::Tactical);
+        assert!(ordered[1..].iter().all(|(_, tag)| tag.is_quiet()));
+    }
+
+    #[test]
+    fn test_hash_move_is_first() {
+        let mut board = Board::from_fen("4k3/8/2q1r3/3P4/8/8/8/K7 w - - 0 1");
+        let hash_move = board.find_move("a1b1").unwrap();
+        let killer = board.find_move("a1a2").unwrap();
+
+        let mut tt = TranspositionTable::default();
+        tt.insert(TTEntry {
+            key: board.zobrist_key(),
+            depth: 3,
+            score: 0,
+            bound: Bound::Exact,
+            best_ply: Some(hash_move),
+        });
+        let stored = tt.get(board.zobrist_key()).and_then(|entry| entry.best_ply);
+
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, stored, [Some(killer), None], None).collect();
+
+        assert_eq!(ordered[0], (hash_move, MoveTag::HashMove));
+        assert_eq!(ordered[1].1, MoveTag::Tactical);
+    }
+
+    #[test]
+    fn test_hash_move_missing_from_move_list_is_ignored() {
+        let mut board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+        let moves = board.get_legal_moves();
+        let elsewhere = Ply::new(Square::from("a1"), Square::from("a8"));
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, Some(elsewhere), [None, None], None).collect();
+
+        assert!(ordered.iter().all(|(_, tag)| *tag != MoveTag::HashMove));
+    }
+
+    #[test]
+    fn test_threatened_piece_moves_first_among_quiet_moves() {
+        let mut board = Board::from_fen("3rk3/8/8/8/3B4/8/8/N3K3 w - - 0 1");
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, None, [None, None], None).collect();
+
+        let first_quiet = ordered
+            .iter()
+            .find(|(_, tag)| *tag == MoveTag::Quiet)
+            .unwrap();
+        assert_eq!(first_quiet.0.start, Square::from("d4"));
+    }
+
+    #[test]
+    fn test_countermove_is_ordered_before_quiet_moves() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2N w - - 0 1");
+        let countermove = board.find_move("h1g3").unwrap();
+        let moves = board.get_legal_moves();
+        let ordered: Vec<(Ply, MoveTag)> =
+            MoveOrderer::new(&board, moves, None, [None, None], Some(countermove)).collect();
+
+        assert_eq!(ordered[0], (countermove, MoveTag::Countermove));
+        assert!(ordered[1..].iter().all(|(_, tag)| *tag == MoveTag::Quiet));
+    }
+
+    /// Takes every move a `StagedMoveOrderer` hands out on `board`
+    fn staged(
+        board: &mut Board,
+        hash_move: Option<Ply>,
+        killers: [Option<Ply>; 2],
+        countermove: Option<Ply>,
+    ) -> Vec<(Ply, MoveTag)> {
+        let mut orderer = StagedMoveOrderer::new(hash_move, killers, countermove);
+        let mut moves = Vec::new();
+        while let Some(next) = orderer.next_move(board) {
+            moves.push(next);
+        }
+
+        moves
+    }
+
+    #[test]
+    fn test_staged_order_matches_move_orderer() {
+        let fens = [
+            "4k3/8/2q1r3/3P4/8/8/8/K7 w - - 0 1",
+            "4k3/8/4p3/3p4/8/8/3Q4/4K2N w - - 0 1",
+            "3rk3/8/8/8/3B4/8/8/N3K3 w - - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+        ];
+        for fen in fens {
+            let mut board = Board::from_fen(fen);
+            let moves = board.get_legal_moves();
+            let hash_move = moves.last().copied();
+            let expected: Vec<(Ply, MoveTag)> =
+                MoveOrderer::new(&board, moves, hash_move, [None, None], None).collect();
+
+            assert_eq!(staged(&mut board, hash_move, [None, None], None), expected);
+        }
+    }
+
+    #[test]
+    fn test_staged_refutations_come_before_quiet_moves() {
+        let mut board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1");
+        let killer = board.find_move("e1f1").unwrap();
+        let countermove = board.find_move("e1d1").unwrap();
+        let ordered = staged(&mut board, None, [Some(killer), None], Some(countermove));
+
+        assert_eq!(ordered[0].1, MoveTag::Tactical);
+        assert_eq!(ordered[1], (killer, MoveTag::Killer));
+        assert_eq!(ordered[2], (countermove, MoveTag::Countermove));
+        assert!(ordered[3..].iter().all(|(_, tag)| *tag == MoveTag::Quiet));
+        assert_eq!(ordered.len(), board.get_legal_moves().len());
+    }
+
+    #[test]
+    fn test_staged_skips_illegal_and_capturing_refutations() {
+        // The knight on e2 is pinned, the king may take the knight on f1, and d2 is covered by it
+        let mut board = Board::from_fen("4k3/4r3/8/8/8/8/4N3/4Kn2 w - - 0 1");
+        let pinned = Ply::new(Square::from("e2"), Square::from("c3"));
+        let capturing_killer = Ply::new(Square::from("e1"), Square::from("f1"));
+        let illegal_countermove = Ply::new(Square::from("e1"), Square::from("d2"));
+        let ordered = staged(
+            &mut board,
+            Some(pinned),
+            [Some(capturing_killer), None],
+            Some(illegal_countermove),
+        );
+
+        assert_eq!(ordered[0].0.to_string(), "e1f1");
+        assert_eq!(ordered[0].1, MoveTag::Tactical);
+        assert!(ordered[1..].iter().all(|(_, tag)| *tag == MoveTag::Quiet));
+        let mut moves: Vec<Ply> = ordered.into_iter().map(|(mv, _)| mv).collect();
+        let mut legal = board.get_legal_moves();
+        moves.sort_by_key(ToString::to_string);
+        legal.sort_by_key(ToString::to_string);
+        assert_eq!(moves, legal);
+    }
+}
@@ -0,0 +1,458 @@
+use crate::board::{Board, Ply};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of entries in a table created with `TranspositionTable::default`
+const DEFAULT_ENTRIES: usize = 1 << 16;
+
+/// How the stored score relates to the true score of the position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    /// The score is exact
+    Exact,
+    /// The search failed high, so the true score is at least the stored score
+    Lower,
+    /// The search failed low, so the true score is at most the stored score
+    Upper,
+}
+
+/// The result of searching a position, kept so it can be reused when the position is reached again
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TTEntry {
+    pub key: u64,
+    pub depth: usize,
+    pub score: i64,
+    pub bound: Bound,
+    pub best_ply: Option<Ply>,
+}
+
+/// The number of entries stored with each kind of bound
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BoundCounts {
+    pub exact: usize,
+    pub lower: usize,
+    pub upper: usize,
+}
+
+/// How often the table has been probed, and how those probes turned out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProbeStats {
+    /// The number of lookups
+    pub probes: u64,
+    /// The number of lookups that found an entry for the position
+    pub hits: u64,
+    /// The number of lookups whose slot held an entry for a different position
+    pub collisions: u64,
+}
+
+/// An entry along with the generation of the search that stored it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Slot {
+    entry: TTEntry,
+    generation: u8,
+}
+
+/// A fixed-size hash table of search results indexed by Zobrist key
+#[allow(clippy::module_name_repetitions)]
+pub struct TranspositionTable {
+    entries: Vec<Option<Slot>>,
+    generation: u8,
+    // Lookups only borrow the table immutably, since searches share it behind a read lock
+    probes: AtomicU64,
+    hits: AtomicU64,
+    collisions: AtomicU64,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::with_entries(DEFAULT_ENTRIES)
+    }
+}
+
+impl TranspositionTable {
+    /// Creates an empty table
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The number of entries to allocate, rounded down to a power of two
+    ///
+    /// # Example
+    /// ```
+    /// let tt = TranspositionTable::with_entries(1024);
+    /// ```
+    pub fn with_entries(entries: usize) -> Self {
+        let entries = if entries.is_power_of_two() {
+            entries
+        } else {
+            (entries.next_power_of_two() / 2).max(1)
+        };
+
+        Self {
+            entries: vec![None; entries],
+            generation: 0,
+            probes: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            collisions: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates an empty table that takes up at most `megabytes` of memory
+    ///
+    /// # Arguments
+    ///
+    /// * `megabytes` - The memory to use, as set by the UCI `Hash` option
+    ///
+    /// # Example
+    /// ```
+    /// let tt = TranspositionTable::with_megabytes(16);
+    /// ```
+    pub fn with_megabytes(megabytes: usize) -> Self {
+        Self::with_entries(megabytes * 1024 * 1024 / size_of::<Option<Slot>>())
+    }
+
+    const fn index(&self, key: u64) -> usize {
+        #[allow(clippy::cast_possible_truncation)]
+        let key = key as usize;
+        key & (self.entries.len() - 1)
+    }
+
+    /// Returns the entry stored for the position with `key`, if there is one
+    ///
+    /// Every lookup is counted in the table's `ProbeStats`.
+    ///
+    /// # Example
+    /// ```
+    /// let tt = TranspositionTable::default();
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// let entry = tt.get(board.zobrist_key());
+    /// ```
+    pub fn get(&self, key: u64) -> Option<TTEntry> {
+        self.probes.fetch_add(1, Ordering::Relaxed);
+        let slot = self.entries[self.index(key)]?;
+        if slot.entry.key == key {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            Some(slot.entry)
+        } else {
+            self.collisions.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    /// Returns the entry stored for the position on `board`, if there is one
+    ///
+    /// # Example
+    /// ```
+    /// let tt = TranspositionTable::default();
+    /// let board = BoardBuilder::construct_starting_board().build();
+    /// assert_eq!(tt.probe(&board), None);
+    /// ```
+    pub fn probe(&self, board: &Board) -> Option<TTEntry> {
+        self.get(board.zobrist_key())
+    }
+
+    /// Stores `entry` unless its slot holds a more valuable entry from the current search
+    ///
+    /// Deeper entries took more work to find, so a shallower one only replaces them when it has
+    /// an exact score and they do not. Entries left over from previous searches are always
+    /// replaced.
+    ///
+    /// # Example
+    /// ```
+    /// let mut tt = TranspositionTable::default();
+    /// tt.insert(TTEntry { key: 1, depth: 3, score: 20, bound: Bound::Exact, best_ply: None });
+    /// ```
+    pub fn insert(&mut self, entry: TTEntry) {
+        let idx = self.index(entry.key);
+        let replace = self.entries[idx].is_none_or(|slot| {
+            slot.generation != self.generation
+                || entry.depth >= slot.entry.depth
+                || (entry.bound == Bound::Exact && slot.entry.bound != Bound::Exact)
+        });
+
+        if replace {
+            self.entries[idx] = Some(Slot {
+                entry,
+                generation: self.generation,
+            });
+        }
+    }
+
+    /// Marks the start of a new search, so that entries stored from now on can be told apart
+    /// from older ones
+    pub const fn new_generation(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Returns the number of searches started since the table was created, wrapping at 256
+    pub const fn generation(&self) -> u8 {
+        self.generation
+    }
+
+    /// Returns the number of entries the table can hold
+    pub const fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the number of slots that hold an entry
+    pub fn capacity_used(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    /// Returns how full the table is in permille, as reported by the UCI `hashfull` field
+    ///
+    /// # Example
+    /// ```
+    /// let tt = TranspositionTable::default();
+    /// assert_eq!(tt.hashfull(), 0);
+    /// ```
+    pub fn hashfull(&self) -> usize {
+        self.capacity_used() * 1000 / self.capacity()
+    }
+
+    /// Returns how the lookups made since the table was created turned out
+    ///
+    /// # Example
+    /// ```
+    /// let tt = TranspositionTable::default();
+    /// tt.get(1);
+    /// assert_eq!(tt.stats().probes, 1);
+    /// ```
+    pub fn stats(&self) -> ProbeStats {
+        ProbeStats {
+            probes: self.probes.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            collisions: self.collisions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns how many of the stored entries have each kind of bound
+    pub fn bound_counts(&self) -> BoundCounts {
+        self.entries
+            .iter()
+            .flatten()
+            .fold(BoundCounts::default(), |mut counts, slot| {
+                match slot.entry.bound {
+                    Bound::Exact => counts.exact += 1,
+                    Bound::Lower => counts.lower += 1,
+                    Bound::Upper => counts.upper += 1,
+                }
+                counts
+            })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut tt = TranspositionTable::with_entries(16);
+        let entry = TTEntry {
+            key: 0xDEAD_BEEF,
+            depth: 4,
+            score: 35,
+            bound: Bound::Exact,
+            best_ply: None,
+        };
+        tt.insert(entry);
+
+        assert_eq!(tt.get(0xDEAD_BEEF), Some(entry));
+        assert_eq!(tt.get(0xDEAD_BEEF + 16), None);
+    }
+
+    #[test]
+    fn test_probe_after_insert_counts_a_hit() {
+        let mut tt = TranspositionTable::with_entries(16);
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let entry = TTEntry {
+            key: board.zobrist_key(),
+            depth: 5,
+            score: 12,
+            bound: Bound::Lower,
+            best_ply: board.find_move("e2e4").ok(),
+        };
+        assert_eq!(tt.probe(&board), None);
+        assert_eq!(
+            tt.stats(),
+            ProbeStats {
+                probes: 1,
+                hits: 0,
+                collisions: 0
+            }
+        );
+
+        tt.insert(entry);
+        assert_eq!(tt.probe(&board), Some(entry));
+        assert_eq!(
+            tt.stats(),
+            ProbeStats {
+                probes: 2,
+                hits: 1,
+                collisions: 0
+            }
+        );
+
+        let mv = board.find_move("e2e4").unwrap();
+        board.make_move(mv);
+        assert_eq!(tt.probe(&board), None);
+    }
+
+    #[test]
+    fn test_probe_counts_collisions() {
+        let mut tt = TranspositionTable::with_entries(16);
+        tt.insert(TTEntry {
+            key: 3,
+            depth: 1,
+            score: 0,
+            bound: Bound::Exact,
+            best_ply: None,
+        });
+
+        assert_eq!(tt.get(3 + 16), None);
+        assert_eq!(tt.get(4), None);
+        assert_eq!(
+            tt.stats(),
+            ProbeStats {
+                probes: 2,
+                hits: 0,
+                collisions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_size_is_a_power_of_two() {
+        assert_eq!(TranspositionTable::with_entries(1000).entries.len(), 512);
+        assert_eq!(TranspositionTable::with_entries(1024).entries.len(), 1024);
+        assert_eq!(TranspositionTable::with_entries(0).entries.len(), 1);
+    }
+
+    #[test]
+    fn test_with_megabytes() {
+        let small = TranspositionTable::with_megabytes(1);
+        let large = TranspositionTable::with_megabytes(4);
+        let entry_size = size_of::<Option<Slot>>();
+
+        assert!(small.capacity().is_power_of_two());
+        assert!(small.capacity() * entry_size <= 1024 * 1024);
+        assert!(small.capacity() * entry_size * 2 > 1024 * 1024);
+        assert_eq!(large.capacity(), small.capacity() * 4);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut tt = TranspositionTable::with_entries(8);
+        assert_eq!(tt.capacity_used(), 0);
+        assert_eq!(tt.hashfull(), 0);
+
+        for (key, bound) in [
+            (1, Bound::Exact),
+            (2, Bound::Lower),
+            (3, Bound::Lower),
+            (4, Bound::Upper),
+        ] {
+            tt.insert(TTEntry {
+                key,
+                depth: 1,
+                score: 0,
+                bound,
+                best_ply: None,
+            });
+        }
+
+        assert_eq!(tt.capacity_used(), 4);
+        assert_eq!(tt.hashfull(), 500);
+        assert_eq!(
+            tt.bound_counts(),
+            BoundCounts {
+                exact: 1,
+                lower: 2,
+                upper: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_deep_entry_survives_shallow_insert() {
+        let mut tt = TranspositionTable::with_entries(16);
+        let deep = TTEntry {
+            key: 7,
+            depth: 8,
+            score: 120,
+            bound: Bound::Lower,
+            best_ply: None,
+        };
+        let shallow = TTEntry {
+            depth: 2,
+            score: -40,
+            ..deep
+        };
+        tt.insert(deep);
+        tt.insert(shallow);
+
+        assert_eq!(tt.get(7), Some(deep));
+    }
+
+    #[test]
+    fn test_exact_entry_replaces_deeper_bound() {
+        let mut tt = TranspositionTable::with_entries(16);
+        let deep = TTEntry {
+            key: 7,
+            depth: 8,
+            score: 120,
+            bound: Bound::Upper,
+            best_ply: None,
+        };
+        let exact = TTEntry {
+            depth: 2,
+            bound: Bound::Exact,
+            ..deep
+        };
+        tt.insert(deep);
+        tt.insert(exact);
+
+        assert_eq!(tt.get(7), Some(exact));
+    }
+
+    #[test]
+    fn test_stale_entries_are_replaced() {
+        let mut tt = TranspositionTable::with_entries(16);
+        let deep = TTEntry {
+            key: 7,
+            depth: 8,
+            score: 120,
+            bound: Bound::Exact,
+            best_ply: None,
+        };
+        let shallow = TTEntry {
+            key: 23,
+            depth: 1,
+            bound: Bound::Lower,
+            ..deep
+        };
+        tt.insert(deep);
+
+        tt.insert(shallow);
+        assert_eq!(tt.get(7), Some(deep));
+        assert_eq!(tt.get(23), None);
+
+        tt.new_generation();
+        tt.insert(shallow);
+        assert_eq!(tt.get(7), None);
+        assert_eq!(tt.get(23), Some(shallow));
+    }
+
+    #[test]
+    fn test_new_generation_wraps() {
+        let mut tt = TranspositionTable::with_entries(1);
+        for _ in 0..257 {
+            tt.new_generation();
+        }
+        assert_eq!(tt.generation(), 1);
+    }
+}
@@ -1,23 +1,58 @@
 use build_time::build_time_utc;
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
-use crate::board::{Board, BoardBuilder};
+use crate::bench::{bench, BENCH_DEPTH};
+use crate::board::{Board, BoardBuilder, Ply};
+use crate::book::Book;
 
+use crate::evaluate::params::EvalParams;
 use crate::evaluate::simple_evaluator::SimpleEvaluator;
-use crate::search::limits::SearchLimits;
+use crate::perft::perft_divide;
+use crate::search::config::Config;
+use crate::search::limits::{SearchLimits, DEFAULT_MOVE_OVERHEAD};
+use crate::search::logger::{Logger, StdoutLogger};
+use crate::search::transposition_table::TranspositionTable;
 use crate::search::Search;
 
+mod options;
+
+use options::{Options, OPTIONS};
+
 const TITLE: &str = "Rust Chess Engine";
 const AUTHOR: &str = "Brandon Harrison";
 
 const VERSION: &str = build_time_utc!("%Y.%m.%d %H:%M:%S");
 
+/// Every token that can start a new parameter of the `go` command
+const GO_KEYWORDS: [&str; 12] = [
+    "searchmoves",
+    "ponder",
+    "wtime",
+    "btime",
+    "winc",
+    "binc",
+    "movestogo",
+    "depth",
+    "nodes",
+    "mate",
+    "movetime",
+    "infinite",
+];
+
+/// The running flag, pondering flag, and thread of a search started by `go`
+type SearchHandles = (Arc<AtomicBool>, Arc<AtomicBool>, JoinHandle<()>);
+
 pub fn start() {
     let mut board = BoardBuilder::construct_starting_board().build();
     let mut search_running: Option<Arc<AtomicBool>> = None;
+    let mut search_pondering: Option<Arc<AtomicBool>> = None;
     let mut join_handle: Option<thread::JoinHandle<()>> = None;
+    let mut options = Options::new();
+    let transposition_table = Arc::new(RwLock::new(hash_table(&options)));
+    let mut book: Option<Book> = None;
 
     loop {
         let mut line = String::new();
@@ -36,9 +71,10 @@ pub fn start() {
             "isready" => println!("readyok"),
             "ucinewgame" => board = BoardBuilder::construct_starting_board().build(),
             "position" => {
-                board = load_position(&fields)
+                board = load_position(&fields, options.get_check("UCI_Chess960"))
                     .inspect_err(|e| eprintln!("Failed to set position: {e}"))
                     .unwrap_or(board);
+                claimable_draw(&board).inspect(|draw| println!("info string {draw}"));
             }
             "go" => {
                 if let Some(jh) = &join_handle {
@@ -47,8 +83,20 @@ pub fn start() {
                         continue;
                     }
                 }
-                if let Ok((new_search, new_join_handle)) = go(&board, &fields) {
+                let analyse_mode = options.get_check("UCI_AnalyseMode");
+                if let Some(ply) = book_move(book.as_ref(), &board, &fields, analyse_mode) {
+                    println!("bestmove {ply}");
+                    continue;
+                }
+                if let Ok((new_search, new_pondering, new_join_handle)) = go(
+                    &board,
+                    &fields,
+                    &options,
+                    &transposition_table,
+                    Box::new(StdoutLogger),
+                ) {
                     search_running = Some(new_search);
+                    search_pondering = Some(new_pondering);
                     join_handle = Some(new_join_handle);
                 } else {
                     eprintln!("Failed to execute go command!");
@@ -59,8 +107,44 @@ pub fn start() {
                     is_running.store(false, std::sync::atomic::Ordering::Relaxed);
                 }
             }
+            "ponderhit" => {
+                if let Some(is_pondering) = &search_pondering {
+                    is_pondering.store(false, std::sync::atomic::Ordering::Relaxed);
+                }
+            }
             "quit" => break,
-            "setoption" => println!("Not supported"),
+            "setoption" => {
+                match options.set(&fields) {
+                    Ok(Some(note)) | Err(note) => println!("info string {note}"),
+                    Ok(None) => (),
+                }
+                let book_option = fields.get(2).is_some_and(|name| {
+                    name.eq_ignore_ascii_case("OwnBook") || name.eq_ignore_ascii_case("BookFile")
+                });
+                if book_option {
+                    book = load_book(&options);
+                }
+                if fields
+                    .get(2)
+                    .is_some_and(|name| name.eq_ignore_ascii_case("Hash"))
+                {
+                    match transposition_table.write() {
+                        Ok(mut tt) => *tt = hash_table(&options),
+                        Err(_) => eprintln!("The transposition table is unavailable!"),
+                    }
+                }
+            }
+            "perft" => match fields.get(1).and_then(|depth| depth.parse().ok()) {
+                Some(depth) => {
+                    perft_divide(&mut board.clone(), depth);
+                }
+                None => println!("Expected perft <depth>"),
+            },
+            "bench" => println!("{}", bench(BENCH_DEPTH)),
+            "tt" => match transposition_table.read() {
+                Ok(tt) => println!("{}", tt_report(&tt, &board)),
+                Err(_) => eprintln!("The transposition table is unavailable!"),
+            },
             "debug" => println!("Not supported"),
             _ => println!("Invalid command!"),
         }
@@ -70,10 +154,13 @@ pub fn start() {
 fn print_engine_info() {
     println!("id name {TITLE} {VERSION}");
     println!("id author {AUTHOR}");
+    for option in &OPTIONS {
+        println!("{option}");
+    }
     println!("uciok");
 }
 
-fn load_position(fields: &[&str]) -> Result<Board, String> {
+fn load_position(fields: &[&str], chess960: bool) -> Result<Board, String> {
     let mut board = BoardBuilder::construct_starting_board().build();
     let mut idx = 1;
 
@@ -93,22 +180,188 @@ fn load_position(fields: &[&str]) -> Result<Board, String> {
         _ => return Err(format!("Unrecognized position command: {}", fields[1])),
     }
 
+    // A FEN can only imply Chess960, since its standard castling is also valid Chess960
+    if chess960 {
+        board.set_chess960(true);
+    }
+
     if fields.len() - idx >= 2 && fields[idx] == "moves" {
         idx += 1;
         for token in &fields[idx..] {
-            if let Ok(m) = board.find_move(token) {
-                board.make_move(m);
-            } else {
-                return Err(format!("Invalid move: {token}"));
-            }
+            Ply::parse(token)
+                .and_then(|ply| board.try_make_move(ply))
+                .map_err(|e| e.to_string())?;
         }
     }
 
     Ok(board)
 }
 
-fn go(board: &Board, fields: &[&str]) -> Result<(Arc<AtomicBool>, JoinHandle<()>), String> {
-    let mut limits = SearchLimits::new();
+/// Describes the draw the side to move could claim in the loaded position, if any
+///
+/// The search keeps playing drawn positions, so this lets a GUI or game harness see the result
+fn claimable_draw(board: &Board) -> Option<&'static str> {
+    if board.is_draw_by_repetition() {
+        Some("draw by threefold repetition")
+    } else if board.is_draw_by_fifty_moves() {
+        Some("draw by the fifty move rule")
+    } else {
+        None
+    }
+}
+
+/// Creates an empty transposition table of the size given by the `Hash` option in megabytes
+fn hash_table(options: &Options) -> TranspositionTable {
+    TranspositionTable::with_megabytes(usize::try_from(options.get_spin("Hash")).unwrap_or(1))
+}
+
+/// Loads the opening book named by the `BookFile` option, if `OwnBook` is enabled
+fn load_book(options: &Options) -> Option<Book> {
+    if !options.get_check("OwnBook") {
+        return None;
+    }
+
+    Book::load(&options.get_string("BookFile"))
+        .inspect_err(|e| println!("info string {e}"))
+        .ok()
+}
+
+/// Returns a move from the opening book to play instead of searching, if there is one
+///
+/// The book is not consulted for `ponder` or `infinite` searches, since the GUI decides when
+/// those end, or in analysis mode, where the user wants the engine's own evaluation.
+fn book_move(
+    book: Option<&Book>,
+    board: &Board,
+    fields: &[&str],
+    analyse_mode: bool,
+) -> Option<Ply> {
+    if analyse_mode || fields.contains(&"ponder") || fields.contains(&"infinite") {
+        return None;
+    }
+
+    book?.probe(&mut board.clone())
+}
+
+/// Reads the search parameters that can be tuned through options
+fn search_config(options: &Options) -> Config {
+    // A negative margin turns delta pruning off
+    let delta_margin = options.get_spin("DeltaMargin");
+
+    Config::new()
+        .qsearch_see_threshold(options.get_spin("QSearchSEEThreshold"))
+        .delta_margin((delta_margin >= 0).then_some(delta_margin))
+        .repetition_draw_count(u8::try_from(options.get_spin("RepetitionDrawCount")).unwrap_or(2))
+        .multipv(usize::try_from(options.get_spin("MultiPV")).unwrap_or(1))
+        .contempt(options.get_spin("Contempt"))
+}
+
+/// Reads the evaluation weights that can be tuned through options, keeping the defaults for the
+/// rest
+fn eval_params(options: &Options) -> EvalParams {
+    EvalParams {
+        queen_value: options.get_spin("QueenValue"),
+        rook_value: options.get_spin("RookValue"),
+        bishop_value: options.get_spin("BishopValue"),
+        knight_value: options.get_spin("KnightValue"),
+        pawn_value: options.get_spin("PawnValue"),
+        mobility_weight: options.get_spin("MobilityWeight"),
+        doubled_pawn_penalty: options.get_spin("DoubledPawnPenalty"),
+        isolated_pawn_penalty: options.get_spin("IsolatedPawnPenalty"),
+        passed_pawn_weight: options.get_spin("PassedPawnWeight"),
+        ..EvalParams::DEFAULT
+    }
+}
+
+/// Describes what the transposition table holds, including its entry for the position on
+/// `board`, for the non-standard `tt` command
+fn tt_report(tt: &TranspositionTable, board: &Board) -> String {
+    let bounds = tt.bound_counts();
+    // Read before probing, so the report does not count its own lookup
+    let stats = tt.stats();
+    let entry = tt.probe(board).map_or_else(
+        || "none".to_string(),
+        |entry| {
+            let best_ply = entry
+                .best_ply
+                .map_or_else(|| "none".to_string(), |ply| ply.to_string());
+            format!(
+                "depth {} score {} bound {:?} move {best_ply}",
+                entry.depth, entry.score, entry.bound
+            )
+        },
+    );
+    format!(
+        "info string tt used {} of {} hashfull {} generation {} exact {} lower {} upper {} \
+         probes {} hits {} collisions {} entry {entry}",
+        tt.capacity_used(),
+        tt.capacity(),
+        tt.hashfull(),
+        tt.generation(),
+        bounds.exact,
+        bounds.lower,
+        bounds.upper,
+        stats.probes,
+        stats.hits,
+        stats.collisions
+    )
+}
+
+fn go(
+    board: &Board,
+    fields: &[&str],
+    options: &Options,
+    transposition_table: &Arc<RwLock<TranspositionTable>>,
+    logger: Box<dyn Logger + Send>,
+) -> Result<SearchHandles, String> {
+    let limits = parse_go(board, fields, options)?;
+    let infinite = limits.infinite;
+    let evaluator = SimpleEvaluator::with_params(&eval_params(options));
+    let mut search = Search::new(board, &evaluator, Some(limits));
+    search.set_config(search_config(options));
+    search.set_transposition_table(transposition_table.clone());
+    search.set_logger(logger);
+    let is_running = search.get_running();
+    let is_pondering = search.get_pondering();
+    let (running, pondering) = (is_running.clone(), is_pondering.clone());
+    let join_handle = thread::spawn(move || {
+        let best_move = search.search(None);
+
+        // The GUI expects no bestmove until it has sent either ponderhit or stop, or only stop
+        // for an infinite search
+        while (infinite || pondering.load(std::sync::atomic::Ordering::Relaxed))
+            && running.load(std::sync::atomic::Ordering::Relaxed)
+        {
+            thread::sleep(Duration::from_millis(1));
+        }
+        search.report_best_move(best_move);
+    });
+
+    Ok((is_running, is_pondering, join_handle))
+}
+
+/// Reads the limits of a search from the fields of a `go` command
+///
+/// # Arguments
+///
+/// * `board` - The position to search, which `searchmoves` are read in
+/// * `fields` - The fields of the `go` command
+/// * `options` - The UCI options, which supply the move overhead
+///
+/// # Returns
+///
+/// * `Result<SearchLimits, String>` - The limits, or an error for an unknown parameter
+///
+/// # Example
+/// ```
+/// let board = BoardBuilder::construct_starting_board().build();
+/// let limits = parse_go(&board, &["go", "depth", "4", "movetime", "50"], &Options::new()).unwrap();
+/// assert_eq!(limits.depth, Some(4));
+/// ```
+fn parse_go(board: &Board, fields: &[&str], options: &Options) -> Result<SearchLimits, String> {
+    let mut limits = SearchLimits::new().move_overhead(
+        u64::try_from(options.get_spin("Move Overhead")).unwrap_or(DEFAULT_MOVE_OVERHEAD),
+    );
 
     let mut idx = 1;
     while idx < fields.len() {
@@ -116,8 +369,22 @@ fn go(board: &Board, fields: &[&str]) -> Result<(Arc<AtomicBool>, JoinHandle<()>
 
         #[allow(clippy::match_same_arms)]
         match token {
-            "searchmoves" => {}
-            "ponder" => {}
+            "searchmoves" => {
+                let mut position = board.clone();
+                let mut searchmoves = Vec::new();
+                while let Some(notation) = fields
+                    .get(idx + 1)
+                    .filter(|field| !GO_KEYWORDS.contains(field))
+                {
+                    match position.find_move(notation) {
+                        Ok(ply) => searchmoves.push(ply),
+                        Err(_) => eprintln!("Ignoring illegal searchmove: {notation}"),
+                    }
+                    idx += 1;
+                }
+                limits = limits.searchmoves(searchmoves);
+            }
+            "ponder" => limits = limits.ponder(true),
             "wtime" => {
                 idx += 1;
                 limits = limits.white_time(parse_value(fields[idx], token));
@@ -134,7 +401,10 @@ fn go(board: &Board, fields: &[&str]) -> Result<(Arc<AtomicBool>, JoinHandle<()>
                 idx += 1;
                 limits = limits.black_increment(parse_value(fields[idx], token));
             }
-            "movestogo" => {}
+            "movestogo" => {
+                idx += 1;
+                limits = limits.movestogo(parse_value(fields[idx], token));
+            }
             "depth" => {
                 idx += 1;
                 limits = limits.depth(parse_value(fields[idx], token));
@@ -143,28 +413,22 @@ fn go(board: &Board, fields: &[&str]) -> Result<(Arc<AtomicBool>, JoinHandle<()>
                 idx += 1;
                 limits = limits.nodes(parse_value(fields[idx], token));
             }
-            "mate" => {}
+            "mate" => {
+                idx += 1;
+                limits = limits.mate(parse_value(fields[idx], token));
+            }
             "movetime" => {
                 idx += 1;
                 limits = limits.movetime(parse_value(fields[idx], token));
             }
-            "infinite" => {
-                limits = limits.depth(None);
-            }
+            "infinite" => limits = limits.infinite(true),
             _ => return Err("Invalid go command!".to_string()),
         };
 
         idx += 1;
     }
 
-    let mut search = Search::new(board, &SimpleEvaluator::new(), Some(limits));
-    let is_running = search.get_running();
-    let join_handle = thread::spawn(move || {
-        let best_move = search.search(None);
-        println!("bestmove {best_move}");
-    });
-
-    Ok((is_running, join_handle))
+    Ok(limits)
 }
 
 fn parse_value<T>(str: &str, kind: &str) -> Option<T>
@@ -182,3 +446,487 @@ where
 
     Some(result.unwrap())
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::piece::{Color, Kind};
+    use crate::board::square::Square;
+    use crate::evaluate::Evaluator;
+    use crate::search::logger::BufferLogger;
+    use crate::search::transposition_table::{Bound, TTEntry};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_book_move() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let mut bytes = 0x463B_9618_1691_FC9C_u64.to_be_bytes().to_vec();
+        bytes.extend([0x03, 0x1C, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        let book = Book::from_bytes(&bytes).unwrap();
+
+        let ply = book_move(Some(&book), &board, &["go", "wtime", "1000"], false);
+        assert_eq!(ply.map(|ply| ply.to_string()), Some("e2e4".to_string()));
+        assert_eq!(
+            book_move(Some(&book), &board, &["go", "wtime", "1000"], true),
+            None
+        );
+        assert_eq!(
+            book_move(Some(&book), &board, &["go", "infinite"], false),
+            None
+        );
+        assert_eq!(book_move(None, &board, &["go"], false), None);
+    }
+
+    #[test]
+    fn test_claimable_draw() {
+        let fields = [
+            "position", "startpos", "moves", "g1f3", "g8f6", "f3g1", "f6g8",
+        ];
+        let board = load_position(&fields, false).unwrap();
+        assert_eq!(claimable_draw(&board), None);
+
+        let fields = [
+            "position", "startpos", "moves", "g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6",
+            "f3g1", "f6g8",
+        ];
+        let board = load_position(&fields, false).unwrap();
+        assert_eq!(claimable_draw(&board), Some("draw by threefold repetition"));
+
+        let fields = [
+            "position",
+            "fen",
+            "4k3/8/8/8/8/8/8/R3K3",
+            "w",
+            "-",
+            "-",
+            "99",
+            "80",
+            "moves",
+            "a1a2",
+        ];
+        let board = load_position(&fields, false).unwrap();
+        assert_eq!(claimable_draw(&board), Some("draw by the fifty move rule"));
+    }
+
+    #[test]
+    fn test_load_position_reports_bad_moves() {
+        let board =
+            load_position(&["position", "startpos", "moves", "e2e4", "e7e5"], false).unwrap();
+        assert_eq!(
+            board.last_move().map(Ply::to_notation),
+            Some("e7e5".to_string())
+        );
+
+        assert_eq!(
+            load_position(&["position", "startpos", "moves", "e2e4", "e4e6"], false).err(),
+            Some("Illegal move: e4e6".to_string())
+        );
+        assert_eq!(
+            load_position(&["position", "startpos", "moves", "e2"], false).err(),
+            Some("Malformed move: e2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_position_applies_promotions() {
+        let fen = [
+            "position",
+            "fen",
+            "1n2k3/P7/8/8/8/8/8/4K3",
+            "w",
+            "-",
+            "-",
+            "0",
+            "1",
+        ];
+        for (notation, kind) in [
+            ("a7a8q", Kind::Queen(Color::White)),
+            ("a7a8n", Kind::Knight(Color::White)),
+            ("a7b8r", Kind::Rook(Color::White)),
+        ] {
+            let fields = [&fen[..], &["moves", notation]].concat();
+            let board = load_position(&fields, false).unwrap();
+            let ply = board.last_move().unwrap();
+
+            assert_eq!(ply.promoted_to, Some(kind));
+            assert_eq!(board.get_piece(ply.dest), Some(kind));
+            assert_eq!(board.get_piece(Square::from("a7")), None);
+        }
+
+        let fields = [&fen[..], &["moves", "a7a8"]].concat();
+        assert_eq!(
+            load_position(&fields, false).err(),
+            Some("Move is missing a promotion piece: a7a8".to_string())
+        );
+        let fields = [&fen[..], &["moves", "a7a8k"]].concat();
+        assert_eq!(
+            load_position(&fields, false).err(),
+            Some("Malformed move: a7a8k".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_position_applies_en_passant() {
+        let fields = [
+            "position", "startpos", "moves", "e2e4", "a7a6", "e4e5", "d7d5", "e5d6",
+        ];
+        let board = load_position(&fields, false).unwrap();
+        let ply = board.last_move().unwrap();
+
+        assert!(ply.en_passant);
+        assert_eq!(ply.captured_piece, Some(Kind::Pawn(Color::Black)));
+        assert_eq!(board.get_piece(Square::from("d5")), None);
+        assert_eq!(
+            board.get_piece(Square::from("d6")),
+            Some(Kind::Pawn(Color::White))
+        );
+
+        let fen = [
+            "position",
+            "fen",
+            "4k3/8/8/3pP3/8/8/8/4K3",
+            "w",
+            "-",
+            "-",
+            "0",
+            "1",
+        ];
+        let fields = [&fen[..], &["moves", "e5d6"]].concat();
+        assert_eq!(
+            load_position(&fields, false).err(),
+            Some("Illegal move: e5d6".to_string())
+        );
+    }
+
+    #[test]
+    fn test_search_config_defaults_match_the_engine() {
+        assert_eq!(search_config(&Options::new()), Config::new());
+    }
+
+    #[test]
+    fn test_setoption_changes_delta_margin() {
+        let mut options = Options::new();
+        options
+            .set(&["setoption", "name", "DeltaMargin", "value", "350"])
+            .unwrap();
+        assert_eq!(search_config(&options).delta_margin, Some(350));
+
+        options
+            .set(&["setoption", "name", "DeltaMargin", "value", "-1"])
+            .unwrap();
+        assert_eq!(search_config(&options).delta_margin, None);
+    }
+
+    #[test]
+    fn test_setoption_changes_repetition_draw_count() {
+        let mut options = Options::new();
+        options
+            .set(&["setoption", "name", "RepetitionDrawCount", "value", "1"])
+            .unwrap();
+
+        assert_eq!(search_config(&options).repetition_draw_count, 1);
+    }
+
+    #[test]
+    fn test_eval_params_defaults_match_the_engine() {
+        assert_eq!(eval_params(&Options::new()), EvalParams::DEFAULT);
+    }
+
+    #[test]
+    fn test_setoption_changes_material_weight() {
+        // White has a queen against a rook
+        let mut board = Board::from_fen("3rk3/8/8/8/8/8/8/3QK3 w - - 0 1");
+        let mut options = Options::new();
+        let before = SimpleEvaluator::with_params(&eval_params(&options)).evaluate(&mut board);
+
+        options
+            .set(&["setoption", "name", "QueenValue", "value", "1200"])
+            .unwrap();
+        let after = SimpleEvaluator::with_params(&eval_params(&options)).evaluate(&mut board);
+
+        assert_eq!(after - before, 300);
+    }
+
+    #[test]
+    fn test_hash_option_sizes_table() {
+        let mut options = Options::new();
+        let default_capacity = hash_table(&options).capacity();
+
+        options
+            .set(&["setoption", "name", "Hash", "value", "64"])
+            .unwrap();
+        let table = hash_table(&options);
+        assert_eq!(table.capacity(), default_capacity * 4);
+        assert!(tt_report(&table, &Board::default())
+            .contains(&format!("used 0 of {}", table.capacity())));
+
+        options
+            .set(&["setoption", "name", "Hash", "value", "1"])
+            .unwrap();
+        assert_eq!(hash_table(&options).capacity(), default_capacity / 16);
+    }
+
+    #[test]
+    fn test_tt_report_after_search() {
+        let transposition_table = Arc::new(RwLock::new(TranspositionTable::with_entries(1024)));
+        let board = BoardBuilder::construct_starting_board().build();
+        assert_eq!(
+            tt_report(&transposition_table.read().unwrap(), &board),
+            "info string tt used 0 of 1024 hashfull 0 generation 0 exact 0 lower 0 upper 0 \
+             probes 0 hits 0 collisions 0 entry none"
+        );
+
+        let mut search = Search::new(&board, &SimpleEvaluator::new(), None);
+        search.set_transposition_table(transposition_table.clone());
+        search.search(Some(3));
+
+        let (report, used, bounds, stats) = {
+            let tt = transposition_table.read().unwrap();
+            (
+                tt_report(&tt, &board),
+                tt.capacity_used(),
+                tt.bound_counts(),
+                tt.stats(),
+            )
+        };
+        assert!(used > 0);
+        assert_eq!(bounds.exact + bounds.lower + bounds.upper, used);
+        assert!(bounds.lower > 0 && bounds.upper > 0);
+        assert!(report.contains(&format!("used {used} of 1024")));
+        assert!(report.contains("generation 1"));
+        assert!(stats.hits > 0 && stats.hits <= stats.probes);
+    }
+
+    #[test]
+    fn test_tt_report_describes_the_current_position() {
+        let mut tt = TranspositionTable::with_entries(1024);
+        let mut board = BoardBuilder::construct_starting_board().build();
+        tt.insert(TTEntry {
+            key: board.zobrist_key(),
+            depth: 4,
+            score: 30,
+            bound: Bound::Lower,
+            best_ply: board.find_move("e2e4").ok(),
+        });
+
+        let report = tt_report(&tt, &board);
+        assert!(
+            report.ends_with(" entry depth 4 score 30 bound Lower move e2e4"),
+            "{report}"
+        );
+        assert!(
+            report.contains(" probes 0 hits 0 collisions 0 "),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn test_go_when_mated_writes_bestmove_none() {
+        let board = Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1");
+        let transposition_table = Arc::new(RwLock::new(TranspositionTable::with_entries(1024)));
+        let logger = BufferLogger::default();
+
+        let (_, _, join_handle) = go(
+            &board,
+            &["go", "wtime", "1000", "btime", "1000"],
+            &Options::new(),
+            &transposition_table,
+            Box::new(logger.clone()),
+        )
+        .unwrap();
+        join_handle.join().unwrap();
+
+        let lines = logger.lines();
+        assert_eq!(lines[lines.len() - 2], "info string checkmate");
+        assert_eq!(lines[lines.len() - 1], "bestmove (none)");
+    }
+
+    #[test]
+    fn test_parse_go_reads_every_limit() {
+        let board = BoardBuilder::construct_starting_board().build();
+        let fields = [
+            "go",
+            "wtime",
+            "60000",
+            "btime",
+            "50000",
+            "winc",
+            "1000",
+            "binc",
+            "900",
+            "movestogo",
+            "30",
+            "depth",
+            "4",
+            "nodes",
+            "5000",
+            "movetime",
+            "50",
+            "mate",
+            "3",
+        ];
+        let limits = parse_go(&board, &fields, &Options::new()).unwrap();
+
+        assert_eq!(limits.white_time, Some(60_000));
+        assert_eq!(limits.black_time, Some(50_000));
+        assert_eq!(limits.white_increment, Some(1_000));
+        assert_eq!(limits.black_increment, Some(900));
+        assert_eq!(limits.movestogo, Some(30));
+        assert_eq!(limits.depth, Some(4));
+        assert_eq!(limits.nodes, Some(5_000));
+        assert_eq!(limits.movetime, Some(50));
+        assert_eq!(limits.mate, Some(3));
+        assert!(!limits.infinite);
+        assert!(parse_go(&board, &["go", "sideways"], &Options::new()).is_err());
+    }
+
+    /// Runs `go` with `fields` on `fen` and returns the deepest reported iteration along with
+    /// the bestmove line
+    fn run_go(fen: &str, fields: &[&str]) -> (usize, String) {
+        let mut board = Board::from_fen(fen);
+        // The attack tables are built the first time moves are generated, which should not count
+        // against a movetime
+        board.get_legal_moves();
+        let transposition_table = Arc::new(RwLock::new(TranspositionTable::with_entries(1024)));
+        let logger = BufferLogger::default();
+
+        let (_, _, join_handle) = go(
+            &board,
+            fields,
+            &Options::new(),
+            &transposition_table,
+            Box::new(logger.clone()),
+        )
+        .unwrap();
+        join_handle.join().unwrap();
+
+        let lines = logger.lines();
+        let deepest = lines
+            .iter()
+            .filter_map(|line| line.strip_prefix("info depth "))
+            .filter_map(|rest| rest.split_whitespace().next()?.parse().ok())
+            .max()
+            .unwrap_or(0);
+
+        (deepest, lines.last().cloned().unwrap_or_default())
+    }
+
+    #[test]
+    fn test_go_mate_finds_mate_in_two() {
+        // 1. Nf6+ gxf6 2. Bxf7#
+        const FEN: &str = "r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 1";
+
+        let (deepest, bestmove) = run_go(FEN, &["go", "mate", "2"]);
+        assert!(deepest <= 3, "{deepest}");
+        assert!(bestmove.starts_with("bestmove d5f6"), "{bestmove}");
+    }
+
+    #[test]
+    fn test_go_single_limits() {
+        const FEN: &str = "4k3/8/8/8/8/8/3PP3/4K3 w - - 0 1";
+
+        let (deepest, bestmove) = run_go(FEN, &["go", "depth", "3"]);
+        assert_eq!(deepest, 3);
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+
+        let (deepest, bestmove) = run_go(FEN, &["go", "depth", "8"]);
+        assert_eq!(deepest, 8);
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+
+        let start = std::time::Instant::now();
+        let (_, bestmove) = run_go(FEN, &["go", "movetime", "50"]);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+
+        let (_, bestmove) = run_go(FEN, &["go", "nodes", "2000"]);
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+
+        let start = std::time::Instant::now();
+        let (_, bestmove) = run_go(FEN, &["go", "wtime", "1000", "btime", "1000"]);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+    }
+
+    #[test]
+    fn test_go_depth_binds_before_movetime() {
+        let (deepest, bestmove) = run_go(
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+            &["go", "depth", "4", "movetime", "50"],
+        );
+
+        assert_eq!(deepest, 4);
+        assert!(bestmove.starts_with("bestmove "), "{bestmove}");
+    }
+
+    #[test]
+    fn test_go_infinite_runs_until_stop() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let transposition_table = Arc::new(RwLock::new(TranspositionTable::with_entries(1024)));
+        let logger = BufferLogger::default();
+
+        let (running, _, join_handle) = go(
+            &board,
+            &["go", "infinite", "wtime", "10", "depth", "1"],
+            &Options::new(),
+            &transposition_table,
+            Box::new(logger.clone()),
+        )
+        .unwrap();
+        thread::sleep(Duration::from_millis(200));
+        assert!(!join_handle.is_finished());
+        assert!(logger
+            .lines()
+            .iter()
+            .any(|line| line.starts_with("info depth 1 ")));
+        assert!(!logger
+            .lines()
+            .iter()
+            .any(|line| line.starts_with("bestmove")));
+
+        running.store(false, std::sync::atomic::Ordering::Relaxed);
+        join_handle.join().unwrap();
+
+        let lines = logger.lines();
+        let bestmove = lines.last().unwrap();
+        let tokens: Vec<&str> = bestmove.split_whitespace().collect();
+        assert_eq!(tokens[0], "bestmove");
+        assert!(board.find_move(tokens[1]).is_ok(), "{bestmove}");
+    }
+
+    #[test]
+    fn test_go_depth_writes_bestmove_to_logger() {
+        let mut board = BoardBuilder::construct_starting_board().build();
+        let transposition_table = Arc::new(RwLock::new(TranspositionTable::with_entries(1024)));
+        let logger = BufferLogger::default();
+
+        let (_, _, join_handle) = go(
+            &board,
+            &["go", "depth", "2"],
+            &Options::new(),
+            &transposition_table,
+            Box::new(logger.clone()),
+        )
+        .unwrap();
+        join_handle.join().unwrap();
+
+        let lines = logger.lines();
+        let bestmove = lines.last().unwrap();
+        let tokens: Vec<&str> = bestmove.split_whitespace().collect();
+        assert_eq!(tokens[0], "bestmove");
+        assert!(board.find_move(tokens[1]).is_ok(), "{bestmove}");
+        assert!(
+            tokens.len() == 2 || (tokens.len() == 4 && tokens[2] == "ponder"),
+            "{bestmove}"
+        );
+        assert!(lines.iter().any(|line| line.starts_with("info depth 2 ")));
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.starts_with("bestmove"))
+                .count(),
+            1
+        );
+    }
+}
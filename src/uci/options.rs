@@ -0,0 +1,595 @@
+use crate::evaluate::params::EvalParams;
+use std::fmt;
+
+/// How a spin option reacts to a value outside of its `min..=max` range
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutOfRange {
+    Clamp,
+    Reject,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Spin {
+        default: i64,
+        min: i64,
+        max: i64,
+        out_of_range: OutOfRange,
+    },
+    Check {
+        default: bool,
+    },
+    String {
+        default: &'static str,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Spin(i64),
+    Check(bool),
+    String(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UciOption {
+    pub name: &'static str,
+    pub kind: Kind,
+}
+
+/// Every option the engine advertises in response to the `uci` command
+pub const OPTIONS: [UciOption; 22] = [
+    UciOption {
+        name: "Hash",
+        kind: Kind::Spin {
+            default: 16,
+            min: 1,
+            max: 1024,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "Threads",
+        kind: Kind::Spin {
+            default: 1,
+            min: 1,
+            max: 1,
+            out_of_range: OutOfRange::Reject,
+        },
+    },
+    UciOption {
+        name: "MultiPV",
+        kind: Kind::Spin {
+            default: 1,
+            min: 1,
+            max: 256,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "QSearchSEEThreshold",
+        kind: Kind::Spin {
+            default: 0,
+            min: -10_000,
+            max: 10_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "DeltaMargin",
+        kind: Kind::Spin {
+            default: 200,
+            min: -1,
+            max: 10_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "RepetitionDrawCount",
+        kind: Kind::Spin {
+            default: 2,
+            min: 1,
+            max: 2,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "OwnBook",
+        kind: Kind::Check { default: false },
+    },
+    UciOption {
+        name: "BookFile",
+        kind: Kind::String {
+            default: "book.bin",
+        },
+    },
+    UciOption {
+        name: "Ponder",
+        kind: Kind::Check { default: false },
+    },
+    UciOption {
+        name: "UCI_Chess960",
+        kind: Kind::Check { default: false },
+    },
+    UciOption {
+        name: "Move Overhead",
+        kind: Kind::Spin {
+            default: 10,
+            min: 0,
+            max: 5_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "UCI_AnalyseMode",
+        kind: Kind::Check { default: false },
+    },
+    UciOption {
+        name: "Contempt",
+        kind: Kind::Spin {
+            default: 0,
+            min: -1_000,
+            max: 1_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "QueenValue",
+        kind: Kind::Spin {
+            default: EvalParams::DEFAULT.queen_value,
+            min: 0,
+            max: 5_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "RookValue",
+        kind: Kind::Spin {
+            default: EvalParams::DEFAULT.rook_value,
+            min: 0,
+            max: 5_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "BishopValue",
+        kind: Kind::Spin {
+            default: EvalParams::DEFAULT.bishop_value,
+            min: 0,
+            max: 5_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "KnightValue",
+        kind: Kind::Spin {
+            default: EvalParams::DEFAULT.knight_value,
+            min: 0,
+            max: 5_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "PawnValue",
+        kind: Kind::Spin {
+            default: EvalParams::DEFAULT.pawn_value,
+            min: 0,
+            max: 5_000,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "MobilityWeight",
+        kind: Kind::Spin {
+            default: 1,
+            min: 0,
+            max: 100,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "DoubledPawnPenalty",
+        kind: Kind::Spin {
+            default: 15,
+            min: 0,
+            max: 500,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "IsolatedPawnPenalty",
+        kind: Kind::Spin {
+            default: 10,
+            min: 0,
+            max: 500,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+    UciOption {
+        name: "PassedPawnWeight",
+        kind: Kind::Spin {
+            default: 1,
+            min: 0,
+            max: 100,
+            out_of_range: OutOfRange::Clamp,
+        },
+    },
+];
+
+impl UciOption {
+    /// Returns the value the option holds before any `setoption` is received
+    pub fn default_value(&self) -> Value {
+        match self.kind {
+            Kind::Spin { default, .. } => Value::Spin(default),
+            Kind::Check { default } => Value::Check(default),
+            Kind::String { default } => Value::String(default.to_string()),
+        }
+    }
+
+    /// Parses and validates a raw `setoption` value against this option's definition
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The text following `value` in the `setoption` command
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Value, Option<String>), String>` - The accepted value, along with a note if the
+    ///   value had to be adjusted, or a message explaining why the value was rejected
+    ///
+    /// # Example
+    /// ```
+    /// let (value, note) = OPTIONS[0].validate("99999").unwrap();
+    /// ```
+    pub fn validate(&self, raw: &str) -> Result<(Value, Option<String>), String> {
+        match self.kind {
+            Kind::Spin {
+                min,
+                max,
+                out_of_range,
+                ..
+            } => {
+                let parsed: i64 = raw.parse().map_err(|_| {
+                    format!(
+                        "Invalid value \"{raw}\" for {}, expected an integer",
+                        self.name
+                    )
+                })?;
+
+                if (min..=max).contains(&parsed) {
+                    return Ok((Value::Spin(parsed), None));
+                }
+
+                match out_of_range {
+                    OutOfRange::Clamp => {
+                        let clamped = parsed.clamp(min, max);
+                        Ok((
+                            Value::Spin(clamped),
+                            Some(format!(
+                                "{} value {parsed} is out of range [{min}, {max}], using {clamped}",
+                                self.name
+                            )),
+                        ))
+                    }
+                    OutOfRange::Reject => Err(format!(
+                        "{} value {parsed} is out of range [{min}, {max}], ignoring",
+                        self.name
+                    )),
+                }
+            }
+            Kind::Check { .. } => match raw {
+                "true" => Ok((Value::Check(true), None)),
+                "false" => Ok((Value::Check(false), None)),
+                _ => Err(format!(
+                    "Invalid value \"{raw}\" for {}, expected true or false",
+                    self.name
+                )),
+            },
+            Kind::String { .. } => Ok((Value::String(raw.to_string()), None)),
+        }
+    }
+}
+
+impl fmt::Display for UciOption {
+    /// Formats the option the way it is advertised after the `uci` command
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            Kind::Spin {
+                default, min, max, ..
+            } => write!(
+                f,
+                "option name {} type spin default {default} min {min} max {max}",
+                self.name
+            ),
+            Kind::Check { default } => {
+                write!(f, "option name {} type check default {default}", self.name)
+            }
+            Kind::String { default } => {
+                write!(f, "option name {} type string default {default}", self.name)
+            }
+        }
+    }
+}
+
+/// The current value of every option in `OPTIONS`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    values: [Value; OPTIONS.len()],
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Self {
+            values: OPTIONS.map(|option| option.default_value()),
+        }
+    }
+
+    /// Returns the current value of the option called `name`, if it exists
+    pub fn get(&self, name: &str) -> Option<Value> {
+        OPTIONS
+            .iter()
+            .position(|option| option.name.eq_ignore_ascii_case(name))
+            .map(|idx| self.values[idx].clone())
+    }
+
+    /// Returns the current value of the spin option called `name`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no spin option called `name`
+    pub fn get_spin(&self, name: &str) -> i64 {
+        match self.get(name) {
+            Some(Value::Spin(value)) => value,
+            _ => panic!("No spin option named {name}"),
+        }
+    }
+
+    /// Returns the current value of the check option called `name`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no check option called `name`
+    pub fn get_check(&self, name: &str) -> bool {
+        match self.get(name) {
+            Some(Value::Check(value)) => value,
+            _ => panic!("No check option named {name}"),
+        }
+    }
+
+    /// Returns the current value of the string option called `name`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no string option called `name`
+    pub fn get_string(&self, name: &str) -> String {
+        match self.get(name) {
+            Some(Value::String(value)) => value,
+            _ => panic!("No string option named {name}"),
+        }
+    }
+
+    /// Handles a full `setoption name <id> [value <x>]` command
+    ///
+    /// Option names are matched case-insensitively and may contain spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - The whitespace separated tokens of the command, including `setoption`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<String>, String>` - A note to report if the value was adjusted, or a
+    ///   message explaining why the command was rejected
+    ///
+    /// # Example
+    /// ```
+    /// let mut options = Options::new();
+    /// options.set(&["setoption", "name", "Hash", "value", "64"]).unwrap();
+    /// ```
+    pub fn set(&mut self, fields: &[&str]) -> Result<Option<String>, String> {
+        if fields.get(1) != Some(&"name") {
+            return Err("Expected setoption name <id> [value <x>]".to_string());
+        }
+
+        let value_idx = fields.iter().position(|&field| field == "value");
+        let name = fields[2..value_idx.unwrap_or(fields.len())].join(" ");
+        let raw = value_idx.map(|idx| fields[idx + 1..].join(" "));
+
+        let idx = OPTIONS
+            .iter()
+            .position(|option| option.name.eq_ignore_ascii_case(&name))
+            .ok_or_else(|| format!("Unknown option: {name}"))?;
+        let raw = raw.ok_or_else(|| format!("No value given for {}", OPTIONS[idx].name))?;
+
+        let (value, note) = OPTIONS[idx].validate(&raw)?;
+        self.values[idx] = value;
+
+        Ok(note)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_defaults() {
+        let options = Options::new();
+        assert_eq!(options.get_spin("Hash"), 16);
+        assert_eq!(options.get_spin("Threads"), 1);
+        assert_eq!(options.get("Nonexistent"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            OPTIONS[0].to_string(),
+            "option name Hash type spin default 16 min 1 max 1024"
+        );
+    }
+
+    #[test]
+    fn test_set_in_range() {
+        let mut options = Options::new();
+        let result = options.set(&["setoption", "name", "hash", "value", "64"]);
+
+        assert_eq!(result, Ok(None));
+        assert_eq!(options.get_spin("Hash"), 64);
+    }
+
+    #[test]
+    fn test_set_hash_out_of_range_is_clamped() {
+        let mut options = Options::new();
+        let result = options.set(&["setoption", "name", "Hash", "value", "99999"]);
+
+        assert!(result.is_ok_and(|note| note.is_some()));
+        assert_eq!(options.get_spin("Hash"), 1024);
+
+        let result = options.set(&["setoption", "name", "Hash", "value", "0"]);
+        assert!(result.is_ok_and(|note| note.is_some()));
+        assert_eq!(options.get_spin("Hash"), 1);
+    }
+
+    #[test]
+    fn test_set_bad_threads_is_rejected() {
+        let mut options = Options::new();
+
+        let result = options.set(&["setoption", "name", "Threads", "value", "0"]);
+        assert_eq!(
+            result,
+            Err("Threads value 0 is out of range [1, 1], ignoring".to_string())
+        );
+        assert_eq!(options.get_spin("Threads"), 1);
+
+        let result = options.set(&["setoption", "name", "Threads", "value", "two"]);
+        assert!(result.is_err());
+        assert_eq!(options.get_spin("Threads"), 1);
+    }
+
+    #[test]
+    fn test_set_book_options() {
+        let mut options = Options::new();
+        assert!(!options.get_check("OwnBook"));
+        assert_eq!(options.get_string("BookFile"), "book.bin");
+
+        let result = options.set(&["setoption", "name", "OwnBook", "value", "true"]);
+        assert_eq!(result, Ok(None));
+        let result = options.set(&["setoption", "name", "BookFile", "value", "my", "book.bin"]);
+        assert_eq!(result, Ok(None));
+
+        assert!(options.get_check("OwnBook"));
+        assert_eq!(options.get_string("BookFile"), "my book.bin");
+        assert_eq!(
+            OPTIONS[7].to_string(),
+            "option name BookFile type string default book.bin"
+        );
+    }
+
+    #[test]
+    fn test_set_malformed() {
+        let mut options = Options::new();
+        assert!(options.set(&["setoption"]).is_err());
+        assert!(options.set(&["setoption", "name", "Hash"]).is_err());
+        assert!(options
+            .set(&["setoption", "name", "SyzygyPath", "value", "/tb"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_set_ponder() {
+        let mut options = Options::new();
+        assert!(!options.get_check("Ponder"));
+
+        let result = options.set(&["setoption", "name", "Ponder", "value", "true"]);
+        assert_eq!(result, Ok(None));
+        assert!(options.get_check("Ponder"));
+        assert_eq!(
+            OPTIONS[8].to_string(),
+            "option name Ponder type check default false"
+        );
+    }
+
+    #[test]
+    fn test_set_chess960() {
+        let mut options = Options::new();
+        assert!(!options.get_check("UCI_Chess960"));
+
+        let result = options.set(&["setoption", "name", "UCI_Chess960", "value", "true"]);
+        assert_eq!(result, Ok(None));
+        assert!(options.get_check("UCI_Chess960"));
+        assert_eq!(
+            OPTIONS[9].to_string(),
+            "option name UCI_Chess960 type check default false"
+        );
+    }
+
+    #[test]
+    fn test_set_move_overhead() {
+        let mut options = Options::new();
+        assert_eq!(options.get_spin("Move Overhead"), 10);
+
+        let result = options.set(&["setoption", "name", "Move", "Overhead", "value", "250"]);
+        assert_eq!(result, Ok(None));
+        assert_eq!(options.get_spin("Move Overhead"), 250);
+        assert_eq!(
+            OPTIONS[10].to_string(),
+            "option name Move Overhead type spin default 10 min 0 max 5000"
+        );
+    }
+
+    #[test]
+    fn test_set_analyse_mode() {
+        let mut options = Options::new();
+        assert!(!options.get_check("UCI_AnalyseMode"));
+
+        let result = options.set(&["setoption", "name", "UCI_AnalyseMode", "value", "true"]);
+        assert_eq!(result, Ok(None));
+        assert!(options.get_check("UCI_AnalyseMode"));
+        assert_eq!(
+            OPTIONS[11].to_string(),
+            "option name UCI_AnalyseMode type check default false"
+        );
+    }
+
+    #[test]
+    fn test_set_contempt() {
+        let mut options = Options::new();
+        assert_eq!(options.get_spin("Contempt"), 0);
+
+        let result = options.set(&["setoption", "name", "Contempt", "value", "-40"]);
+        assert_eq!(result, Ok(None));
+        assert_eq!(options.get_spin("Contempt"), -40);
+        assert_eq!(
+            OPTIONS[12].to_string(),
+            "option name Contempt type spin default 0 min -1000 max 1000"
+        );
+    }
+
+    #[test]
+    fn test_evaluation_weight_options() {
+        let mut options = Options::new();
+        assert_eq!(options.get_spin("PawnValue"), 100);
+
+        let result = options.set(&["setoption", "name", "PawnValue", "value", "9000"]);
+        assert_eq!(
+            result,
+            Ok(Some(
+                "PawnValue value 9000 is out of range [0, 5000], using 5000".to_string()
+            ))
+        );
+        assert_eq!(options.get_spin("PawnValue"), 5_000);
+        assert_eq!(
+            OPTIONS[18].to_string(),
+            "option name MobilityWeight type spin default 1 min 0 max 100"
+        );
+    }
+}